@@ -13,7 +13,7 @@ use cubek::{
         AttentionElems, AttentionIdent, AttentionPrecision, AttentionProblem, attention_types::*,
     },
     attention::launch::Strategy,
-    random::random_uniform,
+    random::{RandomGenerator, random_uniform},
 };
 use std::marker::PhantomData;
 
@@ -42,24 +42,40 @@ impl<R: Runtime, AP: AttentionPrecision> Benchmark for AttentionBench<R, AP> {
     fn prepare(&self) -> Self::Input {
         let client = R::client(&self.device);
 
+        let mut generator = RandomGenerator::new(0);
+
         fn make_random<R: Runtime, T: Numeric>(
             client: &ComputeClient<R>,
+            generator: &mut RandomGenerator,
             shape: Vec<usize>,
         ) -> TensorHandle<R> {
             let dtype = T::as_type_native_unchecked();
             let tensor = TensorHandle::empty(client, shape, dtype);
-            random_uniform(client, 0., 1., tensor.as_ref(), dtype).unwrap();
+            random_uniform(client, generator, 0., 1., tensor.as_ref(), dtype).unwrap();
             tensor
         }
 
-        let query =
-            make_random::<R, QG<AP>>(&client, self.problem.shape(AttentionIdent::Query).to_vec());
-        let key =
-            make_random::<R, KG<AP>>(&client, self.problem.shape(AttentionIdent::Key).to_vec());
-        let value =
-            make_random::<R, VG<AP>>(&client, self.problem.shape(AttentionIdent::Value).to_vec());
+        let query = make_random::<R, QG<AP>>(
+            &client,
+            &mut generator,
+            self.problem.shape(AttentionIdent::Query).to_vec(),
+        );
+        let key = make_random::<R, KG<AP>>(
+            &client,
+            &mut generator,
+            self.problem.shape(AttentionIdent::Key).to_vec(),
+        );
+        let value = make_random::<R, VG<AP>>(
+            &client,
+            &mut generator,
+            self.problem.shape(AttentionIdent::Value).to_vec(),
+        );
         let mask = self.problem.masked.then(|| {
-            make_random::<R, MSK<AP>>(&client, self.problem.shape(AttentionIdent::Mask).to_vec())
+            make_random::<R, MSK<AP>>(
+                &client,
+                &mut generator,
+                self.problem.shape(AttentionIdent::Mask).to_vec(),
+            )
         });
 
         AttentionInputs {
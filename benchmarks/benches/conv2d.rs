@@ -14,7 +14,7 @@ use cubek::{
         definition::{AccG, AccR, LhsG, LhsS, MatmulElems, MatmulPrecision, RhsG},
         launch::MatmulInputHandleRef,
     },
-    random::random_uniform,
+    random::{RandomGenerator, random_uniform},
 };
 use std::marker::PhantomData;
 
@@ -24,6 +24,7 @@ impl<R: Runtime, MP: MatmulPrecision> Benchmark for Conv2dBench<R, MP> {
 
     fn prepare(&self) -> Self::Input {
         let client = R::client(&self.device);
+        let mut generator = RandomGenerator::new(0);
 
         let input = TensorHandle::empty(
             &client,
@@ -32,6 +33,7 @@ impl<R: Runtime, MP: MatmulPrecision> Benchmark for Conv2dBench<R, MP> {
         );
         random_uniform(
             &client,
+            &mut generator,
             0.0,
             1.0,
             input.as_ref(),
@@ -45,6 +47,7 @@ impl<R: Runtime, MP: MatmulPrecision> Benchmark for Conv2dBench<R, MP> {
         );
         random_uniform(
             &client,
+            &mut generator,
             0.0,
             1.0,
             weight.as_ref(),
@@ -58,6 +61,7 @@ impl<R: Runtime, MP: MatmulPrecision> Benchmark for Conv2dBench<R, MP> {
         );
         random_uniform(
             &client,
+            &mut generator,
             0.0,
             1.0,
             bias.as_ref(),
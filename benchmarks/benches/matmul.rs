@@ -21,7 +21,7 @@ use cubek::{
             simple::SimpleArgs, simple_unit::SimpleUnitSelectionArgs,
         },
     },
-    random::random_uniform,
+    random::{RandomGenerator, random_uniform},
 };
 use std::collections::BTreeMap;
 
@@ -31,6 +31,7 @@ impl<R: Runtime> Benchmark for MatmulBench<R> {
 
     fn prepare(&self) -> Self::Input {
         let client = R::client(&self.device);
+        let mut generator = RandomGenerator::new(0);
 
         let mut lhs = TensorHandle::empty(
             &client,
@@ -41,7 +42,15 @@ impl<R: Runtime> Benchmark for MatmulBench<R> {
             let len = lhs.shape.len();
             lhs.strides.swap(len - 2, len - 1);
         }
-        random_uniform(&client, 0.0, 1.0, lhs.as_ref(), self.dtypes.lhs_global).unwrap();
+        random_uniform(
+            &client,
+            &mut generator,
+            0.0,
+            1.0,
+            lhs.as_ref(),
+            self.dtypes.lhs_global,
+        )
+        .unwrap();
 
         let mut rhs = TensorHandle::empty(
             &client,
@@ -54,7 +63,15 @@ impl<R: Runtime> Benchmark for MatmulBench<R> {
             rhs.strides.swap(len - 2, len - 1);
         }
 
-        random_uniform(&client, 0.0, 1.1, rhs.as_ref(), self.dtypes.rhs_global).unwrap();
+        random_uniform(
+            &client,
+            &mut generator,
+            0.0,
+            1.1,
+            rhs.as_ref(),
+            self.dtypes.rhs_global,
+        )
+        .unwrap();
 
         (
             MatmulInputHandle::Normal(lhs),
@@ -5,7 +5,7 @@ use cubecl::{
     prelude::{barrier::Barrier, *},
     std::tensor::TensorHandle,
 };
-use cubek::random::random_uniform;
+use cubek::random::{RandomGenerator, random_uniform};
 use std::marker::PhantomData;
 
 #[cube]
@@ -728,19 +728,36 @@ impl<R: Runtime, E: Float> Benchmark for MemcpyAsyncBench<R, E> {
 
     fn prepare(&self) -> Self::Input {
         let client = R::client(&self.device);
+        let mut generator = RandomGenerator::new(0);
 
         let a = TensorHandle::empty(
             &client,
             vec![self.data_count],
             E::as_type_native_unchecked(),
         );
-        random_uniform(&client, 0., 1., a.as_ref(), E::as_type_native_unchecked()).unwrap();
+        random_uniform(
+            &client,
+            &mut generator,
+            0.,
+            1.,
+            a.as_ref(),
+            E::as_type_native_unchecked(),
+        )
+        .unwrap();
         let b = TensorHandle::empty(
             &client,
             vec![self.window_size],
             E::as_type_native_unchecked(),
         );
-        random_uniform(&client, 0., 1., b.as_ref(), E::as_type_native_unchecked()).unwrap();
+        random_uniform(
+            &client,
+            &mut generator,
+            0.,
+            1.,
+            b.as_ref(),
+            E::as_type_native_unchecked(),
+        )
+        .unwrap();
 
         (a, b)
     }
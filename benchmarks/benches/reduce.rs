@@ -5,7 +5,7 @@ use cubecl::{
     std::tensor::TensorHandle,
 };
 use cubek::{
-    random::random_uniform,
+    random::{RandomGenerator, random_uniform},
     reduce::{
         components::instructions::ReduceOperationConfig,
         launch::{LineSizeStrategy, ReduceStrategy, RoutineStrategy},
@@ -33,9 +33,10 @@ impl<R: Runtime, E: Float> Benchmark for ReduceBench<R, E> {
     fn prepare(&self) -> Self::Input {
         let client = R::client(&self.device);
         let elem = E::as_type_native_unchecked();
+        let mut generator = RandomGenerator::new(0);
 
         let input = TensorHandle::empty(&client, self.shape.clone(), elem);
-        random_uniform(&client, 0., 1., input.as_ref(), elem).unwrap();
+        random_uniform(&client, &mut generator, 0., 1., input.as_ref(), elem).unwrap();
         let mut shape_out = self.shape.clone();
         shape_out[self.axis] = 1;
         let out = TensorHandle::empty(&client, shape_out, elem);
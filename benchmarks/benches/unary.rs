@@ -4,7 +4,7 @@ use cubecl::{
     prelude::*,
     std::tensor::TensorHandle,
 };
-use cubek::random::random_uniform;
+use cubek::random::{RandomGenerator, random_uniform};
 use std::marker::PhantomData;
 
 #[cube(launch)]
@@ -27,13 +27,14 @@ impl<R: Runtime, E: Float> Benchmark for UnaryBench<R, E> {
     fn prepare(&self) -> Self::Input {
         let client = R::client(&self.device);
         let elem = E::as_type_native_unchecked();
+        let mut generator = RandomGenerator::new(0);
 
         let lhs = TensorHandle::empty(&client, self.shape.clone(), elem);
-        random_uniform(&client, 0., 1., lhs.as_ref(), elem).unwrap();
+        random_uniform(&client, &mut generator, 0., 1., lhs.as_ref(), elem).unwrap();
         let rhs = TensorHandle::empty(&client, self.shape.clone(), elem);
-        random_uniform(&client, 0., 1., rhs.as_ref(), elem).unwrap();
+        random_uniform(&client, &mut generator, 0., 1., rhs.as_ref(), elem).unwrap();
         let out = TensorHandle::empty(&client, self.shape.clone(), elem);
-        random_uniform(&client, 0., 1., out.as_ref(), elem).unwrap();
+        random_uniform(&client, &mut generator, 0., 1., out.as_ref(), elem).unwrap();
 
         (lhs, rhs, out)
     }
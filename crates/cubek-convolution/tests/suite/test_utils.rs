@@ -336,11 +336,11 @@ macro_rules! sample_float {
             impl Sample for $t
             {
                 fn sample<R: Runtime>(client: &ComputeClient<R>, shape: &[usize], seed: u64) -> TensorHandle<R> {
-                    cubek_random::seed(seed);
+                    let mut generator = cubek_random::RandomGenerator::new(seed);
                     let dtype = Self::as_type_native_unchecked();
                     let output = TensorHandle::empty(client, shape.to_vec(), dtype);
 
-                    cubek_random::random_uniform(&client, f32::from_int(-1), f32::from_int(1), output.as_ref(), dtype).unwrap();
+                    cubek_random::random_uniform(&client, &mut generator, f32::from_int(-1), f32::from_int(1), output.as_ref(), dtype).unwrap();
 
                     output
                 }
@@ -361,12 +361,13 @@ impl Sample for flex32 {
         shape: &[usize],
         seed: u64,
     ) -> TensorHandle<R> {
-        cubek_random::seed(seed);
+        let mut generator = cubek_random::RandomGenerator::new(seed);
         let dtype = f32::as_type_native_unchecked();
         let output = TensorHandle::empty(client, shape.to_vec(), dtype);
 
         cubek_random::random_uniform(
             client,
+            &mut generator,
             f32::from_int(-1),
             f32::from_int(1),
             output.as_ref(),
@@ -384,12 +385,13 @@ impl Sample for tf32 {
         shape: &[usize],
         seed: u64,
     ) -> TensorHandle<R> {
-        cubek_random::seed(seed);
+        let mut generator = cubek_random::RandomGenerator::new(seed);
         let dtype = f32::as_type_native_unchecked();
         let output = TensorHandle::empty(client, shape.to_vec(), dtype);
 
         cubek_random::random_uniform(
             client,
+            &mut generator,
             f32::from_int(-1),
             f32::from_int(1),
             output.as_ref(),
@@ -214,6 +214,7 @@ impl<'a, R: Runtime> GlobalLayoutLaunch<'a, R> {
         shape: &'a [usize],
         problem: &MatmulProblem,
         scheme: QuantScheme,
+        scale_axis: Option<usize>,
         line_size: LineSize,
         config: GlobalLayoutConfig,
     ) -> (GlobalLayoutLaunch<'a, R>, GlobalScaleLayoutArgs<'a, R>) {
@@ -239,6 +240,31 @@ impl<'a, R: Runtime> GlobalLayoutLaunch<'a, R> {
         let scales_layout = {
             let shape = (ScalarArg::new(rows as u32), ScalarArg::new(cols as u32));
 
+            if let Some(axis) = scale_axis {
+                let (per_row, per_col) = (axis == rank - 2, axis == rank - 1);
+                // A line is dequantized with a single scale, so it can't span several channels.
+                let vectorized_axis = match values.strides[rank - 1] == 1 {
+                    true => per_col,
+                    false => per_row,
+                };
+                assert!(
+                    !vectorized_axis || line_size * scheme.num_quants() == 1,
+                    "Per-axis scales along the contiguous dimension require unvectorized values"
+                );
+
+                let scales_layout =
+                    GlobalLayoutLaunch::from_handle_batched(client, scales, problem, 1, config);
+                return (
+                    values_layout,
+                    GlobalScaleLayoutArgs::PerAxis(PerAxisLayoutLaunch::new(
+                        shape,
+                        scales_layout,
+                        per_row,
+                        per_col,
+                    )),
+                );
+            }
+
             match scheme.level {
                 QuantLevel::Tensor => GlobalScaleLayoutArgs::PerTensor { shape },
                 QuantLevel::Block(block_size) => {
@@ -371,6 +397,7 @@ impl<'a, R: Runtime> BatchLayoutLaunch<'a, R> {
 pub enum GlobalScaleLayout {
     PerTensor { shape: Coords2d },
     BlockScaled(BlockScaledLayout),
+    PerAxis(PerAxisLayout),
 }
 
 /// Workaround for enums not supporting `comptime`, should fix that in the future
@@ -397,6 +424,34 @@ impl BlockScaledLayout {
     }
 }
 
+/// One scale per row, per column or per batch, depending on the quantization axis
+#[derive(CubeType, CubeLaunch)]
+pub struct PerAxisLayout {
+    shape: Coords2d,
+    scales_layout: GlobalLayout,
+    #[cube(comptime)]
+    per_row: bool,
+    #[cube(comptime)]
+    per_col: bool,
+}
+
+#[cube]
+impl PerAxisLayout {
+    pub fn new(
+        shape: Coords2d,
+        scales_layout: GlobalLayout,
+        #[comptime] per_row: bool,
+        #[comptime] per_col: bool,
+    ) -> Self {
+        PerAxisLayout {
+            shape,
+            scales_layout,
+            per_row,
+            per_col,
+        }
+    }
+}
+
 #[cube]
 impl Layout for GlobalScaleLayout {
     type Coordinates = BatchedCoords;
@@ -417,6 +472,26 @@ impl Layout for GlobalScaleLayout {
                 let (row, col) = (row / block_row, col / block_col);
                 scales_layout.to_source_pos((batch, row, col))
             }
+            GlobalScaleLayout::PerAxis(layout) => {
+                let PerAxisLayout {
+                    scales_layout,
+                    per_row,
+                    per_col,
+                    ..
+                } = layout;
+
+                // Scales have a size of 1 on every dimension but the axis
+                let (batch, row, col) = coords;
+                let mut scale_row = 0u32.runtime();
+                let mut scale_col = 0u32.runtime();
+                if per_row {
+                    scale_row = row;
+                }
+                if per_col {
+                    scale_col = col;
+                }
+                scales_layout.to_source_pos((batch, scale_row, scale_col))
+            }
         }
     }
 
@@ -433,6 +508,10 @@ impl Layout for GlobalScaleLayout {
                 let (row, col) = layout.shape;
                 (u32::MAX.runtime() as usize, row, col)
             }
+            GlobalScaleLayout::PerAxis(layout) => {
+                let (row, col) = layout.shape;
+                (u32::MAX.runtime() as usize, row, col)
+            }
         }
     }
 
@@ -444,6 +523,18 @@ impl Layout for GlobalScaleLayout {
                 let config = &layout.scales_layout.config.comptime();
                 let (rows, cols) = layout.shape;
 
+                match (config.check_row_bounds, config.check_col_bounds) {
+                    (true, true) => row < rows && col < cols,
+                    (true, false) => row < rows,
+                    (false, true) => col < cols,
+                    (false, false) => true,
+                }
+            }
+            GlobalScaleLayout::PerAxis(layout) => {
+                let (_, row, col) = pos;
+                let config = &layout.scales_layout.config.comptime();
+                let (rows, cols) = layout.shape;
+
                 match (config.check_row_bounds, config.check_col_bounds) {
                     (true, true) => row < rows && col < cols,
                     (true, false) => row < rows,
@@ -209,10 +209,19 @@ impl<Lhs: Numeric, Rhs: Numeric, Acc: Numeric, A: Routine<()>> ConcreteInputsFac
                 scale,
                 shape,
                 scheme,
+                scale_axis,
                 ..
             } => {
                 let (data_layout, scales_layout) = GlobalLayoutLaunch::from_quantized_handle(
-                    client, data, scale, shape, problem, **scheme, line_size, config,
+                    client,
+                    data,
+                    scale,
+                    shape,
+                    problem,
+                    **scheme,
+                    *scale_axis,
+                    line_size,
+                    config,
                 );
                 let data_view =
                     ViewArg::new::<GlobalLayout>(data.as_array_arg(line_size), data_layout);
@@ -17,6 +17,8 @@ pub enum MatmulInputHandle<R: Runtime> {
         scale: TensorHandle<R>,
         shape: Vec<usize>,
         scheme: QuantScheme,
+        /// One scale per index of this axis, replacing the level of the scheme
+        scale_axis: Option<usize>,
     },
 }
 
@@ -31,6 +33,7 @@ impl<R: Runtime> MatmulInputHandle<R> {
                 scale,
                 shape,
                 scheme,
+                scale_axis,
             } => MatmulInputHandleRef::Quantized {
                 data: data.as_ref(),
                 scale: scale.as_ref(),
@@ -38,6 +41,7 @@ impl<R: Runtime> MatmulInputHandle<R> {
                 scale_dtype: scale.dtype,
                 shape,
                 scheme,
+                scale_axis: *scale_axis,
             },
         }
     }
@@ -54,11 +58,13 @@ impl<R: Runtime> MatmulInputHandle<R> {
                 scheme,
                 data_dtype,
                 scale_dtype,
+                scale_axis,
             } => MatmulInputHandle::Quantized {
                 data: TensorHandle::from_ref(data, *data_dtype),
                 scale: TensorHandle::from_ref(scale, *scale_dtype),
                 shape: shape.to_vec(),
                 scheme: **scheme,
+                scale_axis: *scale_axis,
             },
         }
     }
@@ -81,14 +87,24 @@ impl<R: Runtime> MatmulInputHandle<R> {
                 scale,
                 shape,
                 scheme,
+                scale_axis,
             } => {
                 let rank = data.shape.len();
 
                 data.shape.swap(dim0, dim1);
                 data.strides.swap(dim0, dim1);
 
-                // Swap dims for scale and block size if block scaled quant is used
-                if let QuantLevel::Block(block) = &mut scheme.level {
+                // Swap dims for scale and axis if per-axis quant is used, or block size if block
+                // scaled quant is used
+                if let Some(axis) = scale_axis {
+                    scale.shape.swap(dim0, dim1);
+                    scale.strides.swap(dim0, dim1);
+                    if *axis == dim0 {
+                        *axis = dim1;
+                    } else if *axis == dim1 {
+                        *axis = dim0;
+                    }
+                } else if let QuantLevel::Block(block) = &mut scheme.level {
                     scale.shape.swap(dim0, dim1);
                     scale.strides.swap(dim0, dim1);
                     let mut block_size = block.to_dim_vec(rank);
@@ -121,11 +137,13 @@ impl<R: Runtime> Clone for MatmulInputHandle<R> {
                 scale,
                 shape,
                 scheme,
+                scale_axis,
             } => Self::Quantized {
                 data: data.clone(),
                 scale: scale.clone(),
                 shape: shape.clone(),
                 scheme: *scheme,
+                scale_axis: *scale_axis,
             },
         }
     }
@@ -142,6 +160,8 @@ pub enum MatmulInputHandleRef<'a, R: Runtime> {
         /// Unpacked shape, excluding padding
         shape: &'a [usize],
         scheme: &'a QuantScheme,
+        /// One scale per index of this axis, replacing the level of the scheme
+        scale_axis: Option<usize>,
     },
 }
 
@@ -173,6 +193,29 @@ impl<'a, R: Runtime> MatmulInputHandleRef<'a, R> {
             scheme,
             data_dtype,
             scale_dtype,
+            scale_axis: None,
+        }
+    }
+
+    /// Quantized input with one scale per index of `axis`. `scale` has the rank of the data, with
+    /// the size of `axis` on that axis and 1 everywhere else.
+    pub fn quantized_per_axis(
+        data: TensorHandleRef<'a, R>,
+        scale: TensorHandleRef<'a, R>,
+        shape: &'a [usize],
+        scheme: &'a QuantScheme,
+        axis: usize,
+        data_dtype: StorageType,
+        scale_dtype: StorageType,
+    ) -> Self {
+        Self::Quantized {
+            data,
+            scale,
+            shape,
+            scheme,
+            data_dtype,
+            scale_dtype,
+            scale_axis: Some(axis),
         }
     }
 
@@ -226,6 +269,7 @@ impl<'a, R: Runtime> MatmulInputHandleRef<'a, R> {
                 scheme,
                 data_dtype,
                 scale_dtype,
+                scale_axis,
             } => {
                 let mut scheme = **scheme;
                 let data = match scheme.store {
@@ -263,6 +307,7 @@ impl<'a, R: Runtime> MatmulInputHandleRef<'a, R> {
                     scale: TensorHandle::from_ref(scale, *scale_dtype),
                     shape: shape.to_vec(),
                     scheme,
+                    scale_axis: *scale_axis,
                 }
             }
         };
@@ -10,6 +10,7 @@ use crate::definition::{MatmulElems, MatmulProblem, MatmulSetupError};
 
 use crate::launch::InputArg;
 use crate::launch::handle::{MatmulInputHandle, MatmulInputHandleRef};
+use crate::launch::per_axis_line_size;
 use crate::launch::{ConcreteInputsFactory, ConcreteOutputFactory, OutputArg, TensorArgs};
 use crate::routines::naive::NaiveRoutine;
 use crate::routines::{BlueprintStrategy, Routine as _};
@@ -94,11 +95,13 @@ pub fn launch_ref<R: Runtime>(
         rhs.data().strides,
         rank - 2,
     );
-    let line_sizes = MatmulLineSizes {
+    let mut line_sizes = MatmulLineSizes {
         lhs: lhs_line_size,
         rhs: rhs_line_size,
         out: 1,
     };
+    per_axis_line_size(&lhs, &mut line_sizes.lhs)?;
+    per_axis_line_size(&rhs, &mut line_sizes.rhs)?;
 
     let problem = MatmulProblem::from_shapes_and_strides(
         lhs_shape.to_vec(),
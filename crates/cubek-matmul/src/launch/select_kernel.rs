@@ -10,7 +10,7 @@ use crate::routines::LaunchInfo;
 use crate::routines::{BlueprintStrategy, Routine};
 use crate::{definition::MatmulElems, launch::ConfigRuntimeArg};
 use cubecl::prelude::TensorHandleRef;
-use cubecl::{Runtime, client::ComputeClient};
+use cubecl::{Runtime, client::ComputeClient, ir::LineSize};
 
 /// Select which kernel to launch for the given Algorithm.
 ///
@@ -30,6 +30,10 @@ where
     InputArg<MA>: ConcreteInputsFactory<A>,
     OutputArg<MA>: ConcreteOutputFactory<A>,
 {
+    let mut line_sizes = line_sizes;
+    per_axis_line_size(lhs, &mut line_sizes.lhs)?;
+    per_axis_line_size(rhs, &mut line_sizes.rhs)?;
+
     let mut view_line_sizes = line_sizes;
 
     if let MatmulInputHandleRef::Quantized { scheme, .. } = lhs {
@@ -64,6 +68,40 @@ where
     launch_kernel::<MA, R, A>(client, input, output, (), launch_info)
 }
 
+/// Lines of a quantized input are dequantized with a single scale, so they can't span the channels
+/// of per-axis scales along the contiguous dimension. The input is read unvectorized in that case,
+/// which packed values can't be.
+#[allow(clippy::result_large_err)]
+pub(crate) fn per_axis_line_size<R: Runtime>(
+    handle: &MatmulInputHandleRef<'_, R>,
+    line_size: &mut LineSize,
+) -> Result<(), MatmulSetupError> {
+    if let MatmulInputHandleRef::Quantized {
+        data,
+        scheme,
+        scale_axis: Some(axis),
+        ..
+    } = handle
+    {
+        let rank = data.shape.len();
+        let contiguous_axis = match data.strides[rank - 1] == 1 {
+            true => rank - 1,
+            false => rank - 2,
+        };
+
+        if *axis == contiguous_axis {
+            if scheme.num_quants() > 1 {
+                return Err(MatmulSetupError::InvalidConfig(Box::new(
+                    "Per-axis scales along the contiguous dimension require unpacked values",
+                )));
+            }
+            *line_size = 1;
+        }
+    }
+
+    Ok(())
+}
+
 /// Select which kernel to launch for the given Algorithm.
 #[allow(clippy::too_many_arguments)]
 pub fn launch_kernel_virtual<'a, MA: MatmulArgs, R: Runtime, A: Routine<MA::Config>>(
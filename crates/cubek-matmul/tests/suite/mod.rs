@@ -2,6 +2,7 @@
 
 pub mod layered;
 pub mod naive;
+pub mod quantized;
 
 mod reference;
 
@@ -0,0 +1,286 @@
+use crate::suite::assert_result;
+use cubecl::features::TypeUsage;
+use cubecl::prelude::*;
+use cubecl::quant::scheme::{
+    QuantLevel, QuantMode, QuantParam, QuantScheme, QuantStore, QuantValue,
+};
+use cubecl::std::tensor::TensorHandle;
+use cubek_matmul::definition::{MatmulElems, MatmulProblem, MatmulSetupError, MatrixLayout};
+use cubek_matmul::launch::{MatmulInputHandleRef, Strategy, launch_ref};
+use cubek_test_utils::{HostData, HostDataVec, StrideSpec};
+
+type TestRuntime = cubecl::TestRuntime;
+
+const M: usize = 16;
+const N: usize = 24;
+const K: usize = 32;
+
+#[test]
+pub fn test_quantized_per_row_lhs_naive() {
+    test_quantized_per_row_lhs(Strategy::Naive);
+}
+
+#[test]
+pub fn test_quantized_per_row_lhs_unit() {
+    test_quantized_per_row_lhs(Strategy::SimpleUnit(Default::default()));
+}
+
+#[test]
+pub fn test_quantized_per_col_rhs_naive() {
+    // The naive matmul lays the rhs out column-major, so packed values are supported
+    test_quantized_per_col_rhs(Strategy::Naive, QuantStore::PackedU32(0));
+}
+
+#[test]
+pub fn test_quantized_per_col_rhs_unit() {
+    test_quantized_per_col_rhs(Strategy::SimpleUnit(Default::default()), QuantStore::Native);
+}
+
+#[test]
+pub fn test_quantized_per_col_rhs_packed_unsupported() {
+    let client = TestRuntime::client(&Default::default());
+    let scheme = scheme(QuantStore::PackedU32(0));
+    let shape = vec![1, K, N];
+    let (rhs, rhs_scale, _) = quantized_input(&client, &shape, 2, &scheme);
+    let (lhs, _) = float_input(&client, vec![1, M, K]);
+    let out = TensorHandle::zeros(&client, vec![1, M, N], f32::as_type_native_unchecked());
+
+    let lhs_handle = MatmulInputHandleRef::Normal(lhs.as_ref(), f32::as_type_native_unchecked());
+    let rhs_handle = MatmulInputHandleRef::quantized_per_axis(
+        rhs.as_ref(),
+        rhs_scale.as_ref(),
+        &shape,
+        &scheme,
+        2,
+        rhs.dtype,
+        f32::as_type_native_unchecked(),
+    );
+
+    let mut dtypes = MatmulElems::from_single_dtype(f32::as_type_native_unchecked());
+    let result = launch_ref(
+        &Strategy::SimpleUnit(Default::default()),
+        &client,
+        &lhs_handle,
+        &rhs_handle,
+        &out.as_ref(),
+        &mut dtypes,
+    );
+    assert!(
+        matches!(result, Err(MatmulSetupError::InvalidConfig(_))),
+        "Packed values with scales along the contiguous dimension should be rejected"
+    );
+}
+
+fn test_quantized_per_row_lhs(strategy: Strategy) {
+    let client = TestRuntime::client(&Default::default());
+    let scheme = scheme(QuantStore::PackedU32(0));
+    let shape = vec![1, M, K];
+    let (lhs, lhs_scale, lhs_data) = quantized_input(&client, &shape, 1, &scheme);
+    let (rhs, rhs_data) = float_input(&client, vec![1, K, N]);
+
+    let lhs_handle = MatmulInputHandleRef::quantized_per_axis(
+        lhs.as_ref(),
+        lhs_scale.as_ref(),
+        &shape,
+        &scheme,
+        1,
+        lhs.dtype,
+        f32::as_type_native_unchecked(),
+    );
+    let rhs_handle = MatmulInputHandleRef::Normal(rhs.as_ref(), f32::as_type_native_unchecked());
+
+    test_quantized(
+        &client,
+        strategy,
+        &lhs_handle,
+        &rhs_handle,
+        (&lhs_data, &rhs_data),
+        (Some(scheme), None),
+    );
+}
+
+/// Scales along the contiguous dimension of a row-major rhs.
+fn test_quantized_per_col_rhs(strategy: Strategy, store: QuantStore) {
+    let client = TestRuntime::client(&Default::default());
+    // Native values can't be tested without support for `i8`
+    if matches!(store, QuantStore::Native)
+        && !i8::supported_uses(&client).contains(TypeUsage::Conversion)
+    {
+        return;
+    }
+
+    let scheme = scheme(store);
+    let shape = vec![1, K, N];
+    let (rhs, rhs_scale, rhs_data) = quantized_input(&client, &shape, 2, &scheme);
+    let (lhs, lhs_data) = float_input(&client, vec![1, M, K]);
+
+    let lhs_handle = MatmulInputHandleRef::Normal(lhs.as_ref(), f32::as_type_native_unchecked());
+    let rhs_handle = MatmulInputHandleRef::quantized_per_axis(
+        rhs.as_ref(),
+        rhs_scale.as_ref(),
+        &shape,
+        &scheme,
+        2,
+        rhs.dtype,
+        f32::as_type_native_unchecked(),
+    );
+
+    test_quantized(
+        &client,
+        strategy,
+        &lhs_handle,
+        &rhs_handle,
+        (&lhs_data, &rhs_data),
+        (None, Some(scheme)),
+    );
+}
+
+fn test_quantized(
+    client: &ComputeClient<TestRuntime>,
+    strategy: Strategy,
+    lhs: &MatmulInputHandleRef<'_, TestRuntime>,
+    rhs: &MatmulInputHandleRef<'_, TestRuntime>,
+    (lhs_data, rhs_data): (&HostData, &HostData),
+    (lhs_scheme, rhs_scheme): (Option<QuantScheme>, Option<QuantScheme>),
+) {
+    let mut dtypes = MatmulElems::from_single_dtype(f32::as_type_native_unchecked());
+    let problem = MatmulProblem::from_parameters(
+        M,
+        N,
+        K,
+        vec![1],
+        vec![1],
+        MatrixLayout::RowMajor,
+        MatrixLayout::RowMajor,
+        MatrixLayout::RowMajor,
+        lhs_scheme,
+        rhs_scheme,
+        dtypes.as_global_elems(),
+    );
+    let out = TensorHandle::zeros(
+        client,
+        problem.out_shape.clone(),
+        f32::as_type_native_unchecked(),
+    );
+
+    launch_ref(&strategy, client, lhs, rhs, &out.as_ref(), &mut dtypes).unwrap();
+
+    assert_result(lhs_data, rhs_data, &problem, client, &out, dtypes);
+}
+
+fn scheme(store: QuantStore) -> QuantScheme {
+    QuantScheme::default()
+        .with_level(QuantLevel::Tensor)
+        .with_mode(QuantMode::Symmetric)
+        .with_value(QuantValue::Q8S)
+        .with_store(store)
+        .with_param(QuantParam::F32)
+}
+
+/// Row-major values quantized with a different scale per index of `axis`, with their scales and
+/// the dequantized values used as reference.
+fn quantized_input(
+    client: &ComputeClient<TestRuntime>,
+    shape: &[usize],
+    axis: usize,
+    scheme: &QuantScheme,
+) -> (
+    TensorHandle<TestRuntime>,
+    TensorHandle<TestRuntime>,
+    HostData,
+) {
+    let num_elems = shape.iter().product::<usize>();
+    let inner_len = shape[axis + 1..].iter().product::<usize>();
+    let num_channels = shape[axis];
+
+    let scales: Vec<f32> = (0..num_channels)
+        .map(|channel| (1 + channel % 7) as f32 / 127.0)
+        .collect();
+    let values: Vec<i8> = (0..num_elems)
+        .map(|i| (f32::sin(i as f32 * 0.37) * 127.0).round() as i8)
+        .collect();
+    let dequantized = values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| *value as f32 * scales[(i / inner_len) % num_channels])
+        .collect();
+
+    let rank = shape.len();
+    let mut scale_shape = vec![1; rank];
+    scale_shape[axis] = num_channels;
+
+    let data = match scheme.store {
+        QuantStore::Native => upload(
+            client,
+            i8::as_bytes(&values),
+            shape.to_vec(),
+            i8::as_type_native_unchecked(),
+        ),
+        _ => {
+            let mut packed_shape = shape.to_vec();
+            packed_shape[rank - 1] /= scheme.num_quants();
+            upload(
+                client,
+                u32::as_bytes(&pack(&values)),
+                packed_shape,
+                u32::as_type_native_unchecked(),
+            )
+        }
+    };
+    let scale = upload(
+        client,
+        f32::as_bytes(&scales),
+        scale_shape,
+        f32::as_type_native_unchecked(),
+    );
+    let host_data = HostData {
+        data: HostDataVec::F32(dequantized),
+        shape: shape.to_vec(),
+        strides: StrideSpec::RowMajor.compute_strides(shape),
+    };
+
+    (data, scale, host_data)
+}
+
+/// Pack four values per `u32`, the first in the lowest bits.
+fn pack(values: &[i8]) -> Vec<u32> {
+    values
+        .chunks(4)
+        .map(|chunk| {
+            chunk.iter().enumerate().fold(0, |packed, (i, value)| {
+                packed | ((*value as u8 as u32) << (8 * i))
+            })
+        })
+        .collect()
+}
+
+fn float_input(
+    client: &ComputeClient<TestRuntime>,
+    shape: Vec<usize>,
+) -> (TensorHandle<TestRuntime>, HostData) {
+    let num_elems = shape.iter().product::<usize>();
+    let data: Vec<f32> = (0..num_elems).map(|i| f32::cos(i as f32 * 0.21)).collect();
+    let handle = upload(
+        client,
+        f32::as_bytes(&data),
+        shape.clone(),
+        f32::as_type_native_unchecked(),
+    );
+    let host_data = HostData {
+        data: HostDataVec::F32(data),
+        strides: StrideSpec::RowMajor.compute_strides(&shape),
+        shape,
+    };
+
+    (handle, host_data)
+}
+
+fn upload(
+    client: &ComputeClient<TestRuntime>,
+    bytes: &[u8],
+    shape: Vec<usize>,
+    dtype: StorageType,
+) -> TensorHandle<TestRuntime> {
+    let alloc = client.create_tensor_from_slice(bytes, &shape, dtype.size());
+    TensorHandle::new(alloc.handle, shape, alloc.strides, dtype)
+}
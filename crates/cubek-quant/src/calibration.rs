@@ -0,0 +1,753 @@
+//! Calibration of static per-tensor scales from the statistics of many batches.
+//!
+//! The statistics stay on the device and are updated by every observed batch. The absolute maximum
+//! and the moments are collected by [Calibrator::observe]. The histogram needs a fixed range, so
+//! it's collected over a second pass with [Calibrator::observe_histogram], the range being the
+//! absolute maximum of the first pass.
+
+use cubecl::calculate_cube_count_elemwise;
+use cubecl::features::TypeUsage;
+use cubecl::ir::{ElemType, UIntKind};
+use cubecl::prelude::*;
+use cubecl::server::Handle;
+use cubecl::std::tensor::layout::linear::{LinearView, linear_view};
+use cubecl::tensor_line_size_parallel;
+
+use crate::{
+    QuantError,
+    scheme::{QuantLevel, QuantMode, QuantScheme, QuantValue},
+};
+
+/// Default number of bins of the histogram, as in TensorRT.
+pub const DEFAULT_NUM_BINS: usize = 2048;
+
+/// Number of units of the reductions over a batch and over the candidate ranges.
+const CUBE_SIZE: usize = 256;
+
+/// Positions of the statistics in the device buffer. The moments are kept as the mean and the sum
+/// of squared deviations from it, the number of values being counted on the host.
+const ABSMAX: usize = 0;
+const MEAN: usize = 1;
+const M2: usize = 2;
+const RANGE: usize = 3;
+/// The partial statistics of a batch hold their number of values in place of the range.
+const COUNT: usize = 3;
+const NUM_STATS: usize = 4;
+
+/// Counts below this value are considered empty when comparing histograms, so bins that only hold
+/// clipped values have a finite divergence.
+const KL_EPSILON: f32 = 0.0001;
+
+/// How the clipping range of the scale is derived from the statistics.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CalibrationMethod {
+    /// The largest absolute value observed, which a single outlier can inflate.
+    Absmax,
+    /// The smallest range holding the given percentage of the observed values, e.g. `99.99`.
+    Percentile(f32),
+    /// The range minimizing the mean squared error of the histogram, counting the clipping error
+    /// of the values above the range and the rounding error of the values below it.
+    Mse,
+    /// The range minimizing the KL divergence between the histogram and its quantized version, as
+    /// in the entropy calibration of TensorRT.
+    KlDivergence,
+}
+
+/// Objective of the candidate range search, a comptime value of the kernel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum SearchObjective {
+    Mse,
+    KlDivergence,
+}
+
+impl CalibrationMethod {
+    fn needs_histogram(&self) -> bool {
+        !matches!(self, CalibrationMethod::Absmax)
+    }
+}
+
+/// Mean and variance of the observed values.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Moments {
+    pub mean: f32,
+    pub variance: f32,
+}
+
+/// Running statistics of the observed batches, from which static scales are computed.
+///
+/// Histogram bins count the absolute values in `[0, range)` in `u32`, values above the range being
+/// counted in the last bin.
+pub struct Calibrator {
+    stats: Handle,
+    histogram: Handle,
+    num_bins: usize,
+    num_values: u64,
+    histogram_range_fixed: bool,
+}
+
+/// One unit per line, each cube writing the absolute maximum, number of values, mean and sum of
+/// squared deviations of its lines to `partials`.
+#[cube(launch_unchecked)]
+fn partial_stats_kernel<F: Float>(
+    input: &LinearView<Line<F>>,
+    partials: &mut Array<f32>,
+    num_partials: usize,
+    #[define(F)] _dtype: StorageType,
+) {
+    let unit = UNIT_POS as usize;
+    let mut shared = SharedMemory::<f32>::new(NUM_STATS * CUBE_SIZE);
+    shared[ABSMAX * CUBE_SIZE + unit] = 0.0;
+    shared[COUNT * CUBE_SIZE + unit] = 0.0;
+    shared[MEAN * CUBE_SIZE + unit] = 0.0;
+    shared[M2 * CUBE_SIZE + unit] = 0.0;
+
+    if input.is_in_bounds(ABSOLUTE_POS) {
+        let line = Line::<f32>::cast_from(input[ABSOLUTE_POS]);
+
+        #[unroll]
+        for i in 0..input.line_size() {
+            accumulate_stats(&mut shared, unit, line[i]);
+        }
+    }
+
+    reduce_stats(&mut shared);
+
+    if unit == 0 && CUBE_POS < num_partials {
+        let base = CUBE_POS * NUM_STATS;
+        partials[base + ABSMAX] = shared[ABSMAX * CUBE_SIZE];
+        partials[base + COUNT] = shared[COUNT * CUBE_SIZE];
+        partials[base + MEAN] = shared[MEAN * CUBE_SIZE];
+        partials[base + M2] = shared[M2 * CUBE_SIZE];
+    }
+}
+
+/// A single cube, which reduces the partial statistics of a batch and merges them into the
+/// running statistics of the `num_values` values observed before it.
+#[cube(launch_unchecked)]
+fn merge_stats_kernel(
+    partials: &Array<f32>,
+    stats: &mut Array<f32>,
+    num_partials: usize,
+    num_values: f32,
+) {
+    let unit = UNIT_POS as usize;
+    let mut shared = SharedMemory::<f32>::new(NUM_STATS * CUBE_SIZE);
+    let mut absmax = 0.0f32;
+    let mut count = 0.0f32;
+    let mut mean = 0.0f32;
+    let mut m2 = 0.0f32;
+
+    let mut index = unit;
+    loop {
+        if index >= num_partials {
+            break;
+        }
+
+        let base = index * NUM_STATS;
+        absmax = f32::max(absmax, partials[base + ABSMAX]);
+        let (merged_count, merged_mean, merged_m2) = merge_moments(
+            count,
+            mean,
+            m2,
+            partials[base + COUNT],
+            partials[base + MEAN],
+            partials[base + M2],
+        );
+        count = merged_count;
+        mean = merged_mean;
+        m2 = merged_m2;
+        index += CUBE_SIZE;
+    }
+
+    shared[ABSMAX * CUBE_SIZE + unit] = absmax;
+    shared[COUNT * CUBE_SIZE + unit] = count;
+    shared[MEAN * CUBE_SIZE + unit] = mean;
+    shared[M2 * CUBE_SIZE + unit] = m2;
+
+    reduce_stats(&mut shared);
+
+    if unit == 0 {
+        let (_, running_mean, running_m2) = merge_moments(
+            num_values,
+            stats[MEAN],
+            stats[M2],
+            shared[COUNT * CUBE_SIZE],
+            shared[MEAN * CUBE_SIZE],
+            shared[M2 * CUBE_SIZE],
+        );
+        stats[ABSMAX] = f32::max(stats[ABSMAX], shared[ABSMAX * CUBE_SIZE]);
+        stats[MEAN] = running_mean;
+        stats[M2] = running_m2;
+    }
+}
+
+/// Welford update of the moments of a unit with a single value.
+#[cube]
+fn accumulate_stats(shared: &mut SharedMemory<f32>, unit: usize, value: f32) {
+    let absmax = ABSMAX * CUBE_SIZE + unit;
+    let count = COUNT * CUBE_SIZE + unit;
+    let mean = MEAN * CUBE_SIZE + unit;
+    shared[absmax] = f32::max(shared[absmax], f32::abs(value));
+    shared[count] += 1.0;
+
+    let delta = value - shared[mean];
+    shared[mean] += delta / shared[count];
+    shared[M2 * CUBE_SIZE + unit] += delta * (value - shared[mean]);
+}
+
+/// Number of values, mean and sum of squared deviations of two sets of values, merged as in
+/// Chan et al. so the moments never go through the raw sum of squares.
+#[cube]
+fn merge_moments(
+    count_a: f32,
+    mean_a: f32,
+    m2_a: f32,
+    count_b: f32,
+    mean_b: f32,
+    m2_b: f32,
+) -> (f32, f32, f32) {
+    let count = count_a + count_b;
+    let mut mean = mean_a;
+    let mut m2 = m2_a;
+
+    // An empty set leaves the moments untouched, which also avoids dividing by a zero count
+    if count_b > 0.0 {
+        let delta = mean_b - mean_a;
+        let weight = count_b / count;
+        mean += delta * weight;
+        m2 += m2_b + delta * delta * count_a * weight;
+    }
+
+    (count, mean, m2)
+}
+
+/// Tree reduction of the statistics of every unit into the first unit.
+#[cube]
+fn reduce_stats(shared: &mut SharedMemory<f32>) {
+    let unit = UNIT_POS as usize;
+    sync_cube();
+
+    let mut stride = (CUBE_SIZE / 2).runtime();
+    loop {
+        if stride == 0 {
+            break;
+        }
+
+        if unit < stride {
+            let absmax = ABSMAX * CUBE_SIZE + unit;
+            let count = COUNT * CUBE_SIZE + unit;
+            let mean = MEAN * CUBE_SIZE + unit;
+            let m2 = M2 * CUBE_SIZE + unit;
+            shared[absmax] = f32::max(shared[absmax], shared[absmax + stride]);
+
+            let (merged_count, merged_mean, merged_m2) = merge_moments(
+                shared[count],
+                shared[mean],
+                shared[m2],
+                shared[count + stride],
+                shared[mean + stride],
+                shared[m2 + stride],
+            );
+            shared[count] = merged_count;
+            shared[mean] = merged_mean;
+            shared[m2] = merged_m2;
+        }
+        sync_cube();
+
+        stride /= 2;
+    }
+}
+
+/// A single unit, which fixes the range of the histogram to the absolute maximum observed so far.
+#[cube(launch_unchecked)]
+fn fix_range_kernel(stats: &mut Array<f32>) {
+    stats[RANGE] = stats[ABSMAX];
+}
+
+/// One unit per line, which counts the absolute value of each element in its bin.
+#[cube(launch_unchecked)]
+fn histogram_kernel<F: Float>(
+    input: &LinearView<Line<F>>,
+    stats: &Array<f32>,
+    histogram: &mut Array<Atomic<u32>>,
+    #[define(F)] _dtype: StorageType,
+) {
+    if !input.is_in_bounds(ABSOLUTE_POS) {
+        terminate!();
+    }
+
+    let num_bins = histogram.len();
+    let bins_per_unit = f32::cast_from(num_bins) / stats[RANGE];
+    let line = Line::<f32>::cast_from(input[ABSOLUTE_POS]);
+
+    #[unroll]
+    for i in 0..input.line_size() {
+        // A zero range puts every value in the first bin
+        let mut bin = 0.0f32;
+        if stats[RANGE] > 0.0 {
+            bin = f32::min(
+                f32::abs(line[i]) * bins_per_unit,
+                f32::cast_from(num_bins - 1),
+            );
+        }
+
+        histogram[usize::cast_from(bin)].fetch_add(1u32);
+    }
+}
+
+/// A zero clipping range keeps a unit scale, like the scales of
+/// [quantize_dynamic](crate::quantize::quantize_dynamic).
+#[cube]
+fn write_scale<FS: Numeric>(
+    out_scale: &mut LinearView<Line<FS>, ReadWrite>,
+    clip: f32,
+    half_range: f32,
+) {
+    let mut scale = 1.0f32;
+    if clip > 0.0 {
+        scale = clip / half_range;
+    }
+
+    out_scale[0] = Line::new(FS::cast_from(scale));
+}
+
+/// A single unit, which uses the absolute maximum as the clipping range.
+#[cube(launch_unchecked)]
+fn absmax_clip_kernel<FS: Numeric>(
+    stats: &Array<f32>,
+    out_scale: &mut LinearView<Line<FS>, ReadWrite>,
+    half_range: f32,
+    #[define(FS)] _dtype: StorageType,
+) {
+    write_scale(out_scale, stats[ABSMAX], half_range);
+}
+
+/// A single unit, which walks the cumulative histogram until it holds `percentile` of the values.
+#[cube(launch_unchecked)]
+fn percentile_clip_kernel<FS: Numeric>(
+    histogram: &Array<u32>,
+    stats: &Array<f32>,
+    out_scale: &mut LinearView<Line<FS>, ReadWrite>,
+    percentile: f32,
+    half_range: f32,
+    #[define(FS)] _dtype: StorageType,
+) {
+    let num_bins = histogram.len();
+    let mut total = 0.0f32;
+    for bin in 0..num_bins {
+        total += f32::cast_from(histogram[bin]);
+    }
+
+    let target = total * percentile / 100.0;
+    let mut cumulative = 0.0f32;
+    let mut num_kept = num_bins;
+    for bin in 0..num_bins {
+        cumulative += f32::cast_from(histogram[bin]);
+        if cumulative >= target {
+            num_kept = bin + 1;
+            break;
+        }
+    }
+
+    let bin_width = stats[RANGE] / f32::cast_from(num_bins);
+    write_scale(out_scale, f32::cast_from(num_kept) * bin_width, half_range);
+}
+
+/// A single cube, each unit evaluating a subset of the candidate ranges before the best one is
+/// selected by the first unit. Candidates keep the first `first_candidate + i` bins, and ties go to
+/// the smallest range.
+#[cube(launch_unchecked)]
+#[allow(clippy::too_many_arguments)]
+fn search_clip_kernel<FS: Numeric>(
+    histogram: &Array<u32>,
+    stats: &Array<f32>,
+    out_scale: &mut LinearView<Line<FS>, ReadWrite>,
+    half_range: f32,
+    q_max: f32,
+    num_levels: usize,
+    first_candidate: usize,
+    #[comptime] objective: SearchObjective,
+    #[define(FS)] _dtype: StorageType,
+) {
+    let unit = UNIT_POS as usize;
+    let num_bins = histogram.len();
+    let bin_width = stats[RANGE] / f32::cast_from(num_bins);
+
+    let mut best_error = f32::max_value();
+    let mut best_kept = num_bins;
+    let mut num_kept = first_candidate + unit;
+    loop {
+        if num_kept > num_bins {
+            break;
+        }
+
+        let clip = f32::cast_from(num_kept) * bin_width;
+        let error = match objective {
+            SearchObjective::Mse => mse(histogram, bin_width, clip / half_range, q_max),
+            SearchObjective::KlDivergence => kl_divergence(histogram, num_kept, num_levels),
+        };
+        if error < best_error {
+            best_error = error;
+            best_kept = num_kept;
+        }
+
+        num_kept += CUBE_SIZE;
+    }
+
+    let mut errors = SharedMemory::<f32>::new(CUBE_SIZE);
+    let mut kept = SharedMemory::<usize>::new(CUBE_SIZE);
+    errors[unit] = best_error;
+    kept[unit] = best_kept;
+    sync_cube();
+
+    if unit == 0 {
+        for i in 1..CUBE_SIZE {
+            if errors[i] < best_error || (errors[i] == best_error && kept[i] < best_kept) {
+                best_error = errors[i];
+                best_kept = kept[i];
+            }
+        }
+
+        write_scale(out_scale, f32::cast_from(best_kept) * bin_width, half_range);
+    }
+}
+
+/// Squared error of the values of the histogram, at the center of their bin. Values up to the
+/// largest quantized value have a uniform rounding error of `scale² / 12`, and larger values are
+/// clipped to it.
+#[cube]
+fn mse(histogram: &Array<u32>, bin_width: f32, scale: f32, q_max: f32) -> f32 {
+    let max_value = q_max * scale;
+    let rounding = scale * scale / 12.0;
+    let mut error = 0.0f32;
+
+    for bin in 0..histogram.len() {
+        let count = f32::cast_from(histogram[bin]);
+        let clipped = (f32::cast_from(bin) + 0.5) * bin_width - max_value;
+        let mut bin_error = rounding;
+        if clipped > 0.0 {
+            bin_error = clipped * clipped;
+        }
+
+        error += count * bin_error;
+    }
+
+    error
+}
+
+/// Divergence between the first `num_kept` bins, with the clipped values added to the last one,
+/// and the same bins merged into `num_levels` quantized bins. The count of a quantized bin is
+/// spread evenly over the non-empty bins it merges.
+#[cube]
+fn kl_divergence(histogram: &Array<u32>, num_kept: usize, num_levels: usize) -> f32 {
+    let mut clipped = 0.0f32;
+    for bin in num_kept..histogram.len() {
+        clipped += f32::cast_from(histogram[bin]);
+    }
+
+    let mut total_kept = 0.0f32;
+    for bin in 0..num_kept {
+        total_kept += f32::cast_from(histogram[bin]);
+    }
+    let total = total_kept + clipped;
+
+    let mut divergence = f32::max_value();
+    if total_kept > 0.0 {
+        divergence = 0.0;
+
+        for level in 0..num_levels {
+            let start = level * num_kept / num_levels;
+            let end = (level + 1) * num_kept / num_levels;
+
+            let mut level_count = 0.0f32;
+            let mut non_empty = 0.0f32;
+            for bin in start..end {
+                let count = f32::cast_from(histogram[bin]);
+                level_count += count;
+                if count > 0.0 {
+                    non_empty += 1.0;
+                }
+            }
+
+            for bin in start..end {
+                let count = f32::cast_from(histogram[bin]);
+                let mut p = count;
+                if bin == num_kept - 1 {
+                    p += clipped;
+                }
+
+                if p > 0.0 {
+                    let mut q = KL_EPSILON;
+                    if count > 0.0 {
+                        q = level_count / non_empty;
+                    }
+
+                    let p_norm = p / total;
+                    let q_norm = q / total_kept;
+                    divergence += p_norm * f32::ln(p_norm / q_norm);
+                }
+            }
+        }
+    }
+
+    divergence
+}
+
+impl Calibrator {
+    /// Calibrator without any observed value, with `num_bins` histogram bins.
+    pub fn new<R: Runtime>(client: &ComputeClient<R>, num_bins: usize) -> Self {
+        Self {
+            stats: client.create_from_slice(f32::as_bytes(&[0.0; NUM_STATS])),
+            histogram: client.create_from_slice(u32::as_bytes(&vec![0; num_bins])),
+            num_bins,
+            num_values: 0,
+            histogram_range_fixed: false,
+        }
+    }
+
+    /// Update the absolute maximum and the moments with the values of `input`.
+    #[allow(clippy::result_large_err)]
+    pub fn observe<R: Runtime>(
+        &mut self,
+        client: &ComputeClient<R>,
+        input: &TensorHandleRef<R>,
+        input_elem: ElemType,
+    ) -> Result<(), QuantError> {
+        let num_elems = input.shape.iter().product::<usize>();
+        let line_size = line_size(client, input);
+        let num_lines = num_elems / line_size;
+        let num_partials = num_lines.div_ceil(CUBE_SIZE);
+
+        let partials = client.empty(num_partials * NUM_STATS * size_of::<f32>());
+        let cube_dim = CubeDim::new_1d(CUBE_SIZE as u32);
+        let cube_count = calculate_cube_count_elemwise(client, num_lines, cube_dim);
+
+        unsafe {
+            partial_stats_kernel::launch_unchecked(
+                client,
+                cube_count,
+                cube_dim,
+                linear_view(client, input, line_size),
+                ArrayArg::from_raw_parts::<f32>(&partials, num_partials * NUM_STATS, 1),
+                ScalarArg::new(num_partials),
+                input_elem.into(),
+            )
+            .map_err(QuantError::Launch)?;
+
+            merge_stats_kernel::launch_unchecked(
+                client,
+                CubeCount::new_single(),
+                cube_dim,
+                ArrayArg::from_raw_parts::<f32>(&partials, num_partials * NUM_STATS, 1),
+                ArrayArg::from_raw_parts::<f32>(&self.stats, NUM_STATS, 1),
+                ScalarArg::new(num_partials),
+                ScalarArg::new(self.num_values as f32),
+            )
+            .map_err(QuantError::Launch)?;
+        }
+
+        self.num_values += num_elems as u64;
+        Ok(())
+    }
+
+    /// Count the absolute values of `input` in the histogram.
+    ///
+    /// The range of the histogram is fixed on the first call to the absolute maximum observed by
+    /// [observe](Self::observe), which must have seen every batch beforehand.
+    #[allow(clippy::result_large_err)]
+    pub fn observe_histogram<R: Runtime>(
+        &mut self,
+        client: &ComputeClient<R>,
+        input: &TensorHandleRef<R>,
+        input_elem: ElemType,
+    ) -> Result<(), QuantError> {
+        let counter = StorageType::Atomic(ElemType::UInt(UIntKind::U32));
+        if !client
+            .properties()
+            .features
+            .type_usage(counter)
+            .contains(TypeUsage::AtomicAdd)
+        {
+            return Err(QuantError::MissingTypeSupport(counter));
+        }
+
+        if self.num_values == 0 {
+            return Err(QuantError::MissingStatistics {
+                details: "the range of the histogram is the absolute maximum of the observed values, but no value was observed".into(),
+            });
+        }
+
+        if !self.histogram_range_fixed {
+            unsafe {
+                fix_range_kernel::launch_unchecked(
+                    client,
+                    CubeCount::new_single(),
+                    CubeDim::new_single(),
+                    ArrayArg::from_raw_parts::<f32>(&self.stats, NUM_STATS, 1),
+                )
+                .map_err(QuantError::Launch)?;
+            }
+            self.histogram_range_fixed = true;
+        }
+
+        let num_elems = input.shape.iter().product::<usize>();
+        let line_size = line_size(client, input);
+        let num_lines = num_elems / line_size;
+        let cube_dim = CubeDim::new(client, num_lines);
+        let cube_count = calculate_cube_count_elemwise(client, num_lines, cube_dim);
+
+        unsafe {
+            histogram_kernel::launch_unchecked(
+                client,
+                cube_count,
+                cube_dim,
+                linear_view(client, input, line_size),
+                ArrayArg::from_raw_parts::<f32>(&self.stats, NUM_STATS, 1),
+                ArrayArg::from_raw_parts::<Atomic<u32>>(&self.histogram, self.num_bins, 1),
+                input_elem.into(),
+            )
+            .map_err(QuantError::Launch)
+        }
+    }
+
+    /// Write the scale of the clipping range selected by `method` to `out_scale`, a tensor of a
+    /// single value ready for [quantize::launch_ref](crate::quantize::launch_ref).
+    ///
+    /// The scheme must be symmetric at the tensor level, with an integer value for
+    /// [Mse](CalibrationMethod::Mse) and [KlDivergence](CalibrationMethod::KlDivergence), which
+    /// assume evenly spaced quantized values.
+    #[allow(clippy::result_large_err)]
+    pub fn compute_scale<R: Runtime>(
+        &self,
+        client: &ComputeClient<R>,
+        out_scale: &TensorHandleRef<'_, R>,
+        method: CalibrationMethod,
+        scheme: &QuantScheme,
+    ) -> Result<(), QuantError> {
+        let integer = matches!(
+            scheme.value,
+            QuantValue::Q8F
+                | QuantValue::Q8S
+                | QuantValue::Q4F
+                | QuantValue::Q4S
+                | QuantValue::Q2F
+                | QuantValue::Q2S
+        );
+        let supported = scheme.level == QuantLevel::Tensor
+            && scheme.mode == QuantMode::Symmetric
+            && (integer
+                || matches!(
+                    method,
+                    CalibrationMethod::Absmax | CalibrationMethod::Percentile(_)
+                ));
+        if !supported {
+            return Err(QuantError::UnsupportedScheme(*scheme));
+        }
+
+        if method.needs_histogram() && !self.histogram_range_fixed {
+            return Err(QuantError::MissingStatistics {
+                details: format!("{method:?} needs a histogram, but no value was counted"),
+            });
+        }
+
+        let (q_min, q_max) = scheme.value.range();
+        let half_range = (q_max - q_min) / 2.0;
+        let num_levels = q_max as usize + 1;
+        let scale_elem: StorageType = ElemType::from_quant_param(scheme.param).into();
+
+        let stats = ArrayArg::from_raw_parts::<f32>(&self.stats, NUM_STATS, 1);
+        let histogram = ArrayArg::from_raw_parts::<u32>(&self.histogram, self.num_bins, 1);
+        let output = linear_view(client, out_scale, 1);
+        let single = CubeCount::new_single();
+
+        unsafe {
+            match method {
+                CalibrationMethod::Absmax => absmax_clip_kernel::launch_unchecked(
+                    client,
+                    single,
+                    CubeDim::new_single(),
+                    stats,
+                    output,
+                    ScalarArg::new(half_range),
+                    scale_elem,
+                ),
+                CalibrationMethod::Percentile(percentile) => {
+                    percentile_clip_kernel::launch_unchecked(
+                        client,
+                        single,
+                        CubeDim::new_single(),
+                        histogram,
+                        stats,
+                        output,
+                        ScalarArg::new(percentile),
+                        ScalarArg::new(half_range),
+                        scale_elem,
+                    )
+                }
+                CalibrationMethod::Mse | CalibrationMethod::KlDivergence => {
+                    // KL candidates keep at least one bin per quantized level
+                    let (objective, first_candidate) = match method {
+                        CalibrationMethod::Mse => (SearchObjective::Mse, 1),
+                        _ => (SearchObjective::KlDivergence, num_levels),
+                    };
+                    if first_candidate > self.num_bins {
+                        return Err(QuantError::ShapeMismatch {
+                            details: format!(
+                                "The histogram needs at least {first_candidate} bins, got {}",
+                                self.num_bins
+                            ),
+                        });
+                    }
+
+                    search_clip_kernel::launch_unchecked(
+                        client,
+                        single,
+                        CubeDim::new_1d(CUBE_SIZE as u32),
+                        histogram,
+                        stats,
+                        output,
+                        ScalarArg::new(half_range),
+                        ScalarArg::new(q_max),
+                        ScalarArg::new(num_levels),
+                        ScalarArg::new(first_candidate),
+                        objective,
+                        scale_elem,
+                    )
+                }
+            }
+            .map_err(QuantError::Launch)
+        }
+    }
+
+    /// Read back the mean and variance of the observed values.
+    pub fn moments<R: Runtime>(&self, client: &ComputeClient<R>) -> Moments {
+        let stats = client.read_one(self.stats.clone());
+        let stats = f32::from_bytes(&stats);
+
+        let count = self.num_values.max(1) as f32;
+
+        Moments {
+            mean: stats[MEAN],
+            variance: stats[M2] / count,
+        }
+    }
+
+    /// Number of values observed by [observe](Self::observe).
+    pub fn num_values(&self) -> u64 {
+        self.num_values
+    }
+}
+
+/// A scalar has no dimension to vectorize along, so it's read with a line size of one.
+fn line_size<R: Runtime>(client: &ComputeClient<R>, input: &TensorHandleRef<R>) -> usize {
+    if input.shape.is_empty() {
+        return 1;
+    }
+
+    tensor_line_size_parallel(
+        client.io_optimized_line_sizes_unchecked(input.elem_size),
+        input.shape,
+        input.strides,
+        input.shape.len() - 1,
+    )
+}
@@ -0,0 +1,267 @@
+//! Quantization to 4-bit indices into a table of 16 values, like the NF4 format of QLoRA, instead
+//! of evenly spaced integers.
+
+use cubecl::calculate_cube_count_elemwise;
+use cubecl::ir::ElemType;
+use cubecl::prelude::*;
+use cubecl::std::tensor::layout::linear::{LinearView, linear_view};
+
+use crate::{
+    QuantError,
+    layout::{ScalesView, scales_view},
+    quantize::{launch_absmax_scale, packed_input},
+    scheme::{QuantLevel, QuantMode, QuantParam, QuantScheme, QuantStore, QuantValue},
+    utils::check_block_size_compat,
+};
+
+/// Number of values of a codebook, one per 4-bit index.
+pub const CODEBOOK_LEN: usize = 16;
+
+/// Values the 4-bit indices map to, relative to the scale of their block.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Codebook {
+    values: [f32; CODEBOOK_LEN],
+}
+
+impl Codebook {
+    /// NormalFloat4 of QLoRA, the quantiles of a normal distribution normalized to `[-1, 1]`, with
+    /// an exact zero.
+    pub const NF4: Codebook = Codebook::new([
+        -1.0,
+        -0.696_192_8,
+        -0.525_073_05,
+        -0.394_917_5,
+        -0.284_441_38,
+        -0.184_773_43,
+        -0.091_050_036,
+        0.0,
+        0.079_580_3,
+        0.160_930_2,
+        0.246_112_3,
+        0.337_915_24,
+        0.440_709_83,
+        0.562_617,
+        0.722_956_84,
+        1.0,
+    ]);
+
+    /// Values of `E2M1`, indexed by their bits.
+    pub const E2M1: Codebook = Codebook::new([
+        0.0, 0.5, 1.0, 1.5, 2.0, 3.0, 4.0, 6.0, -0.0, -0.5, -1.0, -1.5, -2.0, -3.0, -4.0, -6.0,
+    ]);
+
+    /// Codebook with the value of every index. Values don't need to be sorted, and when several
+    /// are equally close to a scaled input, the lowest index is used.
+    pub const fn new(values: [f32; CODEBOOK_LEN]) -> Self {
+        Self { values }
+    }
+
+    pub fn values(&self) -> &[f32; CODEBOOK_LEN] {
+        &self.values
+    }
+
+    /// Largest magnitude of the values, which the absolute maximum of each block is scaled to.
+    pub fn max_abs(&self) -> f32 {
+        self.values.iter().fold(0.0, |max, v| max.max(v.abs()))
+    }
+}
+
+/// Scheme of the indices, packed eight per `u32` along the last dimension with the first index in
+/// the lowest bits, and one `f32` scale per block of 64 values. The level and parameter type can
+/// be changed, the other fields are required by [quantize_codebook] and [dequantize_codebook].
+pub fn codebook_scheme() -> QuantScheme {
+    QuantScheme::default()
+        .with_level(QuantLevel::block([64]))
+        .with_mode(QuantMode::Symmetric)
+        .with_value(QuantValue::Q4F)
+        .with_store(QuantStore::PackedU32(0))
+        .with_param(QuantParam::F32)
+}
+
+fn check_codebook_scheme(scheme: &QuantScheme) -> Result<(), QuantError> {
+    let supported = matches!(
+        scheme,
+        QuantScheme {
+            mode: QuantMode::Symmetric,
+            value: QuantValue::Q4F | QuantValue::Q4S,
+            store: QuantStore::PackedU32(0),
+            ..
+        }
+    );
+    match supported {
+        true => Ok(()),
+        false => Err(QuantError::UnsupportedScheme(*scheme)),
+    }
+}
+
+/// One unit per packed `u32`, which searches the nearest value of the codebook for each of its
+/// scaled inputs.
+#[cube(launch_unchecked)]
+fn quantize_codebook_kernel<F: Float, FS: Numeric>(
+    input: &LinearView<Line<F>>,
+    scale: &ScalesView<FS>,
+    codebook: &Array<f32>,
+    output: &mut LinearView<Line<u32>, ReadWrite>,
+    #[comptime] num_quants: usize,
+    #[define(F, FS)] _dtypes: [StorageType; 2],
+) {
+    if !output.is_in_bounds(ABSOLUTE_POS) {
+        terminate!();
+    }
+
+    let packed_pos = ABSOLUTE_POS * num_quants;
+    let scale = f32::cast_from(scale[packed_pos]);
+    let mut packed = 0u32;
+
+    #[unroll]
+    for i in 0..num_quants {
+        let value = if input.line_size().comptime() == num_quants {
+            input[ABSOLUTE_POS][i]
+        } else {
+            // Input line size = 1
+            input[packed_pos + i][0]
+        };
+        let scaled = f32::cast_from(value) / scale;
+
+        let mut index = 0u32;
+        let mut distance = f32::abs(scaled - codebook[0]);
+        for code in 1..CODEBOOK_LEN {
+            let code_distance = f32::abs(scaled - codebook[code]);
+            if code_distance < distance {
+                index = code as u32;
+                distance = code_distance;
+            }
+        }
+
+        packed |= index << (i as u32 * 4);
+    }
+
+    output[ABSOLUTE_POS] = Line::new(packed);
+}
+
+/// One unit per packed `u32`, which writes a line with the codebook value of each index.
+#[cube(launch_unchecked)]
+fn dequantize_codebook_kernel<F: Float, FS: Numeric>(
+    input: &LinearView<Line<u32>>,
+    scale: &ScalesView<FS>,
+    codebook: &Array<f32>,
+    output: &mut LinearView<Line<F>, ReadWrite>,
+    #[define(F, FS)] _dtypes: [StorageType; 2],
+) {
+    if !input.is_in_bounds(ABSOLUTE_POS) {
+        terminate!();
+    }
+
+    let num_quants = output.line_size();
+    let packed = input[ABSOLUTE_POS][0];
+    let scale = f32::cast_from(scale[ABSOLUTE_POS * num_quants]);
+    let mut values = Line::<F>::empty(num_quants);
+
+    #[unroll]
+    for i in 0..num_quants {
+        let index = (packed >> (i as u32 * 4)) & 0xF;
+        values[i] = F::cast_from(codebook[index as usize] * scale);
+    }
+
+    output[ABSOLUTE_POS] = values;
+}
+
+#[allow(clippy::result_large_err)]
+/// Quantize the input tensor to indices into `codebook`, with scales computed on the device.
+///
+/// The scale of each tensor or block is its absolute maximum divided by [Codebook::max_abs], and is
+/// written to `out_scale`. Every value is then replaced by the index of the nearest codebook value
+/// to the scaled input. `scheme` gives the level and parameter type of the scales, with the other
+/// fields of [codebook_scheme].
+///
+/// The scales are a regular tensor, so they can be quantized again with
+/// [launch_ref](crate::quantize::launch_ref) for double quantization.
+pub fn quantize_codebook<R: Runtime>(
+    client: &ComputeClient<R>,
+    input: &TensorHandleRef<R>,
+    output: &TensorHandleRef<R>,
+    out_scale: &TensorHandleRef<'_, R>,
+    codebook: &Codebook,
+    scheme: &QuantScheme,
+    input_elem: ElemType,
+) -> Result<(), QuantError> {
+    check_codebook_scheme(scheme)?;
+
+    let num_quants = scheme.num_quants();
+    check_block_size_compat(scheme, num_quants)?;
+
+    let param_elem = ElemType::from_quant_param(scheme.param);
+    launch_absmax_scale(
+        client,
+        input,
+        output,
+        out_scale,
+        scheme,
+        codebook.max_abs(),
+        input_elem,
+        param_elem,
+    )?;
+
+    let codebook = client.create_from_slice(f32::as_bytes(codebook.values()));
+    let (input, line_size) = packed_input(client, input, scheme, input_elem)?;
+
+    let working_units = output.shape.iter().product::<usize>();
+    let cube_dim = CubeDim::new(client, working_units);
+    let cube_count = calculate_cube_count_elemwise(client, working_units, cube_dim);
+
+    unsafe {
+        quantize_codebook_kernel::launch_unchecked(
+            client,
+            cube_count,
+            cube_dim,
+            linear_view(client, &input.as_ref(), line_size),
+            scales_view(client, output, out_scale, 1, scheme),
+            ArrayArg::from_raw_parts::<f32>(&codebook, CODEBOOK_LEN, 1),
+            linear_view(client, output, 1),
+            num_quants,
+            [input_elem.into(), param_elem.into()],
+        )
+        .map_err(QuantError::Launch)
+    }
+}
+
+#[allow(clippy::result_large_err)]
+/// Convert a tensor quantized by [quantize_codebook] back to a higher precision data type, each
+/// index being replaced by its codebook value times the scale of its block.
+pub fn dequantize_codebook<R: Runtime>(
+    client: &ComputeClient<R>,
+    values: &TensorHandleRef<R>,
+    output: &TensorHandleRef<R>,
+    scale: &TensorHandleRef<'_, R>,
+    codebook: &Codebook,
+    scheme: &QuantScheme,
+    output_dtype: StorageType,
+) -> Result<(), QuantError> {
+    check_codebook_scheme(scheme)?;
+
+    let num_quants = scheme.num_quants();
+    check_block_size_compat(scheme, num_quants)?;
+
+    let codebook = client.create_from_slice(f32::as_bytes(codebook.values()));
+
+    let working_units = values.shape.iter().product::<usize>();
+    let cube_dim = CubeDim::new(client, working_units);
+    let cube_count = calculate_cube_count_elemwise(client, working_units, cube_dim);
+
+    unsafe {
+        dequantize_codebook_kernel::launch_unchecked(
+            client,
+            cube_count,
+            cube_dim,
+            linear_view(client, values, 1),
+            scales_view(client, values, scale, 1, scheme),
+            ArrayArg::from_raw_parts::<f32>(&codebook, CODEBOOK_LEN, 1),
+            linear_view(client, output, num_quants),
+            [
+                output_dtype,
+                ElemType::from_quant_param(scheme.param).into(),
+            ],
+        )
+        .map_err(QuantError::Launch)
+    }
+}
@@ -4,13 +4,16 @@ use cubecl::features::TypeUsage;
 use cubecl::prelude::*;
 use cubecl::{
     calculate_cube_count_elemwise,
-    ir::{ElemType, FloatKind, IntKind},
+    ir::{ElemType, UIntKind},
     tensor_line_size_parallel,
 };
 
 use crate::{
-    layout::{ScalesView, scales_view},
+    QuantError, check_native_support,
+    layout::{ScalesView, scales_view, scales_view_with_axis},
+    mx::{check_packing, element_storage},
     scheme::{QuantLevel, QuantMode, QuantScheme, QuantStore, QuantValue},
+    utils::{check_axis, check_axis_compat, check_rank, native_line_size},
 };
 use cubecl::std::tensor::{
     View,
@@ -24,6 +27,18 @@ pub fn dequantize_symmetric<F: Float, FS: CubePrimitive>(value: Line<F>, scale:
     Line::cast_from(scale) * value
 }
 
+/// Dequantize a line of unsigned values into floating-point values using the provided scale and
+/// zero-point.
+#[cube]
+pub fn dequantize_affine<F: Float, FS: CubePrimitive>(
+    value: Line<F>,
+    scale: FS,
+    zero_point: FS,
+) -> Line<F> {
+    // x = scale * (x_q - zero_point)
+    Line::cast_from(scale) * (value - Line::cast_from(zero_point))
+}
+
 /// Dequantize the value at a specified position using the provided quantization scheme.
 ///
 /// Returns a line of floating-point values. The number of values in the line depends on the number of packed
@@ -69,7 +84,7 @@ pub fn dequantize_symmetric_packed_value<F: Float, FS: CubePrimitive, QS: Int>(
 
     #[unroll]
     for i in 0..line_size_values {
-        let floats = unpack_q::<F, QS>(values[i], scheme.value, scheme.store);
+        let floats = unpack_q::<F, QS>(values[i], scheme.value, scheme.store, true);
         let scale = scales[(position * line_size_values) + i * num_quants];
         let values = dequantize_symmetric::<F, FS>(floats, scale);
         tmp[i] = values;
@@ -78,15 +93,44 @@ pub fn dequantize_symmetric_packed_value<F: Float, FS: CubePrimitive, QS: Int>(
     tmp
 }
 
+/// Dequantize a single packed value of unsigned quants using the scales and zero-points provided.
+///
+/// Returns a line of floating-point values. The number of values in the line depends on the number of packed
+/// values in the stored quantization type.
+#[cube]
+pub fn dequantize_affine_packed_value<F: Float, FS: CubePrimitive, QS: Int>(
+    values: Line<QS>,
+    scales: &View<FS, usize>,
+    zero_points: &View<FS, usize>,
+    position: usize,
+    #[comptime] scheme: QuantScheme,
+) -> Array<Line<F>> {
+    let line_size_values = values.line_size();
+    let num_quants = scheme.num_quants();
+    let mut tmp = Array::lined(line_size_values, num_quants);
+
+    #[unroll]
+    for i in 0..line_size_values {
+        let floats = unpack_q::<F, QS>(values[i], scheme.value, scheme.store, false);
+        let scale_pos = (position * line_size_values) + i * num_quants;
+        let values = dequantize_affine::<F, FS>(floats, scales[scale_pos], zero_points[scale_pos]);
+        tmp[i] = values;
+    }
+
+    tmp
+}
+
 /// Unpack a quantized integer into a line of floating-point values, according to the specified quantization input type.
 ///
 /// This handles types where multiple quantized values are packed into a single integer (the stored quantization type).
+/// Unsigned quants, used by affine quantization, are not sign extended.
 #[allow(clippy::explicit_counter_loop)]
 #[cube]
-fn unpack_q<F: Float, QS: Int>(
+pub(crate) fn unpack_q<F: Float, QS: Int>(
     value: QS,
     #[comptime] quant: QuantValue,
     #[comptime] store: QuantStore,
+    #[comptime] signed: bool,
 ) -> Line<F> {
     let size_quant = quant.size_bits();
     let size_store = store.size_bits(&quant);
@@ -103,13 +147,18 @@ fn unpack_q<F: Float, QS: Int>(
         let offset = QS::cast_from(position * size_quant);
         let raw = (value >> offset) & mask;
 
-        // Branchless two's complement conversion
-        // If raw >= 2^(n-1), then result = raw - 2^n
         let raw_i32 = i32::cast_from(raw);
-        let is_negative = i32::cast_from(raw >= sign_bit); // 1 if negative, 0 if positive
-        let signed_value = raw_i32 - (is_negative * two_pow_n);
 
-        output[position] = F::cast_from(signed_value);
+        if signed {
+            // Branchless two's complement conversion
+            // If raw >= 2^(n-1), then result = raw - 2^n
+            let is_negative = i32::cast_from(raw >= sign_bit); // 1 if negative, 0 if positive
+            let signed_value = raw_i32 - (is_negative * two_pow_n);
+
+            output[position] = F::cast_from(signed_value);
+        } else {
+            output[position] = F::cast_from(raw_i32);
+        }
     }
 
     output
@@ -164,6 +213,57 @@ fn dequantize_symmetric_native_kernel<F: Float, FS: Numeric, Q: Numeric>(
         dequantize_symmetric::<F, FS>(Line::cast_from(input[ABSOLUTE_POS]), scale);
 }
 
+#[cube(launch_unchecked)]
+fn dequantize_affine_packed_kernel<F: Float, FS: Numeric>(
+    input: &LinearView<Line<u32>>,
+    scales: &ScalesView<FS>,
+    zero_points: &ScalesView<FS>,
+    output: &mut LinearView<Line<F>, ReadWrite>,
+    #[comptime] scheme: QuantScheme,
+    #[define(F, FS)] _dtypes: [StorageType; 2],
+) {
+    if !input.is_in_bounds(ABSOLUTE_POS) {
+        terminate!();
+    }
+
+    let line_size_in = input.line_size();
+    let packed_pos = ABSOLUTE_POS * scheme.num_quants();
+
+    let out = dequantize_affine_packed_value::<F, FS, u32>(
+        input[ABSOLUTE_POS],
+        scales,
+        zero_points,
+        packed_pos,
+        scheme,
+    );
+
+    #[unroll]
+    for i in 0..line_size_in {
+        output[ABSOLUTE_POS * line_size_in + i] = out[i];
+    }
+}
+
+#[cube(launch_unchecked)]
+fn dequantize_affine_native_kernel<F: Float, FS: Numeric, Q: Numeric>(
+    input: &LinearView<Line<Q>>,
+    scale: &ScalesView<FS>,
+    zero_point: &ScalesView<FS>,
+    output: &mut LinearView<Line<F>, ReadWrite>,
+    #[define(F, FS, Q)] _dtypes: [StorageType; 3],
+) {
+    if !input.is_in_bounds(ABSOLUTE_POS) {
+        terminate!();
+    }
+
+    let scale_pos = ABSOLUTE_POS * input.line_size();
+
+    output[ABSOLUTE_POS] = dequantize_affine::<F, FS>(
+        Line::cast_from(input[ABSOLUTE_POS]),
+        scale[scale_pos],
+        zero_point[scale_pos],
+    );
+}
+
 #[allow(clippy::result_large_err)]
 /// Convert the tensor back to a higher precision data type.
 pub fn launch_ref<R: Runtime>(
@@ -173,8 +273,48 @@ pub fn launch_ref<R: Runtime>(
     params: &TensorHandleRef<'_, R>,
     scheme: &QuantScheme,
     input_dtype: StorageType,
-) -> Result<(), LaunchError> {
+) -> Result<(), QuantError> {
+    launch(client, values, output, params, scheme, None, input_dtype)
+}
+
+#[allow(clippy::result_large_err)]
+/// Convert a tensor quantized with one scale per index of `axis` by
+/// [quantize::launch_ref_per_axis](crate::quantize::launch_ref_per_axis) back to a higher
+/// precision data type.
+pub fn launch_ref_per_axis<R: Runtime>(
+    client: &ComputeClient<R>,
+    values: &TensorHandleRef<R>,
+    output: &TensorHandleRef<R>,
+    params: &TensorHandleRef<'_, R>,
+    scheme: &QuantScheme,
+    axis: usize,
+    input_dtype: StorageType,
+) -> Result<(), QuantError> {
+    launch(
+        client,
+        values,
+        output,
+        params,
+        scheme,
+        Some(axis),
+        input_dtype,
+    )
+}
+
+#[allow(clippy::result_large_err)]
+fn launch<R: Runtime>(
+    client: &ComputeClient<R>,
+    values: &TensorHandleRef<R>,
+    output: &TensorHandleRef<R>,
+    params: &TensorHandleRef<'_, R>,
+    scheme: &QuantScheme,
+    axis: Option<usize>,
+    input_dtype: StorageType,
+) -> Result<(), QuantError> {
+    check_rank(values.shape)?;
+    check_rank(output.shape)?;
     let dtype_scale: StorageType = ElemType::from_quant_param(scheme.param).into();
+    check_axis(output.shape, axis)?;
 
     match scheme {
         QuantScheme {
@@ -184,6 +324,7 @@ pub fn launch_ref<R: Runtime>(
             client,
             values,
             *scheme,
+            axis,
             params,
             output,
             input_dtype,
@@ -199,17 +340,13 @@ pub fn launch_ref<R: Runtime>(
             store: QuantStore::PackedNative(_),
             ..
         } => {
-            if !i8::supported_uses(client).contains(TypeUsage::Conversion) {
-                panic!(
-                    "{:?} is not supported for native quantization",
-                    scheme.value
-                );
-            }
+            check_native_support(client)?;
 
             dequantize_native(
                 client,
                 values,
                 *scheme,
+                axis,
                 params,
                 output,
                 input_dtype,
@@ -218,23 +355,22 @@ pub fn launch_ref<R: Runtime>(
         }
         QuantScheme {
             store: QuantStore::Native | QuantStore::PackedNative(_),
-            value,
             ..
-        } => {
-            panic!("{value:?} is not supported for native quantization");
-        }
+        } => Err(QuantError::UnsupportedScheme(*scheme)),
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn dequantize_packed<R: Runtime>(
     client: &ComputeClient<R>,
     input: &TensorHandleRef<R>,
     scheme: QuantScheme,
+    axis: Option<usize>,
     scale: &TensorHandleRef<'_, R>,
     output: &TensorHandleRef<R>,
     input_dtype: StorageType,
     scale_dtype: StorageType,
-) -> Result<(), LaunchError> {
+) -> Result<(), QuantError> {
     let num_elems_input: usize = input.shape.iter().product();
 
     let mut line_size_in = tensor_line_size_parallel(
@@ -251,6 +387,8 @@ fn dequantize_packed<R: Runtime>(
         line_size_in = 1;
     }
 
+    check_axis_compat(input.shape, axis, num_quants, num_quants)?;
+
     let num_elems = num_elems_input / line_size_in as usize;
     let cube_dim = CubeDim::new(client, num_elems);
     let cube_count = calculate_cube_count_elemwise(client, num_elems, cube_dim);
@@ -267,32 +405,45 @@ fn dequantize_packed<R: Runtime>(
                 cube_count,
                 cube_dim,
                 linear_view(client, input, line_size_in),
-                scales_view(client, input, scale, 1, &scheme),
+                scales_view_with_axis(client, input, scale, 1, &scheme, axis),
                 linear_view(client, output, line_size_out),
                 scheme,
                 [input_dtype, scale_dtype],
             )
+            .map_err(QuantError::Launch)
         },
-        QuantScheme { .. } => panic!("Unsupported quantization scheme {scheme:?}"),
+        QuantScheme { .. } => Err(QuantError::UnsupportedScheme(scheme)),
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn dequantize_native<R: Runtime>(
     client: &ComputeClient<R>,
     input: &TensorHandleRef<R>,
     scheme: QuantScheme,
+    axis: Option<usize>,
     scale: &TensorHandleRef<'_, R>,
     output: &TensorHandleRef<R>,
     input_dtype: StorageType,
     scale_dtype: StorageType,
-) -> Result<(), LaunchError> {
-    let num_elems: usize = input.shape.iter().product();
+) -> Result<(), QuantError> {
+    let num_elems: usize = output.shape.iter().product();
     let line_size = tensor_line_size_parallel(
         client.io_optimized_line_sizes_unchecked(input_dtype.size()),
-        input.shape,
-        input.strides,
-        input.shape.len() - 1,
+        output.shape,
+        output.strides,
+        output.shape.len() - 1,
     );
+    let line_size = native_line_size(output.shape, axis, line_size);
+    // Lines of output values hold whole stored values
+    let (quant_dtype, packing) = element_storage(scheme.value);
+    check_packing(line_size, packing)?;
+    check_axis_compat(
+        input.shape,
+        axis,
+        scheme.num_quants(),
+        line_size.max(scheme.num_quants()),
+    )?;
     let working_units = num_elems / line_size as usize;
     let cube_dim = CubeDim::new(client, working_units);
     let cube_count = calculate_cube_count_elemwise(client, working_units, cube_dim);
@@ -301,30 +452,125 @@ fn dequantize_native<R: Runtime>(
         QuantScheme {
             level: QuantLevel::Tensor | QuantLevel::Block(_),
             mode: QuantMode::Symmetric,
-            value,
+            store: QuantStore::Native | QuantStore::PackedNative(_),
+            ..
+        } => unsafe {
+            dequantize_symmetric_native_kernel::launch_unchecked(
+                client,
+                cube_count,
+                cube_dim,
+                linear_view(client, input, line_size / packing),
+                scales_view_with_axis(client, input, scale, 1, &scheme, axis),
+                linear_view(client, output, line_size),
+                [input_dtype, scale_dtype, quant_dtype],
+            )
+            .map_err(QuantError::Launch)
+        },
+        QuantScheme { .. } => Err(QuantError::UnsupportedScheme(scheme)),
+    }
+}
+
+#[allow(clippy::result_large_err)]
+/// Convert a tensor quantized with a zero-point by
+/// [quantize::launch_ref_affine](crate::quantize::launch_ref_affine) back to a higher precision
+/// data type.
+pub fn launch_ref_affine<R: Runtime>(
+    client: &ComputeClient<R>,
+    values: &TensorHandleRef<R>,
+    output: &TensorHandleRef<R>,
+    scale: &TensorHandleRef<'_, R>,
+    zero_point: &TensorHandleRef<'_, R>,
+    scheme: &QuantScheme,
+    input_dtype: StorageType,
+) -> Result<(), QuantError> {
+    check_rank(values.shape)?;
+    check_rank(output.shape)?;
+    let dtype_scale: StorageType = ElemType::from_quant_param(scheme.param).into();
+
+    match scheme {
+        QuantScheme {
+            level: QuantLevel::Tensor | QuantLevel::Block(_),
+            value:
+                QuantValue::Q8F
+                | QuantValue::Q8S
+                | QuantValue::Q4F
+                | QuantValue::Q4S
+                | QuantValue::Q2F
+                | QuantValue::Q2S,
+            store: QuantStore::PackedU32(_),
+            ..
+        } => {
+            let num_elems_input: usize = values.shape.iter().product();
+            let mut line_size_in = tensor_line_size_parallel(
+                client.io_optimized_line_sizes_unchecked(values.elem_size),
+                values.shape,
+                values.strides,
+                values.shape.len() - 1,
+            );
+            let line_size_out = scheme.num_quants();
+            let rank = output.shape.len();
+
+            if !output.shape[rank - 1].is_multiple_of(line_size_out) {
+                line_size_in = 1;
+            }
+
+            let num_elems = num_elems_input / line_size_in;
+            let cube_dim = CubeDim::new(client, num_elems);
+            let cube_count = calculate_cube_count_elemwise(client, num_elems, cube_dim);
+
+            unsafe {
+                dequantize_affine_packed_kernel::launch_unchecked(
+                    client,
+                    cube_count,
+                    cube_dim,
+                    linear_view(client, values, line_size_in),
+                    scales_view(client, values, scale, 1, scheme),
+                    scales_view(client, values, zero_point, 1, scheme),
+                    linear_view(client, output, line_size_out),
+                    *scheme,
+                    [input_dtype, dtype_scale],
+                )
+                .map_err(QuantError::Launch)
+            }
+        }
+        QuantScheme {
+            level: QuantLevel::Tensor | QuantLevel::Block(_),
+            value: QuantValue::Q8F | QuantValue::Q8S,
             store: QuantStore::Native,
             ..
         } => {
-            let quant_dtype: ElemType = match value {
-                QuantValue::Q8F | QuantValue::Q8S => ElemType::Int(IntKind::I8),
-                QuantValue::E4M3 => ElemType::Float(FloatKind::E4M3),
-                QuantValue::E5M2 => ElemType::Float(FloatKind::E5M2),
-                QuantValue::E2M1 => ElemType::Float(FloatKind::E2M1),
-                other => panic!("Unsupported quantization value {other:?}"),
-            };
+            if !u8::supported_uses(client).contains(TypeUsage::Conversion) {
+                return Err(QuantError::MissingTypeSupport(
+                    u8::as_type_native_unchecked(),
+                ));
+            }
+
+            let quant_dtype = StorageType::from(ElemType::UInt(UIntKind::U8));
+            let num_elems: usize = values.shape.iter().product();
+            let line_size = tensor_line_size_parallel(
+                client.io_optimized_line_sizes_unchecked(quant_dtype.size()),
+                values.shape,
+                values.strides,
+                values.shape.len() - 1,
+            );
+            let working_units = num_elems / line_size;
+            let cube_dim = CubeDim::new(client, working_units);
+            let cube_count = calculate_cube_count_elemwise(client, working_units, cube_dim);
 
             unsafe {
-                dequantize_symmetric_native_kernel::launch_unchecked(
+                dequantize_affine_native_kernel::launch_unchecked(
                     client,
                     cube_count,
                     cube_dim,
-                    linear_view(client, input, line_size),
-                    scales_view(client, input, scale, 1, &scheme),
+                    linear_view(client, values, line_size),
+                    scales_view(client, values, scale, 1, scheme),
+                    scales_view(client, values, zero_point, 1, scheme),
                     linear_view(client, output, line_size),
-                    [input_dtype, scale_dtype, quant_dtype.into()],
+                    [input_dtype, dtype_scale, quant_dtype],
                 )
+                .map_err(QuantError::Launch)
             }
         }
-        QuantScheme { .. } => panic!("Unsupported quantization scheme {scheme:?}"),
+        _ => Err(QuantError::UnsupportedScheme(*scheme)),
     }
 }
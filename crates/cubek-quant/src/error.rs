@@ -0,0 +1,29 @@
+use alloc::string::String;
+use cubecl::{ir::StorageType, server::LaunchError};
+use thiserror::Error;
+
+use crate::scheme::QuantScheme;
+
+#[derive(Error, Debug, Clone)]
+/// This error should be caught and properly handled.
+pub enum QuantError {
+    /// The scheme isn't supported by the launched operation.
+    #[error("Unsupported quantization scheme {0:?}")]
+    UnsupportedScheme(QuantScheme),
+    /// The block size isn't a multiple of the number of values handled together by a unit.
+    #[error("Block size must be divisible by {div}, got block_size={block_size}")]
+    BlockSizeMismatch { block_size: usize, div: usize },
+    /// The client doesn't support conversions to the type the values are stored in.
+    #[error("{0:?} conversions are not supported by the client")]
+    MissingTypeSupport(StorageType),
+    /// The shape or strides of a tensor don't fit the scheme.
+    #[error("Invalid tensor shape: {details}")]
+    ShapeMismatch { details: String },
+    /// Calibration statistics were used before being collected.
+    #[error("Missing calibration statistics: {details}")]
+    MissingStatistics { details: String },
+
+    /// An error happened during launch.
+    #[error("An error happened during launch\nCaused by:\n  {0}")]
+    Launch(LaunchError),
+}
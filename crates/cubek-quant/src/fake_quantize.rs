@@ -0,0 +1,321 @@
+//! Quantize-dequantize for quantization-aware training. The values are rounded to the grid of the
+//! scheme but stay in floating-point, so no packed buffer is needed between the two steps.
+
+use cubecl::calculate_cube_count_elemwise;
+use cubecl::ir::ElemType;
+use cubecl::prelude::*;
+use cubecl::std::FastDivmod;
+use cubecl::std::tensor::layout::linear::{LinearView, linear_view};
+use cubecl::tensor_line_size_parallel;
+
+use crate::{
+    QuantError,
+    dequantize::dequantize_symmetric,
+    layout::{ScalesView, scales_view},
+    quantize::{BlockReduction, block_element, block_start, quantize_symmetric},
+    scheme::{QuantLevel, QuantMode, QuantScheme, QuantStore, QuantValue},
+};
+
+/// One unit per line, which never crosses a block.
+#[cube(launch_unchecked)]
+fn fake_quantize_kernel<F: Float, FS: Numeric>(
+    input: &LinearView<Line<F>>,
+    scale: &ScalesView<FS>,
+    range_min: InputScalar,
+    range_max: InputScalar,
+    output: &mut LinearView<Line<F>, ReadWrite>,
+    #[define(F, FS)] _dtypes: [StorageType; 2],
+) {
+    if !output.is_in_bounds(ABSOLUTE_POS) {
+        terminate!();
+    }
+
+    let scale = scale[ABSOLUTE_POS * input.line_size()];
+    let values = quantize_symmetric::<F, FS>(
+        input[ABSOLUTE_POS],
+        scale,
+        range_min.get::<F>(),
+        range_max.get::<F>(),
+    );
+    output[ABSOLUTE_POS] = dequantize_symmetric::<F, FS>(values, scale);
+}
+
+/// One unit per line, which passes the gradient of the values that aren't clamped.
+#[cube(launch_unchecked)]
+fn fake_quantize_backward_kernel<F: Float, FS: Numeric>(
+    input: &LinearView<Line<F>>,
+    grad_output: &LinearView<Line<F>>,
+    scale: &ScalesView<FS>,
+    range_min: InputScalar,
+    range_max: InputScalar,
+    grad_input: &mut LinearView<Line<F>, ReadWrite>,
+    #[define(F, FS)] _dtypes: [StorageType; 2],
+) {
+    if !grad_input.is_in_bounds(ABSOLUTE_POS) {
+        terminate!();
+    }
+
+    let line_size = input.line_size();
+    let scale = scale[ABSOLUTE_POS * line_size];
+    let rounded = Line::round(input[ABSOLUTE_POS] / Line::cast_from(scale));
+    let grad = grad_output[ABSOLUTE_POS];
+    let mut grad_in = Line::empty(line_size).fill(F::new(0.0));
+
+    #[unroll]
+    for i in 0..line_size {
+        if rounded[i] >= range_min.get::<F>() && rounded[i] <= range_max.get::<F>() {
+            grad_in[i] = grad[i];
+        }
+    }
+
+    grad_input[ABSOLUTE_POS] = grad_in;
+}
+
+/// Each cube handles a single block like
+/// [quantize_dynamic](crate::quantize::quantize_dynamic), writing the gradient of its values and
+/// reducing the gradient of its scale.
+#[cube(launch_unchecked)]
+#[allow(clippy::too_many_arguments)]
+fn fake_quantize_backward_lsq_kernel<F: Float, FS: Numeric>(
+    input: &LinearView<Line<F>>,
+    grad_output: &LinearView<Line<F>>,
+    scale: &ScalesView<FS>,
+    range_min: f32,
+    range_max: f32,
+    grad_factor: f32,
+    grad_input: &mut LinearView<Line<F>, ReadWrite>,
+    grad_scale: &mut ScalesView<FS, ReadWrite>,
+    blocks_shape: Sequence<FastDivmod<usize>>,
+    blocks_strides: Sequence<usize>,
+    block_shape: Sequence<FastDivmod<usize>>,
+    strides: Sequence<usize>,
+    num_blocks: usize,
+    block_len: usize,
+    #[comptime] cube_size: usize,
+    #[define(F, FS)] _dtypes: [StorageType; 2],
+) {
+    let block = CUBE_POS;
+    if block >= num_blocks {
+        terminate!();
+    }
+
+    let unit = UNIT_POS as usize;
+    let start = block_start(block, &blocks_shape, &blocks_strides);
+    let scale = f32::cast_from(scale[start]);
+
+    let mut sum = 0.0f32;
+    let mut index = unit;
+
+    loop {
+        if index >= block_len {
+            break;
+        }
+
+        let pos = block_element(start, index, &block_shape, &strides);
+        let scaled = f32::cast_from(input[pos][0]) / scale;
+        let rounded = f32::round(scaled);
+        let grad = f32::cast_from(grad_output[pos][0]);
+
+        // Derivative of the dequantized value by the scale, `round(x / s) - x / s` in the range
+        // and the clamped bound outside of it
+        let mut grad_in = grad;
+        let mut derivative = rounded - scaled;
+        if rounded < range_min {
+            grad_in = 0.0;
+            derivative = range_min;
+        } else if rounded > range_max {
+            grad_in = 0.0;
+            derivative = range_max;
+        }
+
+        grad_input[pos] = Line::new(F::cast_from(grad_in));
+        sum += grad * derivative;
+        index += CUBE_DIM as usize;
+    }
+
+    let mut shared = SharedMemory::<f32>::new(cube_size);
+    shared[unit] = sum;
+    sync_cube();
+
+    let mut stride = (cube_size / 2).runtime();
+    loop {
+        if stride == 0 {
+            break;
+        }
+
+        if unit < stride {
+            shared[unit] += shared[unit + stride];
+        }
+        sync_cube();
+
+        stride /= 2;
+    }
+
+    if unit == 0 {
+        grad_scale[start] = FS::cast_from(shared[0] * grad_factor);
+    }
+}
+
+/// The values are never stored, so the store of the scheme is ignored and the scales are laid out
+/// for unpacked values.
+fn unpacked_scheme(scheme: &QuantScheme) -> Result<QuantScheme, QuantError> {
+    match scheme {
+        QuantScheme {
+            mode: QuantMode::Symmetric,
+            value:
+                QuantValue::Q8F
+                | QuantValue::Q8S
+                | QuantValue::Q4F
+                | QuantValue::Q4S
+                | QuantValue::Q2F
+                | QuantValue::Q2S,
+            ..
+        } => Ok(scheme.with_store(QuantStore::Native)),
+        _ => Err(QuantError::UnsupportedScheme(*scheme)),
+    }
+}
+
+/// Line size of the input, reduced until lines don't cross blocks. A scalar has no dimension to
+/// vectorize along, so it's read with a line size of one.
+fn line_size<R: Runtime>(
+    client: &ComputeClient<R>,
+    input: &TensorHandleRef<R>,
+    scheme: &QuantScheme,
+) -> usize {
+    if input.shape.is_empty() {
+        return 1;
+    }
+
+    let mut line_size = tensor_line_size_parallel(
+        client.io_optimized_line_sizes_unchecked(input.elem_size),
+        input.shape,
+        input.strides,
+        input.shape.len() - 1,
+    );
+
+    if let QuantLevel::Block(block_size) = &scheme.level {
+        let block_last = *block_size.as_slice().last().unwrap() as usize;
+        while !block_last.is_multiple_of(line_size) {
+            line_size /= 2;
+        }
+    }
+
+    line_size
+}
+
+#[allow(clippy::result_large_err)]
+/// Quantize and dequantize the input tensor in a single pass, `x' = scale * clamp(round(x / scale))`.
+///
+/// `output` has the shape and element type of `input`, and `scale` holds the scale of each tensor
+/// or block like for [launch_ref](crate::quantize::launch_ref). Only the symmetric mode of the
+/// integer values is supported, the store of the scheme being ignored.
+pub fn fake_quantize<R: Runtime>(
+    client: &ComputeClient<R>,
+    input: &TensorHandleRef<R>,
+    output: &TensorHandleRef<R>,
+    scale: &TensorHandleRef<'_, R>,
+    scheme: &QuantScheme,
+    input_elem: ElemType,
+) -> Result<(), QuantError> {
+    let scheme = unpacked_scheme(scheme)?;
+    let (range_min, range_max) = scheme.value.range();
+    let line_size = line_size(client, input, &scheme);
+
+    let working_units = input.shape.iter().product::<usize>() / line_size;
+    let cube_dim = CubeDim::new(client, working_units);
+    let cube_count = calculate_cube_count_elemwise(client, working_units, cube_dim);
+
+    unsafe {
+        fake_quantize_kernel::launch_unchecked(
+            client,
+            cube_count,
+            cube_dim,
+            linear_view(client, input, line_size),
+            scales_view(client, input, scale, 1, &scheme),
+            InputScalar::new(range_min, input_elem),
+            InputScalar::new(range_max, input_elem),
+            linear_view(client, output, line_size),
+            [
+                input_elem.into(),
+                ElemType::from_quant_param(scheme.param).into(),
+            ],
+        )
+        .map_err(QuantError::Launch)
+    }
+}
+
+#[allow(clippy::result_large_err, clippy::too_many_arguments)]
+/// Gradient of [fake_quantize] with the straight-through estimator, which passes `grad_output`
+/// through the rounding and zeroes it where the values are clamped.
+///
+/// When `grad_scale` is set, the gradient of each scale is also reduced as in LSQ (Learned Step
+/// Size Quantization), scaled by `1 / sqrt(block_len * q_max)` to balance it with the gradient of
+/// the values. It has the shape of `scale`.
+pub fn fake_quantize_backward<R: Runtime>(
+    client: &ComputeClient<R>,
+    input: &TensorHandleRef<R>,
+    grad_output: &TensorHandleRef<R>,
+    grad_input: &TensorHandleRef<R>,
+    scale: &TensorHandleRef<'_, R>,
+    grad_scale: Option<&TensorHandleRef<'_, R>>,
+    scheme: &QuantScheme,
+    input_elem: ElemType,
+) -> Result<(), QuantError> {
+    let scheme = unpacked_scheme(scheme)?;
+    let (range_min, range_max) = scheme.value.range();
+    let dtypes = [
+        input_elem.into(),
+        ElemType::from_quant_param(scheme.param).into(),
+    ];
+
+    let Some(grad_scale) = grad_scale else {
+        let line_size = line_size(client, input, &scheme);
+        let working_units = input.shape.iter().product::<usize>() / line_size;
+        let cube_dim = CubeDim::new(client, working_units);
+        let cube_count = calculate_cube_count_elemwise(client, working_units, cube_dim);
+
+        return unsafe {
+            fake_quantize_backward_kernel::launch_unchecked(
+                client,
+                cube_count,
+                cube_dim,
+                linear_view(client, input, line_size),
+                linear_view(client, grad_output, line_size),
+                scales_view(client, input, scale, 1, &scheme),
+                InputScalar::new(range_min, input_elem),
+                InputScalar::new(range_max, input_elem),
+                linear_view(client, grad_input, line_size),
+                dtypes,
+            )
+            .map_err(QuantError::Launch)
+        };
+    };
+
+    let blocks = BlockReduction::new(client, input.shape, &scheme)?;
+    let grad_factor = 1.0 / f32::sqrt(blocks.block_len as f32 * range_max);
+
+    unsafe {
+        fake_quantize_backward_lsq_kernel::launch_unchecked(
+            client,
+            blocks.cube_count(client),
+            blocks.cube_dim(),
+            linear_view(client, input, 1),
+            linear_view(client, grad_output, 1),
+            scales_view(client, input, scale, 1, &scheme),
+            ScalarArg::new(range_min),
+            ScalarArg::new(range_max),
+            ScalarArg::new(grad_factor),
+            linear_view(client, grad_input, 1),
+            scales_view(client, input, grad_scale, 1, &scheme),
+            blocks.blocks_shape,
+            blocks.blocks_strides,
+            blocks.block_shape,
+            blocks.strides,
+            ScalarArg::new(blocks.num_blocks),
+            ScalarArg::new(blocks.block_len),
+            blocks.cube_size,
+            dtypes,
+        )
+        .map_err(QuantError::Launch)
+    }
+}
@@ -0,0 +1,248 @@
+//! Block formats of llama.cpp GGUF files, dequantized from the raw bytes of a tensor.
+//!
+//! The scales are stored inline with the values, and the K-quants nest quantized block scales in
+//! super-blocks, so these formats have no [QuantScheme](crate::scheme::QuantScheme) equivalent and
+//! are converted to floats before being used.
+
+use cubecl::calculate_cube_count_elemwise;
+use cubecl::prelude::*;
+use cubecl::server::Handle;
+use cubecl::std::tensor::layout::linear::{LinearView, linear_view};
+
+use crate::{QuantError, utils::check_last_dim};
+
+/// GGUF block format of a tensor.
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GgufFormat {
+    /// 32 values of 4 bits with an `f16` scale, `x = (q - 8) * d`.
+    Q4_0,
+    /// 32 values of 4 bits with an `f16` scale and minimum, `x = q * d + m`.
+    Q4_1,
+    /// 32 values of 5 bits, the high bits in a separate `u32`, with an `f16` scale,
+    /// `x = (q - 16) * d`.
+    Q5_0,
+    /// 32 signed bytes with an `f16` scale, `x = q * d`.
+    Q8_0,
+    /// Super-blocks of 256 values of 4 bits, in 8 blocks with 6-bit scales and minimums relative
+    /// to an `f16` super-block scale and minimum.
+    Q4_K,
+    /// Super-blocks of 256 values of 6 bits, in 16 blocks with signed 8-bit scales relative to an
+    /// `f16` super-block scale.
+    Q6_K,
+}
+
+impl GgufFormat {
+    /// Number of values in a block, or super-block for the K-quants.
+    pub fn block_len(&self) -> usize {
+        match self {
+            GgufFormat::Q4_0 | GgufFormat::Q4_1 | GgufFormat::Q5_0 | GgufFormat::Q8_0 => 32,
+            GgufFormat::Q4_K | GgufFormat::Q6_K => 256,
+        }
+    }
+
+    /// Number of bytes of a block, or super-block for the K-quants.
+    pub fn block_bytes(&self) -> usize {
+        match self {
+            GgufFormat::Q4_0 => 18,
+            GgufFormat::Q4_1 => 20,
+            GgufFormat::Q5_0 => 22,
+            GgufFormat::Q8_0 => 34,
+            GgufFormat::Q4_K => 144,
+            GgufFormat::Q6_K => 210,
+        }
+    }
+}
+
+/// One unit per value, reading only the bytes of its block it depends on. Blocks aren't aligned to
+/// words, so the data is read as words and the bytes extracted from them.
+#[cube(launch_unchecked)]
+fn dequantize_gguf_kernel<F: Float>(
+    data: &Array<u32>,
+    output: &mut LinearView<Line<F>, ReadWrite>,
+    #[comptime] format: GgufFormat,
+    #[define(F)] _dtype: StorageType,
+) {
+    if !output.is_in_bounds(ABSOLUTE_POS) {
+        terminate!();
+    }
+
+    let block_len = comptime!(format.block_len());
+    let base = (ABSOLUTE_POS / block_len) * comptime!(format.block_bytes());
+    let index = ABSOLUTE_POS % block_len;
+
+    let value = match format {
+        GgufFormat::Q4_0 => dequantize_q4_0(data, base, index),
+        GgufFormat::Q4_1 => dequantize_q4_1(data, base, index),
+        GgufFormat::Q5_0 => dequantize_q5_0(data, base, index),
+        GgufFormat::Q8_0 => dequantize_q8_0(data, base, index),
+        GgufFormat::Q4_K => dequantize_q4_k(data, base, index),
+        GgufFormat::Q6_K => dequantize_q6_k(data, base, index),
+    };
+
+    output[ABSOLUTE_POS] = Line::new(F::cast_from(value));
+}
+
+/// Values `i` and `i + 16` share the byte `i`, in the low and high nibble.
+#[cube]
+fn read_nibble(data: &Array<u32>, offset: usize, index: usize) -> u32 {
+    let byte = read_byte(data, offset + index % 16);
+    (byte >> (u32::cast_from(index / 16) * 4)) & 0xF
+}
+
+#[cube]
+fn dequantize_q4_0(data: &Array<u32>, base: usize, index: usize) -> f32 {
+    let d = read_half(data, base);
+    let q = read_nibble(data, base + 2, index);
+
+    (f32::cast_from(q) - 8.0) * d
+}
+
+#[cube]
+fn dequantize_q4_1(data: &Array<u32>, base: usize, index: usize) -> f32 {
+    let d = read_half(data, base);
+    let m = read_half(data, base + 2);
+    let q = read_nibble(data, base + 4, index);
+
+    f32::cast_from(q) * d + m
+}
+
+#[cube]
+fn dequantize_q5_0(data: &Array<u32>, base: usize, index: usize) -> f32 {
+    let d = read_half(data, base);
+    let high_bits = read_byte(data, base + 2 + index / 8);
+    let high = (high_bits >> u32::cast_from(index % 8)) & 1;
+    let q = read_nibble(data, base + 6, index) | (high << 4);
+
+    (f32::cast_from(q) - 16.0) * d
+}
+
+#[cube]
+fn dequantize_q8_0(data: &Array<u32>, base: usize, index: usize) -> f32 {
+    let d = read_half(data, base);
+    let q = read_signed_byte(data, base + 2 + index);
+
+    f32::cast_from(q) * d
+}
+
+#[cube]
+fn dequantize_q4_k(data: &Array<u32>, base: usize, index: usize) -> f32 {
+    let d = read_half(data, base);
+    let dmin = read_half(data, base + 2);
+
+    // Each 32 bytes of values hold two blocks of 32, in the low then high nibbles
+    let chunk = index / 64;
+    let high = (index % 64) / 32;
+    let byte = read_byte(data, base + 16 + chunk * 32 + index % 32);
+    let q = (byte >> (u32::cast_from(high) * 4)) & 0xF;
+
+    // 6-bit scales and minimums, the last four stored in the spare high bits of the first eight
+    let block = chunk * 2 + high;
+    let scales = base + 4;
+    let mut sc = 0u32;
+    let mut m = 0u32;
+    if block < 4 {
+        sc = read_byte(data, scales + block) & 63;
+        m = read_byte(data, scales + block + 4) & 63;
+    } else {
+        let packed = read_byte(data, scales + block + 4);
+        sc = (packed & 0xF) | ((read_byte(data, scales + block - 4) >> 6) << 4);
+        m = (packed >> 4) | ((read_byte(data, scales + block) >> 6) << 4);
+    }
+
+    d * f32::cast_from(sc) * f32::cast_from(q) - dmin * f32::cast_from(m)
+}
+
+#[cube]
+fn dequantize_q6_k(data: &Array<u32>, base: usize, index: usize) -> f32 {
+    // Each half of the super-block has 64 bytes of low bits and 32 bytes of high bits, every byte
+    // being shared by values 32 apart
+    let half = index / 128;
+    let quarter = (index % 128) / 32;
+    let l = index % 32;
+
+    let low_byte = read_byte(data, base + half * 64 + (quarter % 2) * 32 + l);
+    let low = (low_byte >> (u32::cast_from(quarter / 2) * 4)) & 0xF;
+    let high_byte = read_byte(data, base + 128 + half * 32 + l);
+    let high = (high_byte >> (u32::cast_from(quarter) * 2)) & 3;
+    let q = i32::cast_from(low | (high << 4)) - 32;
+
+    let sc = read_signed_byte(data, base + 192 + half * 8 + quarter * 2 + l / 16);
+    let d = read_half(data, base + 208);
+
+    d * f32::cast_from(sc) * f32::cast_from(q)
+}
+
+#[cube]
+fn read_byte(data: &Array<u32>, offset: usize) -> u32 {
+    (data[offset / 4] >> (u32::cast_from(offset % 4) * 8)) & 0xFF
+}
+
+#[cube]
+fn read_signed_byte(data: &Array<u32>, offset: usize) -> i32 {
+    i32::cast_from(read_byte(data, offset) ^ 0x80) - 128
+}
+
+/// Little endian `f16` at a byte offset, decoded from its bits so `f16` support isn't needed.
+#[cube]
+fn read_half(data: &Array<u32>, offset: usize) -> f32 {
+    let bits = read_byte(data, offset) | (read_byte(data, offset + 1) << 8);
+    let sign = (bits & 0x8000) << 16;
+    let exponent = (bits >> 10) & 0x1F;
+    let mantissa = bits & 0x3FF;
+
+    // Subnormals are multiples of 2^-24
+    let mut magnitude = f32::cast_from(mantissa) * 5.9604645e-8;
+    if exponent == 0x1F {
+        magnitude = f32::reinterpret(0x7F80_0000 | (mantissa << 13));
+    } else if exponent > 0 {
+        magnitude = f32::reinterpret(((exponent + 112) << 23) | (mantissa << 13));
+    }
+
+    f32::reinterpret(sign | u32::reinterpret(magnitude))
+}
+
+#[allow(clippy::result_large_err)]
+/// Dequantize the raw bytes of a GGUF tensor into `output`.
+///
+/// `data` holds the blocks back to back, as they are stored in the file, covering the values of
+/// `output` in row-major order. The last dimension of `output` must be a multiple of the
+/// [block length](GgufFormat::block_len), and `data` must hold all the blocks, padded to a multiple
+/// of 4 bytes.
+pub fn dequantize_gguf<R: Runtime>(
+    client: &ComputeClient<R>,
+    data: &Handle,
+    output: &TensorHandleRef<R>,
+    format: GgufFormat,
+    output_dtype: StorageType,
+) -> Result<(), QuantError> {
+    check_last_dim(output.shape, format.block_len())?;
+
+    let num_elems = output.shape.iter().product::<usize>();
+    let num_bytes = num_elems / format.block_len() * format.block_bytes();
+    let required_bytes = num_bytes.next_multiple_of(4);
+    if (data.size() as usize) < required_bytes {
+        return Err(QuantError::ShapeMismatch {
+            details: format!(
+                "The data holds {} bytes, but {required_bytes} bytes are required for the {num_elems} values of the output",
+                data.size()
+            ),
+        });
+    }
+
+    let cube_dim = CubeDim::new(client, num_elems);
+    let cube_count = calculate_cube_count_elemwise(client, num_elems, cube_dim);
+
+    unsafe {
+        dequantize_gguf_kernel::launch_unchecked(
+            client,
+            cube_count,
+            cube_dim,
+            ArrayArg::from_raw_parts::<u32>(data, num_bytes.div_ceil(4), 1),
+            linear_view(client, output, 1),
+            format,
+            output_dtype,
+        )
+        .map_err(QuantError::Launch)
+    }
+}
@@ -15,6 +15,7 @@ use crate::scheme::{QuantLevel, QuantScheme};
 pub enum ScalesLayout {
     PerTensor(PerTensorLayout),
     BlockScaled(BlockScaledLayout),
+    PerAxis(PerAxisLayout),
 }
 
 #[cube]
@@ -26,6 +27,7 @@ impl Layout for ScalesLayout {
         match self {
             ScalesLayout::PerTensor(layout) => layout.to_source_pos(pos),
             ScalesLayout::BlockScaled(layout) => layout.to_source_pos(pos),
+            ScalesLayout::PerAxis(layout) => layout.to_source_pos(pos),
         }
     }
 
@@ -33,6 +35,7 @@ impl Layout for ScalesLayout {
         match self {
             ScalesLayout::PerTensor(layout) => layout.shape(),
             ScalesLayout::BlockScaled(layout) => layout.shape(),
+            ScalesLayout::PerAxis(layout) => layout.shape(),
         }
     }
 
@@ -40,6 +43,7 @@ impl Layout for ScalesLayout {
         match self {
             ScalesLayout::PerTensor(layout) => layout.is_in_bounds(pos),
             ScalesLayout::BlockScaled(layout) => layout.is_in_bounds(pos),
+            ScalesLayout::PerAxis(layout) => layout.is_in_bounds(pos),
         }
     }
 
@@ -47,6 +51,7 @@ impl Layout for ScalesLayout {
         match self {
             ScalesLayout::PerTensor(layout) => layout.to_source_pos_checked(pos),
             ScalesLayout::BlockScaled(layout) => layout.to_source_pos_checked(pos),
+            ScalesLayout::PerAxis(layout) => layout.to_source_pos_checked(pos),
         }
     }
 }
@@ -59,6 +64,7 @@ impl ScalesLayout {
         match self {
             ScalesLayout::PerTensor(layout) => layout.is_block_start(pos),
             ScalesLayout::BlockScaled(layout) => layout.is_block_start(pos),
+            ScalesLayout::PerAxis(layout) => layout.is_block_start(pos),
         }
     }
 }
@@ -194,6 +200,76 @@ impl BlockScaledLayout {
     }
 }
 
+/// One scale per index of an axis of the values, shared by all the other dimensions. Used for
+/// per-channel quantization, where the channel axis isn't necessarily the innermost one.
+#[derive(CubeType, CubeLaunch)]
+pub struct PerAxisLayout {
+    /// Number of values after the axis, the distance between two consecutive scales.
+    inner_len: FastDivmod<usize>,
+    axis_len: FastDivmod<usize>,
+    /// Number of scales, the size of the axis.
+    num_scales: usize,
+    tensor_len: usize,
+    scales_stride: usize,
+    #[cube(comptime)]
+    scales_line_size: usize,
+}
+
+#[cube]
+impl PerAxisLayout {
+    pub fn new(
+        inner_len: FastDivmod<usize>,
+        axis_len: FastDivmod<usize>,
+        num_scales: usize,
+        tensor_len: usize,
+        scales_stride: usize,
+        #[comptime] scales_line_size: usize,
+    ) -> Self {
+        PerAxisLayout {
+            inner_len,
+            axis_len,
+            num_scales,
+            tensor_len,
+            scales_stride,
+            scales_line_size,
+        }
+    }
+}
+
+#[cube]
+impl Layout for PerAxisLayout {
+    type Coordinates = Coords1d;
+    type SourceCoordinates = Coords1d;
+
+    fn to_source_pos(&self, pos: Self::Coordinates) -> Self::SourceCoordinates {
+        let channel = self.axis_len.modulo(self.inner_len.div(pos));
+        channel * self.scales_stride / self.scales_line_size
+    }
+
+    fn shape(&self) -> Self::Coordinates {
+        self.tensor_len
+    }
+
+    fn is_in_bounds(&self, pos: Self::Coordinates) -> bool {
+        pos < self.tensor_len
+    }
+
+    fn to_source_pos_checked(&self, pos: Self::Coordinates) -> (Self::SourceCoordinates, bool) {
+        (self.to_source_pos(pos), self.is_in_bounds(pos))
+    }
+}
+
+#[cube]
+impl PerAxisLayout {
+    /// Whether the position is at the start of a new block. Used for electing a unit to write each
+    /// scale.
+    pub fn is_block_start(&self, pos: usize) -> bool {
+        // The first value of each channel, all outer coordinates being zero
+        let (channel, offs_local) = self.inner_len.div_mod(pos);
+        offs_local == 0 && channel < self.num_scales
+    }
+}
+
 /// TensorView with a linear layout inferred from the shape/strides at launch.
 /// Useful for elementwise kernels.
 pub type ScalesView<E, IO = ReadOnly> = TypedView<E, ScalesLayout, IO>;
@@ -209,7 +285,21 @@ pub fn scales_view<'a, R: Runtime>(
     scales_line_size: usize,
     quant_scheme: &QuantScheme,
 ) -> ScalesViewLaunch<'a, R> {
-    let layout = scales_layout(client, values, scales, scales_line_size, quant_scheme);
+    scales_view_with_axis(client, values, scales, scales_line_size, quant_scheme, None)
+}
+
+/// Create a scales view like [scales_view], with one scale per index of `axis` of the values when
+/// it is set, instead of following the level of the scheme.
+pub fn scales_view_with_axis<'a, R: Runtime>(
+    client: &ComputeClient<R>,
+    values: &'a TensorHandleRef<'a, R>,
+    scales: &'a TensorHandleRef<'a, R>,
+    scales_line_size: usize,
+    quant_scheme: &QuantScheme,
+    axis: Option<usize>,
+) -> ScalesViewLaunch<'a, R> {
+    let layout =
+        scales_layout_with_axis(client, values, scales, scales_line_size, quant_scheme, axis);
     let len = scales.shape.iter().product::<usize>();
     let buffer = unsafe {
         ArrayArg::from_raw_parts_and_size(scales.handle, len, scales_line_size, scales.elem_size)
@@ -223,8 +313,34 @@ pub fn scales_layout<'a, R: Runtime>(
     scales: &'a TensorHandleRef<'a, R>,
     scales_line_size: usize,
     scheme: &QuantScheme,
+) -> ScalesLayoutArgs<'a, R> {
+    scales_layout_with_axis(client, values, scales, scales_line_size, scheme, None)
+}
+
+/// Create a scales layout like [scales_layout], with one scale per index of `axis` of the values
+/// when it is set, instead of following the level of the scheme.
+pub fn scales_layout_with_axis<'a, R: Runtime>(
+    client: &ComputeClient<R>,
+    values: &'a TensorHandleRef<'a, R>,
+    scales: &'a TensorHandleRef<'a, R>,
+    scales_line_size: usize,
+    scheme: &QuantScheme,
+    axis: Option<usize>,
 ) -> ScalesLayoutArgs<'a, R> {
     let values_len = values.shape.iter().product::<usize>() * scheme.num_quants();
+
+    if let Some(axis) = axis {
+        let (inner_len, axis_len) = per_axis_lens(values.shape, axis, scheme.num_quants());
+        return ScalesLayoutArgs::PerAxis(PerAxisLayoutLaunch::new(
+            FastDivmodArgs::<usize>::new(client, inner_len),
+            FastDivmodArgs::<usize>::new(client, axis_len),
+            ScalarArg::new(axis_len),
+            ScalarArg::new(values_len),
+            ScalarArg::new(scales.strides[axis]),
+            scales_line_size,
+        ));
+    }
+
     let values_len = ScalarArg::new(values_len);
 
     match &scheme.level {
@@ -264,3 +380,20 @@ fn strides_seq<'a, R: Runtime>(strides: &'a [usize]) -> SequenceArg<'a, R, usize
     }
     out_seq
 }
+
+/// Number of values after `axis` and along `axis`, from the shape of the quantized tensor.
+pub fn per_axis_lens(shape: &[usize], axis: usize, num_quants: usize) -> (usize, usize) {
+    let rank = shape.len();
+    assert!(
+        axis < rank,
+        "Axis {axis} out of bounds for a tensor of rank {rank}"
+    );
+
+    let unpacked = |dim: usize| match dim == rank - 1 {
+        true => shape[dim] * num_quants,
+        false => shape[dim],
+    };
+    let inner_len = (axis + 1..rank).map(unpacked).product();
+
+    (inner_len, unpacked(axis))
+}
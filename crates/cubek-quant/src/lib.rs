@@ -11,13 +11,53 @@ pub mod quantize;
 #[cfg(feature = "kernels")]
 pub mod layout;
 
+#[cfg(feature = "kernels")]
+pub mod calibration;
+
+#[cfg(feature = "kernels")]
+pub mod codebook;
+
+#[cfg(feature = "kernels")]
+pub mod fake_quantize;
+
+#[cfg(feature = "kernels")]
+pub mod gguf;
+
+#[cfg(feature = "kernels")]
+pub mod mx;
+
+#[cfg(feature = "kernels")]
+pub mod nvfp4;
+
+#[cfg(feature = "kernels")]
+pub mod requantize;
+
+#[cfg(feature = "kernels")]
+mod error;
+
+#[cfg(feature = "kernels")]
+mod support;
+
 pub use cubecl_common::quant::scheme;
 
+#[cfg(feature = "kernels")]
+pub use error::*;
+
+#[cfg(feature = "kernels")]
+pub use support::*;
+
 #[cfg(feature = "kernels")]
 pub(crate) mod utils {
+    use alloc::format;
+
+    use crate::QuantError;
+    use crate::layout::per_axis_lens;
     use crate::scheme::{QuantLevel, QuantScheme};
 
-    pub(crate) fn check_block_size_compat(scheme: &QuantScheme, div: usize) {
+    pub(crate) fn check_block_size_compat(
+        scheme: &QuantScheme,
+        div: usize,
+    ) -> Result<(), QuantError> {
         // Validate block size compatibility
         if let QuantScheme {
             level: QuantLevel::Block(block_size),
@@ -25,10 +65,83 @@ pub(crate) mod utils {
         } = scheme
         {
             let block_size = *block_size.as_slice().last().unwrap() as usize;
-            assert!(
-                block_size.is_multiple_of(div),
-                "Block size must be divisible by {div}, got block_size={block_size}"
-            );
+            if !block_size.is_multiple_of(div) {
+                return Err(QuantError::BlockSizeMismatch { block_size, div });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validate that the tensor has at least one dimension.
+    pub(crate) fn check_rank(shape: &[usize]) -> Result<(), QuantError> {
+        match shape.is_empty() {
+            true => Err(QuantError::ShapeMismatch {
+                details: "Quantized tensors must have at least one dimension".into(),
+            }),
+            false => Ok(()),
+        }
+    }
+
+    /// Validate that the quantization axis is in bounds.
+    pub(crate) fn check_axis(shape: &[usize], axis: Option<usize>) -> Result<(), QuantError> {
+        match axis {
+            Some(axis) if axis >= shape.len() => Err(QuantError::ShapeMismatch {
+                details: format!(
+                    "Axis {axis} out of bounds for a tensor of rank {}",
+                    shape.len()
+                ),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Validate that each scale of a per-axis scheme covers a multiple of `div` values.
+    pub(crate) fn check_axis_compat(
+        shape: &[usize],
+        axis: Option<usize>,
+        num_quants: usize,
+        div: usize,
+    ) -> Result<(), QuantError> {
+        if let Some(axis) = axis {
+            let (inner_len, _) = per_axis_lens(shape, axis, num_quants);
+            if !inner_len.is_multiple_of(div) {
+                return Err(QuantError::ShapeMismatch {
+                    details: format!(
+                        "Values after the quantization axis must be a multiple of {div}, got {inner_len}"
+                    ),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validate that the last dimension holds whole blocks of `block_len` values.
+    pub(crate) fn check_last_dim(shape: &[usize], block_len: usize) -> Result<(), QuantError> {
+        check_rank(shape)?;
+
+        let last = shape[shape.len() - 1];
+        match last.is_multiple_of(block_len) {
+            true => Ok(()),
+            false => Err(QuantError::ShapeMismatch {
+                details: format!(
+                    "The last dimension must be a multiple of {block_len}, got {last}"
+                ),
+            }),
+        }
+    }
+
+    /// Line size of native values, falling back to scalars when a line would span several scales of
+    /// a per-axis scheme.
+    pub(crate) fn native_line_size(
+        shape: &[usize],
+        axis: Option<usize>,
+        line_size: usize,
+    ) -> usize {
+        match axis {
+            Some(axis) if !per_axis_lens(shape, axis, 1).0.is_multiple_of(line_size) => 1,
+            _ => line_size,
         }
     }
 }
@@ -0,0 +1,254 @@
+//! OCP microscaling (MX) formats: blocks of 32 values sharing a power-of-two `E8M0` scale, with
+//! `E4M3` or `E5M2` (MXFP8) or `E2M1` (MXFP4) elements.
+
+use cubecl::calculate_cube_count_elemwise;
+use cubecl::ir::{ElemType, FloatKind};
+use cubecl::prelude::*;
+use cubecl::std::tensor::layout::linear::{LinearView, linear_view};
+use cubecl::tensor_line_size_parallel;
+
+use crate::{
+    QuantError, check_native_support,
+    layout::{ScalesView, scales_view},
+    scheme::{QuantLevel, QuantMode, QuantParam, QuantScheme, QuantStore, QuantValue},
+    utils::check_last_dim,
+};
+
+/// Number of values sharing a scale.
+pub const MX_BLOCK_SIZE: usize = 32;
+
+#[allow(clippy::result_large_err)]
+/// Scheme of an MX format with the given element type, or an error for values without an MX
+/// format.
+///
+/// `E4M3` and `E5M2` values are stored natively, one per byte. `E2M1` values are packed two per
+/// byte along the last dimension, the first value in the low nibble.
+pub fn mx_scheme(value: QuantValue) -> Result<QuantScheme, QuantError> {
+    let scheme = mx_block_scheme(value);
+
+    match value {
+        QuantValue::E4M3 | QuantValue::E5M2 => Ok(scheme.with_store(QuantStore::Native)),
+        QuantValue::E2M1 => Ok(scheme.with_store(QuantStore::PackedNative(0))),
+        _ => Err(QuantError::UnsupportedScheme(scheme)),
+    }
+}
+
+#[allow(clippy::result_large_err)]
+/// Exponent of the largest normal value of an MX element type, or an error for values without an
+/// MX format.
+pub fn mx_emax(value: QuantValue) -> Result<i32, QuantError> {
+    match value {
+        QuantValue::E4M3 => Ok(8),
+        QuantValue::E5M2 => Ok(15),
+        QuantValue::E2M1 => Ok(2),
+        _ => Err(QuantError::UnsupportedScheme(mx_block_scheme(value))),
+    }
+}
+
+/// Blocks of [MX_BLOCK_SIZE] values with `E8M0` scales, without the store of the element type.
+fn mx_block_scheme(value: QuantValue) -> QuantScheme {
+    QuantScheme::default()
+        .with_level(QuantLevel::block([MX_BLOCK_SIZE as u8]))
+        .with_mode(QuantMode::Symmetric)
+        .with_value(value)
+        .with_param(QuantParam::UE8M0)
+}
+
+/// Validate that lines hold whole stored values of `packing` elements.
+pub(crate) fn check_packing(line_size: usize, packing: usize) -> Result<(), QuantError> {
+    match line_size.is_multiple_of(packing) {
+        true => Ok(()),
+        false => Err(QuantError::ShapeMismatch {
+            details: format!(
+                "Packed values must be read {packing} at a time, got a line size of {line_size}"
+            ),
+        }),
+    }
+}
+
+/// Storage type of the elements and the number of elements per stored value. `E2M1` elements are
+/// packed in pairs so the kernels always write whole bytes.
+pub(crate) fn element_storage(value: QuantValue) -> (StorageType, usize) {
+    match value {
+        QuantValue::E2M1 => (StorageType::Packed(ElemType::Float(FloatKind::E2M1), 2), 2),
+        _ => (ElemType::from_quant_value(value).into(), 1),
+    }
+}
+
+/// Shared scale of a block, `2^(floor(log2(amax)) - emax)` clamped to the range of `E8M0`.
+#[cube]
+pub fn mx_scale(amax: f32, #[comptime] emax: i32) -> f32 {
+    // floor(log2(amax)) is the unbiased exponent, zeros and subnormals get the smallest scale.
+    let biased = u32::reinterpret(amax) >> 23;
+    let exponent = f32::cast_from(biased) - f32::cast_from(127 + emax);
+    let exponent = i32::cast_from(f32::min(f32::max(exponent, -127.0), 127.0));
+
+    let mut scale = f32::reinterpret(0x0040_0000u32); // 2^-127 is subnormal in f32
+    if exponent > -127 {
+        scale = f32::reinterpret(u32::cast_from(exponent + 127) << 23);
+    }
+
+    scale
+}
+
+/// One unit per block, which finds the largest magnitude, writes the shared scale and converts the
+/// scaled values. Values are scaled in `f32` so power-of-two scales are exact, and the conversion
+/// to the element type rounds to nearest even, saturating to its largest normal value.
+#[cube(launch_unchecked)]
+fn quantize_mx_kernel<F: Float, FS: Numeric, Q: Numeric>(
+    input: &LinearView<Line<F>>,
+    output: &mut LinearView<Line<Q>, ReadWrite>,
+    out_scale: &mut ScalesView<FS, ReadWrite>,
+    range_max: f32,
+    #[comptime] emax: i32,
+    #[define(F, FS, Q)] _dtypes: [StorageType; 3],
+) {
+    let line_size = input.line_size();
+    let lines_per_block = comptime!(MX_BLOCK_SIZE / line_size);
+    let first_line = ABSOLUTE_POS * lines_per_block;
+
+    if !input.is_in_bounds(first_line) {
+        terminate!();
+    }
+
+    let mut amax = 0.0f32;
+
+    #[unroll]
+    for i in 0..lines_per_block {
+        let line = Line::<f32>::cast_from(input[first_line + i]);
+
+        #[unroll]
+        for j in 0..line_size {
+            amax = f32::max(amax, f32::abs(line[j]));
+        }
+    }
+
+    let scale = mx_scale(amax, emax);
+    out_scale[first_line * line_size] = FS::cast_from(scale);
+
+    #[unroll]
+    for i in 0..lines_per_block {
+        let values = Line::<f32>::cast_from(input[first_line + i]) / Line::new(scale);
+        output[first_line + i] =
+            Line::cast_from(clamp(values, Line::new(-range_max), Line::new(range_max)));
+    }
+}
+
+#[allow(clippy::result_large_err)]
+/// Quantize the input tensor to an MX format, computing the shared scale of every block of
+/// [MX_BLOCK_SIZE] values along the last dimension.
+///
+/// `output` and `out_scale` follow the layout of [mx_scheme], the scales being stored as `E8M0`
+/// with the shape of the input divided by [MX_BLOCK_SIZE] on the last dimension. Quantizing the
+/// dequantized values reproduces the same bits.
+pub fn quantize_mx<R: Runtime>(
+    client: &ComputeClient<R>,
+    input: &TensorHandleRef<R>,
+    output: &TensorHandleRef<R>,
+    out_scale: &TensorHandleRef<'_, R>,
+    value: QuantValue,
+    input_elem: ElemType,
+) -> Result<(), QuantError> {
+    let scheme = mx_scheme(value)?;
+    let rank = input.shape.len();
+    check_last_dim(input.shape, MX_BLOCK_SIZE)?;
+    check_native_support(client)?;
+
+    let (quant_dtype, packing) = element_storage(value);
+    let line_size = tensor_line_size_parallel(
+        client.io_optimized_line_sizes_unchecked(input.elem_size),
+        input.shape,
+        input.strides,
+        rank - 1,
+    )
+    .min(MX_BLOCK_SIZE);
+    check_packing(line_size, packing)?;
+
+    let num_blocks = input.shape.iter().product::<usize>() / MX_BLOCK_SIZE;
+    let cube_dim = CubeDim::new(client, num_blocks);
+    let cube_count = calculate_cube_count_elemwise(client, num_blocks, cube_dim);
+    let (_, range_max) = value.range();
+
+    unsafe {
+        quantize_mx_kernel::launch_unchecked(
+            client,
+            cube_count,
+            cube_dim,
+            linear_view(client, input, line_size),
+            linear_view(client, output, line_size / packing),
+            scales_view(client, output, out_scale, 1, &scheme),
+            ScalarArg::new(range_max),
+            mx_emax(value)?,
+            [
+                input_elem.into(),
+                ElemType::from_quant_param(scheme.param).into(),
+                quant_dtype,
+            ],
+        )
+        .map_err(QuantError::Launch)
+    }
+}
+
+/// One unit per line of output values, all sharing the same scale since lines never cross blocks.
+#[cube(launch_unchecked)]
+fn dequantize_mx_kernel<F: Float, FS: Numeric, Q: Numeric>(
+    input: &LinearView<Line<Q>>,
+    scale: &ScalesView<FS>,
+    output: &mut LinearView<Line<F>, ReadWrite>,
+    #[define(F, FS, Q)] _dtypes: [StorageType; 3],
+) {
+    if !output.is_in_bounds(ABSOLUTE_POS) {
+        terminate!();
+    }
+
+    let scale = f32::cast_from(scale[ABSOLUTE_POS * output.line_size()]);
+    let values = Line::<f32>::cast_from(input[ABSOLUTE_POS]) * Line::new(scale);
+    output[ABSOLUTE_POS] = Line::cast_from(values);
+}
+
+#[allow(clippy::result_large_err)]
+/// Convert a tensor quantized by [quantize_mx] back to a higher precision data type.
+pub fn dequantize_mx<R: Runtime>(
+    client: &ComputeClient<R>,
+    values: &TensorHandleRef<R>,
+    output: &TensorHandleRef<R>,
+    scale: &TensorHandleRef<'_, R>,
+    value: QuantValue,
+    output_dtype: StorageType,
+) -> Result<(), QuantError> {
+    let scheme = mx_scheme(value)?;
+    let rank = output.shape.len();
+    check_last_dim(output.shape, MX_BLOCK_SIZE)?;
+    check_native_support(client)?;
+
+    let (quant_dtype, packing) = element_storage(value);
+    let line_size = tensor_line_size_parallel(
+        client.io_optimized_line_sizes_unchecked(output.elem_size),
+        output.shape,
+        output.strides,
+        rank - 1,
+    )
+    .min(MX_BLOCK_SIZE);
+    check_packing(line_size, packing)?;
+
+    let num_lines = output.shape.iter().product::<usize>() / line_size;
+    let cube_dim = CubeDim::new(client, num_lines);
+    let cube_count = calculate_cube_count_elemwise(client, num_lines, cube_dim);
+
+    unsafe {
+        dequantize_mx_kernel::launch_unchecked(
+            client,
+            cube_count,
+            cube_dim,
+            linear_view(client, values, line_size / packing),
+            scales_view(client, values, scale, 1, &scheme),
+            linear_view(client, output, line_size),
+            [
+                output_dtype,
+                ElemType::from_quant_param(scheme.param).into(),
+                quant_dtype,
+            ],
+        )
+        .map_err(QuantError::Launch)
+    }
+}
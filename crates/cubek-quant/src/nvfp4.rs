@@ -0,0 +1,230 @@
+//! NVFP4: `E2M1` values in blocks of 16 sharing an `E4M3` scale, on top of a single `f32` scale for
+//! the whole tensor. The second level brings the block scales back into the narrow range of
+//! `E4M3`, so they keep a fractional precision that power-of-two MX scales don't have.
+
+use cubecl::calculate_cube_count_elemwise;
+use cubecl::ir::{ElemType, FloatKind};
+use cubecl::prelude::*;
+use cubecl::std::tensor::layout::linear::{LinearView, linear_view};
+use cubecl::tensor_line_size_parallel;
+
+use crate::{
+    QuantError, check_native_support,
+    layout::{ScalesView, scales_view},
+    mx::{check_packing, element_storage},
+    quantize::launch_absmax_scale,
+    scheme::{QuantLevel, QuantMode, QuantParam, QuantScheme, QuantStore, QuantValue},
+    utils::check_last_dim,
+};
+
+/// Number of values sharing a block scale.
+pub const NVFP4_BLOCK_SIZE: usize = 16;
+
+/// Largest value of an `E4M3` block scale.
+const BLOCK_SCALE_MAX: f32 = 448.0;
+
+/// Largest value of an `E2M1` element.
+const ELEMENT_MAX: f32 = 6.0;
+
+/// Scheme of the block scales and values, packed two per byte along the last dimension with the
+/// first value in the low nibble.
+pub fn nvfp4_scheme() -> QuantScheme {
+    QuantScheme::default()
+        .with_level(QuantLevel::block([NVFP4_BLOCK_SIZE as u8]))
+        .with_mode(QuantMode::Symmetric)
+        .with_value(QuantValue::E2M1)
+        .with_store(QuantStore::PackedNative(0))
+        .with_param(QuantParam::UE4M3)
+}
+
+/// Scheme of the global scale, one `f32` for the whole tensor.
+fn global_scheme() -> QuantScheme {
+    nvfp4_scheme()
+        .with_level(QuantLevel::Tensor)
+        .with_param(QuantParam::F32)
+}
+
+/// Storage type of the block scales. `ElemType::from_quant_param` doesn't map `UE4M3` to the right
+/// kind, and the scales being positive, the signed type has the same encoding.
+fn block_scale_elem() -> ElemType {
+    ElemType::Float(FloatKind::E4M3)
+}
+
+/// One unit per block, which writes the block scale relative to the global scale and converts the
+/// scaled values. The elements are scaled by the block scale as it was rounded to `E4M3`, so the
+/// rounding of the scale is compensated by the elements.
+#[cube(launch_unchecked)]
+fn quantize_nvfp4_kernel<F: Float, FS: Numeric, Q: Numeric>(
+    input: &LinearView<Line<F>>,
+    global_scale: &ScalesView<f32>,
+    output: &mut LinearView<Line<Q>, ReadWrite>,
+    out_scale: &mut ScalesView<FS, ReadWrite>,
+    #[define(F, FS, Q)] _dtypes: [StorageType; 3],
+) {
+    let line_size = input.line_size();
+    let lines_per_block = comptime!(NVFP4_BLOCK_SIZE / line_size);
+    let first_line = ABSOLUTE_POS * lines_per_block;
+
+    if !input.is_in_bounds(first_line) {
+        terminate!();
+    }
+
+    let mut amax = 0.0f32;
+
+    #[unroll]
+    for i in 0..lines_per_block {
+        let line = Line::<f32>::cast_from(input[first_line + i]);
+
+        #[unroll]
+        for j in 0..line_size {
+            amax = f32::max(amax, f32::abs(line[j]));
+        }
+    }
+
+    let global = global_scale[first_line * line_size];
+    let block_scale = FS::cast_from(f32::min(amax / (ELEMENT_MAX * global), BLOCK_SCALE_MAX));
+    out_scale[first_line * line_size] = block_scale;
+
+    let scale = f32::cast_from(block_scale) * global;
+
+    #[unroll]
+    for i in 0..lines_per_block {
+        // Blocks too small for the smallest block scale are flushed to zero
+        let mut values = Line::empty(line_size).fill(0.0f32);
+        if scale > 0.0 {
+            values = Line::<f32>::cast_from(input[first_line + i]) / Line::new(scale);
+        }
+
+        output[first_line + i] = Line::cast_from(clamp(
+            values,
+            Line::new(-ELEMENT_MAX),
+            Line::new(ELEMENT_MAX),
+        ));
+    }
+}
+
+/// One unit per line of output values, all sharing the same block scale since lines never cross
+/// blocks.
+#[cube(launch_unchecked)]
+fn dequantize_nvfp4_kernel<F: Float, FS: Numeric, Q: Numeric>(
+    input: &LinearView<Line<Q>>,
+    global_scale: &ScalesView<f32>,
+    scale: &ScalesView<FS>,
+    output: &mut LinearView<Line<F>, ReadWrite>,
+    #[define(F, FS, Q)] _dtypes: [StorageType; 3],
+) {
+    if !output.is_in_bounds(ABSOLUTE_POS) {
+        terminate!();
+    }
+
+    let pos = ABSOLUTE_POS * output.line_size();
+    let scale = f32::cast_from(scale[pos]) * global_scale[pos];
+    let values = Line::<f32>::cast_from(input[ABSOLUTE_POS]) * Line::new(scale);
+    output[ABSOLUTE_POS] = Line::cast_from(values);
+}
+
+#[allow(clippy::result_large_err)]
+/// Quantize the input tensor to NVFP4, computing both levels of scales.
+///
+/// The global scale `amax / (448 * 6)` is written to `out_global_scale`, a single `f32`, so the
+/// largest block scale maps to the largest `E4M3` value. The block scales of every
+/// [NVFP4_BLOCK_SIZE] values along the last dimension are written to `out_scale` as `E4M3`, with
+/// the shape of the input divided by [NVFP4_BLOCK_SIZE] on the last dimension. `output` follows
+/// the layout of [nvfp4_scheme].
+pub fn quantize_nvfp4<R: Runtime>(
+    client: &ComputeClient<R>,
+    input: &TensorHandleRef<R>,
+    output: &TensorHandleRef<R>,
+    out_scale: &TensorHandleRef<'_, R>,
+    out_global_scale: &TensorHandleRef<'_, R>,
+    input_elem: ElemType,
+) -> Result<(), QuantError> {
+    let scheme = nvfp4_scheme();
+    let rank = input.shape.len();
+    check_last_dim(input.shape, NVFP4_BLOCK_SIZE)?;
+    check_native_support(client)?;
+
+    launch_absmax_scale(
+        client,
+        input,
+        output,
+        out_global_scale,
+        &global_scheme(),
+        BLOCK_SCALE_MAX * ELEMENT_MAX,
+        input_elem,
+        ElemType::Float(FloatKind::F32),
+    )?;
+
+    let (quant_dtype, packing) = element_storage(scheme.value);
+    let line_size = tensor_line_size_parallel(
+        client.io_optimized_line_sizes_unchecked(input.elem_size),
+        input.shape,
+        input.strides,
+        rank - 1,
+    )
+    .min(NVFP4_BLOCK_SIZE);
+    check_packing(line_size, packing)?;
+
+    let num_blocks = input.shape.iter().product::<usize>() / NVFP4_BLOCK_SIZE;
+    let cube_dim = CubeDim::new(client, num_blocks);
+    let cube_count = calculate_cube_count_elemwise(client, num_blocks, cube_dim);
+
+    unsafe {
+        quantize_nvfp4_kernel::launch_unchecked(
+            client,
+            cube_count,
+            cube_dim,
+            linear_view(client, input, line_size),
+            scales_view(client, output, out_global_scale, 1, &global_scheme()),
+            linear_view(client, output, line_size / packing),
+            scales_view(client, output, out_scale, 1, &scheme),
+            [input_elem.into(), block_scale_elem().into(), quant_dtype],
+        )
+        .map_err(QuantError::Launch)
+    }
+}
+
+#[allow(clippy::result_large_err)]
+/// Convert a tensor quantized by [quantize_nvfp4] back to a higher precision data type, each
+/// value being multiplied by its block scale and the global scale.
+pub fn dequantize_nvfp4<R: Runtime>(
+    client: &ComputeClient<R>,
+    values: &TensorHandleRef<R>,
+    output: &TensorHandleRef<R>,
+    scale: &TensorHandleRef<'_, R>,
+    global_scale: &TensorHandleRef<'_, R>,
+    output_dtype: StorageType,
+) -> Result<(), QuantError> {
+    let scheme = nvfp4_scheme();
+    let rank = output.shape.len();
+    check_last_dim(output.shape, NVFP4_BLOCK_SIZE)?;
+    check_native_support(client)?;
+
+    let (quant_dtype, packing) = element_storage(scheme.value);
+    let line_size = tensor_line_size_parallel(
+        client.io_optimized_line_sizes_unchecked(output.elem_size),
+        output.shape,
+        output.strides,
+        rank - 1,
+    )
+    .min(NVFP4_BLOCK_SIZE);
+    check_packing(line_size, packing)?;
+
+    let num_lines = output.shape.iter().product::<usize>() / line_size;
+    let cube_dim = CubeDim::new(client, num_lines);
+    let cube_count = calculate_cube_count_elemwise(client, num_lines, cube_dim);
+
+    unsafe {
+        dequantize_nvfp4_kernel::launch_unchecked(
+            client,
+            cube_count,
+            cube_dim,
+            linear_view(client, values, line_size / packing),
+            scales_view(client, values, global_scale, 1, &global_scheme()),
+            scales_view(client, values, scale, 1, &scheme),
+            linear_view(client, output, line_size),
+            [output_dtype, block_scale_elem().into(), quant_dtype],
+        )
+        .map_err(QuantError::Launch)
+    }
+}
@@ -1,15 +1,18 @@
 use cubecl::calculate_cube_count_elemwise;
 use cubecl::features::TypeUsage;
-use cubecl::ir::ElemType;
+use cubecl::ir::{ElemType, UIntKind};
 use cubecl::prelude::*;
 use cubecl::std::tensor::layout::linear::LinearView;
 use cubecl::std::tensor::{TensorHandle, into_contiguous_ref};
 use cubecl::std::tensor::{View, layout::linear::linear_view};
+use cubecl::std::{FastDivmod, FastDivmodArgs};
 use cubecl::tensor_line_size_parallel;
 
 use crate::{
-    layout::{ScalesLayout, scales_view},
-    utils::check_block_size_compat,
+    QuantError, check_native_support,
+    layout::{ScalesLayout, scales_layout_with_axis, scales_view, scales_view_with_axis},
+    mx::{check_packing, element_storage},
+    utils::{check_axis, check_axis_compat, check_block_size_compat, check_rank, native_line_size},
 };
 use crate::{
     layout::{ScalesView, scales_layout},
@@ -17,7 +20,7 @@ use crate::{
 };
 
 #[cube]
-fn quantize_symmetric<F: Float, FS: CubePrimitive>(
+pub(crate) fn quantize_symmetric<F: Float, FS: CubePrimitive>(
     value: Line<F>,
     scale: FS,
     range_min: F,
@@ -54,11 +57,26 @@ fn quantize_packed_value<F: Float, FS: CubePrimitive, QS: Int>(
     pack_q::<F, QS>(value, scheme.value)
 }
 
+#[cube]
+fn quantize_affine<F: Float, FS: CubePrimitive>(
+    value: Line<F>,
+    scale: FS,
+    zero_point: FS,
+    range_min: F,
+    range_max: F,
+) -> Line<F> {
+    clamp(
+        Line::round(value / Line::cast_from(scale)) + Line::cast_from(zero_point),
+        Line::new(range_min),
+        Line::new(range_max),
+    )
+}
+
 /// Pack a line of quantized floating-point values into a single integer (the stored quantization type),
 /// according to the specified quantization input type.
 #[allow(clippy::explicit_counter_loop)]
 #[cube]
-fn pack_q<F: Float, QS: Int>(value: Line<F>, #[comptime] quant: QuantValue) -> QS {
+pub(crate) fn pack_q<F: Float, QS: Int>(value: Line<F>, #[comptime] quant: QuantValue) -> QS {
     let size_quant = quant.size_bits();
 
     let size_store = QS::type_size_bits().comptime();
@@ -110,8 +128,7 @@ fn quantize_symmetric_native_kernel<F: Float, FS: Numeric, Q: Numeric>(
         terminate!();
     }
 
-    let native_packing = Q::packing_factor();
-    let in_pos = ABSOLUTE_POS * input.line_size() * native_packing;
+    let in_pos = ABSOLUTE_POS * input.line_size();
     let scale = write_scale(in_pos, scale, out_scale, scales_layout);
 
     output[ABSOLUTE_POS] = quantize_symmetric_q::<F, FS, Q>(
@@ -168,6 +185,197 @@ fn quantize_symmetric_packed_kernel<F: Float, FS: Numeric>(
     }
 }
 
+#[cube(launch_unchecked)]
+#[allow(clippy::too_many_arguments)]
+fn quantize_affine_native_kernel<F: Float, FS: Numeric, Q: Numeric>(
+    input: &LinearView<Line<F>>,
+    scale: &ScalesView<F>,
+    zero_point: &ScalesView<F>,
+    range_min: InputScalar,
+    range_max: InputScalar,
+    output: &mut LinearView<Line<Q>, ReadWrite>,
+    out_scale: &mut ScalesView<FS, ReadWrite>,
+    out_zero_point: &mut ScalesView<FS, ReadWrite>,
+    scales_layout: ScalesLayout,
+    #[define(F, FS, Q)] _dtypes: [StorageType; 3],
+) {
+    if !output.is_in_bounds(ABSOLUTE_POS) {
+        terminate!();
+    }
+
+    let in_pos = ABSOLUTE_POS * input.line_size();
+    let scale = write_scale(in_pos, scale, out_scale, scales_layout);
+    let zero_point = write_scale(in_pos, zero_point, out_zero_point, scales_layout);
+
+    output[ABSOLUTE_POS] = Line::cast_from(quantize_affine::<F, FS>(
+        input[ABSOLUTE_POS],
+        scale,
+        zero_point,
+        range_min.get::<F>(),
+        range_max.get::<F>(),
+    ));
+}
+
+#[cube(launch_unchecked)]
+#[allow(clippy::too_many_arguments)]
+fn quantize_affine_packed_kernel<F: Float, FS: Numeric>(
+    input: &LinearView<Line<F>>,
+    scale: &ScalesView<F>,
+    zero_point: &ScalesView<F>,
+    range_min: InputScalar,
+    range_max: InputScalar,
+    output: &mut LinearView<Line<u32>, ReadWrite>,
+    out_scale: &mut ScalesView<FS, ReadWrite>,
+    out_zero_point: &mut ScalesView<FS, ReadWrite>,
+    scales_layout: ScalesLayout,
+    #[comptime] scheme: QuantScheme,
+    #[define(F, FS)] _dtypes: [StorageType; 2],
+) {
+    if !output.is_in_bounds(ABSOLUTE_POS) {
+        terminate!();
+    }
+
+    let num_quants = scheme.num_quants();
+    let packed_pos = ABSOLUTE_POS * num_quants;
+    let scale = write_scale(packed_pos, scale, out_scale, scales_layout);
+    let zero_point = write_scale(packed_pos, zero_point, out_zero_point, scales_layout);
+
+    let mut values = Line::<F>::empty(num_quants);
+    if input.line_size().comptime() == num_quants {
+        values = input[ABSOLUTE_POS];
+    } else {
+        // Input line size = 1
+        #[unroll]
+        for i in 0..num_quants {
+            values[i] = input[packed_pos + i][0];
+        }
+    }
+
+    let values = quantize_affine::<F, FS>(
+        values,
+        scale,
+        zero_point,
+        range_min.get::<F>(),
+        range_max.get::<F>(),
+    );
+    output[ABSOLUTE_POS] = Line::cast_from(pack_q::<F, u32>(values, scheme.value));
+}
+
+/// Position of the first element of `block`, in a contiguous tensor.
+#[cube]
+pub(crate) fn block_start(
+    block: usize,
+    blocks_shape: &Sequence<FastDivmod<usize>>,
+    blocks_strides: &Sequence<usize>,
+) -> usize {
+    let rank = blocks_strides.len().comptime();
+    let mut rem = block;
+    let mut start = 0;
+
+    #[unroll]
+    for i in 0..rank {
+        let dim = rank - i - 1;
+        let (next, coord) = blocks_shape[dim].div_mod(rem);
+        rem = next;
+        start += coord * blocks_strides[dim];
+    }
+
+    start
+}
+
+/// Position of the element at `index` in the row-major order of a block starting at `start`.
+#[cube]
+pub(crate) fn block_element(
+    start: usize,
+    index: usize,
+    block_shape: &Sequence<FastDivmod<usize>>,
+    strides: &Sequence<usize>,
+) -> usize {
+    let rank = strides.len().comptime();
+    let mut rem = index;
+    let mut pos = start;
+
+    #[unroll]
+    for i in 0..rank {
+        let dim = rank - i - 1;
+        let (next, coord) = block_shape[dim].div_mod(rem);
+        rem = next;
+        pos += coord * strides[dim];
+    }
+
+    pos
+}
+
+/// Write the scale of each block, derived from the maximum absolute value of its elements.
+///
+/// Each cube reduces a single block, its units striding over the elements before a tree reduction
+/// in shared memory.
+#[cube(launch_unchecked)]
+#[allow(clippy::too_many_arguments)]
+fn absmax_scale_kernel<F: Float, FS: Float>(
+    input: &LinearView<Line<F>>,
+    out_scale: &mut ScalesView<FS, ReadWrite>,
+    blocks_shape: Sequence<FastDivmod<usize>>,
+    blocks_strides: Sequence<usize>,
+    block_shape: Sequence<FastDivmod<usize>>,
+    strides: Sequence<usize>,
+    num_blocks: usize,
+    block_len: usize,
+    half_range: InputScalar,
+    #[comptime] cube_size: usize,
+    #[define(F, FS)] _dtypes: [StorageType; 2],
+) {
+    let block = CUBE_POS;
+    if block >= num_blocks {
+        terminate!();
+    }
+
+    let unit = UNIT_POS as usize;
+    let start = block_start(block, &blocks_shape, &blocks_strides);
+
+    let mut absmax = F::new(0.0);
+    let mut index = unit;
+
+    loop {
+        if index >= block_len {
+            break;
+        }
+
+        let pos = block_element(start, index, &block_shape, &strides);
+        absmax = F::max(absmax, F::abs(input[pos][0]));
+        index += CUBE_DIM as usize;
+    }
+
+    let mut shared = SharedMemory::<F>::new(cube_size);
+    shared[unit] = absmax;
+    sync_cube();
+
+    let mut stride = (cube_size / 2).runtime();
+    loop {
+        if stride == 0 {
+            break;
+        }
+
+        if unit < stride {
+            shared[unit] = F::max(shared[unit], shared[unit + stride]);
+        }
+        sync_cube();
+
+        stride /= 2;
+    }
+
+    if unit == 0 {
+        // A block of zeros keeps a unit scale instead of dividing by zero when quantizing.
+        let absmax = FS::cast_from(shared[0]);
+        let mut scale = FS::new(1.0);
+        if absmax > FS::new(0.0) {
+            scale = absmax / half_range.get::<FS>();
+        }
+
+        out_scale[start] = scale;
+    }
+}
+
 #[allow(clippy::result_large_err)]
 pub fn launch_ref<R: Runtime>(
     client: &ComputeClient<R>,
@@ -177,15 +385,64 @@ pub fn launch_ref<R: Runtime>(
     out_scale: &TensorHandleRef<'_, R>,
     scheme: &QuantScheme,
     input_elem: ElemType,
-) -> Result<(), LaunchError> {
+) -> Result<(), QuantError> {
+    launch(
+        client, input, output, scale, out_scale, scheme, None, input_elem,
+    )
+}
+
+/// Quantize the input tensor with one scale per index of `axis`, shared by all the other
+/// dimensions.
+///
+/// `scale` and `out_scale` have the rank of the input, with the size of `axis` on that axis and 1
+/// everywhere else. The axis replaces the level of the scheme, which should be
+/// [QuantLevel::Tensor]. The values after `axis` must be a multiple of the number of packed quants,
+/// so packed stores can't quantize along the packing dimension.
+#[allow(clippy::result_large_err, clippy::too_many_arguments)]
+pub fn launch_ref_per_axis<R: Runtime>(
+    client: &ComputeClient<R>,
+    input: &TensorHandleRef<R>,
+    output: &TensorHandleRef<R>,
+    scale: &TensorHandleRef<'_, R>,
+    out_scale: &TensorHandleRef<'_, R>,
+    scheme: &QuantScheme,
+    axis: usize,
+    input_elem: ElemType,
+) -> Result<(), QuantError> {
+    launch(
+        client,
+        input,
+        output,
+        scale,
+        out_scale,
+        scheme,
+        Some(axis),
+        input_elem,
+    )
+}
+
+#[allow(clippy::result_large_err, clippy::too_many_arguments)]
+fn launch<R: Runtime>(
+    client: &ComputeClient<R>,
+    input: &TensorHandleRef<R>,
+    output: &TensorHandleRef<R>,
+    scale: &TensorHandleRef<'_, R>,
+    out_scale: &TensorHandleRef<'_, R>,
+    scheme: &QuantScheme,
+    axis: Option<usize>,
+    input_elem: ElemType,
+) -> Result<(), QuantError> {
+    check_rank(input.shape)?;
+    check_rank(output.shape)?;
     let param_elem = ElemType::from_quant_param(scheme.param);
+    check_axis(input.shape, axis)?;
 
     match scheme {
         QuantScheme {
             store: QuantStore::PackedU32(_),
             ..
         } => quantize_packed(
-            client, input, scheme, scale, out_scale, output, input_elem, param_elem,
+            client, input, scheme, axis, scale, out_scale, output, input_elem, param_elem,
         ),
         QuantScheme {
             value: QuantValue::Q8F | QuantValue::Q8S | QuantValue::E4M3 | QuantValue::E5M2,
@@ -197,24 +454,16 @@ pub fn launch_ref<R: Runtime>(
             store: QuantStore::PackedNative(_),
             ..
         } => {
-            if !i8::supported_uses(client).contains(TypeUsage::Conversion) {
-                panic!(
-                    "{:?} is not supported for native quantization",
-                    scheme.value
-                );
-            }
+            check_native_support(client)?;
 
             quantize_native(
-                client, input, scheme, scale, out_scale, output, input_elem, param_elem,
+                client, input, scheme, axis, scale, out_scale, output, input_elem, param_elem,
             )
         }
         QuantScheme {
             store: QuantStore::Native | QuantStore::PackedNative(_),
-            value,
             ..
-        } => {
-            panic!("{value:?} is not supported for native quantization");
-        }
+        } => Err(QuantError::UnsupportedScheme(*scheme)),
     }
 }
 
@@ -223,12 +472,13 @@ fn quantize_native<R: Runtime>(
     client: &ComputeClient<R>,
     input: &TensorHandleRef<R>,
     scheme: &QuantScheme,
+    axis: Option<usize>,
     scale: &TensorHandleRef<'_, R>,
     out_scale: &TensorHandleRef<'_, R>,
     output: &TensorHandleRef<R>,
     input_dtype: ElemType,
     scale_dtype: ElemType,
-) -> Result<(), LaunchError> {
+) -> Result<(), QuantError> {
     let num_elems: usize = input.shape.iter().product();
     let line_size = tensor_line_size_parallel(
         client.io_optimized_line_sizes_unchecked(input.elem_size),
@@ -236,6 +486,10 @@ fn quantize_native<R: Runtime>(
         input.strides,
         input.shape.len() - 1,
     );
+    let line_size = native_line_size(input.shape, axis, line_size);
+    // Lines of output values hold whole stored values
+    let (quant_dtype, packing) = element_storage(scheme.value);
+    check_packing(line_size, packing)?;
     let working_units = num_elems / line_size as usize;
     let cube_dim = CubeDim::new(client, working_units);
     let cube_count = calculate_cube_count_elemwise(client, working_units, cube_dim);
@@ -245,12 +499,17 @@ fn quantize_native<R: Runtime>(
         QuantScheme {
             level: QuantLevel::Tensor | QuantLevel::Block(_),
             mode: QuantMode::Symmetric,
-            store: QuantStore::Native,
+            store: QuantStore::Native | QuantStore::PackedNative(_),
             ..
         } => {
             // We could use line_size = block_size if it's in the supported line sizes.. but let's keep it simple
-            check_block_size_compat(scheme, line_size as usize);
-            let quant_type = ElemType::from_quant_value(scheme.value);
+            check_block_size_compat(scheme, line_size as usize)?;
+            check_axis_compat(
+                output.shape,
+                axis,
+                scheme.num_quants(),
+                line_size.max(scheme.num_quants()),
+            )?;
 
             unsafe {
                 quantize_symmetric_native_kernel::launch_unchecked(
@@ -259,17 +518,18 @@ fn quantize_native<R: Runtime>(
                     cube_dim,
                     linear_view(client, input, line_size),
                     // scale is computed based on input float dtype, but stored based on qparams precision
-                    scales_view(client, output, scale, 1, scheme),
+                    scales_view_with_axis(client, output, scale, 1, scheme, axis),
                     InputScalar::new(range_min, input_dtype),
                     InputScalar::new(range_max, input_dtype),
-                    linear_view(client, output, line_size),
-                    scales_view(client, output, out_scale, 1, scheme),
-                    scales_layout(client, output, scale, 1, scheme),
-                    [input_dtype.into(), scale_dtype.into(), quant_type.into()],
+                    linear_view(client, output, line_size / packing),
+                    scales_view_with_axis(client, output, out_scale, 1, scheme, axis),
+                    scales_layout_with_axis(client, output, scale, 1, scheme, axis),
+                    [input_dtype.into(), scale_dtype.into(), quant_dtype],
                 )
+                .map_err(QuantError::Launch)
             }
         }
-        _ => panic!("Unsupported quantization scheme {scheme:?}"),
+        _ => Err(QuantError::UnsupportedScheme(*scheme)),
     }
 }
 
@@ -278,47 +538,37 @@ fn quantize_packed<R: Runtime>(
     client: &ComputeClient<R>,
     input: &TensorHandleRef<R>,
     scheme: &QuantScheme,
+    axis: Option<usize>,
     scale: &TensorHandleRef<'_, R>,
     out_scale: &TensorHandleRef<'_, R>,
     output: &TensorHandleRef<R>,
     dtype_input: ElemType,
     dtype_param: ElemType,
-) -> Result<(), LaunchError> {
-    let num_elems: usize = input.shape.iter().product();
-
-    // Determine if we can use vectorized packing
-    let mut can_vectorize = match scheme {
+) -> Result<(), QuantError> {
+    if !matches!(
+        scheme,
         QuantScheme {
             level: QuantLevel::Tensor | QuantLevel::Block(_),
             mode: QuantMode::Symmetric,
-            store: QuantStore::PackedU32(dim),
+            store: QuantStore::PackedU32(_),
             ..
-        } => {
-            // Check if packing dim is contiguous
-            let ndims = input.shape.len();
-            input.strides[ndims - 1 - *dim] == 1
         }
-        QuantScheme { .. } => panic!("Unsupported quantization scheme {scheme:?}"),
-    };
-    // For larger tensors, copying to contiguous memory should be faster than scalar reads.
-    // 2048 is a conservative floor for the threshold, could be tuned.
+    ) {
+        return Err(QuantError::UnsupportedScheme(*scheme));
+    }
+
+    let num_elems: usize = input.shape.iter().product();
     let num_quants = scheme.num_quants();
-    let input = if !can_vectorize && num_elems >= 2048 {
-        can_vectorize = true;
-        into_contiguous_ref(client, input, dtype_input.into()).expect("Kernel to never fail")
-    } else {
-        TensorHandle::from_ref(input, dtype_input.into())
-    };
+    check_block_size_compat(scheme, num_quants)?; // 32 / 8 = 4
+    check_axis_compat(output.shape, axis, num_quants, num_quants)?;
 
-    // Elements to pack are strided, require scalar reads + manual gather
-    let line_size = if can_vectorize { num_quants } else { 1 };
+    let (input, line_size) = packed_input(client, input, scheme, dtype_input)?;
 
     let working_units = num_elems.div_ceil(line_size);
     let cube_dim = CubeDim::new(client, working_units);
     let cube_count = calculate_cube_count_elemwise(client, working_units, cube_dim);
     let (range_min, range_max) = scheme.value.range();
 
-    check_block_size_compat(scheme, num_quants); // 32 / 8 = 4
     unsafe {
         quantize_symmetric_packed_kernel::launch_unchecked(
             client,
@@ -326,14 +576,359 @@ fn quantize_packed<R: Runtime>(
             cube_dim,
             linear_view(client, &input.as_ref(), line_size),
             // scale is computed based on input float dtype, but stored based on qparams precision
-            scales_view(client, output, scale, 1, scheme),
+            scales_view_with_axis(client, output, scale, 1, scheme, axis),
             InputScalar::new(range_min, dtype_input),
             InputScalar::new(range_max, dtype_input),
             linear_view(client, output, 1),
-            scales_view(client, output, out_scale, 1, scheme),
-            scales_layout(client, output, scale, 1, scheme),
+            scales_view_with_axis(client, output, out_scale, 1, scheme, axis),
+            scales_layout_with_axis(client, output, scale, 1, scheme, axis),
             *scheme,
             [dtype_input.into(), dtype_param.into()],
         )
+        .map_err(QuantError::Launch)
+    }
+}
+
+/// Unsigned range of the integer values of affine quantization, `[0, 2^bits - 1]`.
+///
+/// Affine quantization shifts values by their zero-point instead of centering them on zero, so the
+/// full and symmetric variants of an integer [QuantValue] share the same unsigned range. Other
+/// values are rejected by [launch_ref_affine] before their range is needed.
+pub(crate) fn affine_range(value: QuantValue) -> (f32, f32) {
+    match value {
+        QuantValue::Q8F
+        | QuantValue::Q8S
+        | QuantValue::Q4F
+        | QuantValue::Q4S
+        | QuantValue::Q2F
+        | QuantValue::Q2S => (0.0, ((1u32 << value.size_bits()) - 1) as f32),
+        other => panic!("{other:?} is not supported for affine quantization"),
+    }
+}
+
+/// Quantize the input tensor with a zero-point, `q = clamp(round(x / scale) + zero_point)` in the
+/// unsigned range `[0, 2^bits - 1]` of the integer values.
+///
+/// `zero_point` and `out_zero_point` have the same shape and element types as `scale` and
+/// `out_scale`, with one zero-point per tensor or per block depending on the level of the scheme.
+/// Values are stored as unsigned integers, in `u8` with the native store or packed in `u32`. The
+/// mode of the scheme is ignored, the zero-point selecting the affine mapping.
+#[allow(clippy::result_large_err, clippy::too_many_arguments)]
+pub fn launch_ref_affine<R: Runtime>(
+    client: &ComputeClient<R>,
+    input: &TensorHandleRef<R>,
+    output: &TensorHandleRef<R>,
+    scale: &TensorHandleRef<'_, R>,
+    zero_point: &TensorHandleRef<'_, R>,
+    out_scale: &TensorHandleRef<'_, R>,
+    out_zero_point: &TensorHandleRef<'_, R>,
+    scheme: &QuantScheme,
+    input_elem: ElemType,
+) -> Result<(), QuantError> {
+    check_rank(input.shape)?;
+    check_rank(output.shape)?;
+    if !matches!(
+        scheme.value,
+        QuantValue::Q8F
+            | QuantValue::Q8S
+            | QuantValue::Q4F
+            | QuantValue::Q4S
+            | QuantValue::Q2F
+            | QuantValue::Q2S
+    ) {
+        return Err(QuantError::UnsupportedScheme(*scheme));
+    }
+
+    let param_elem = ElemType::from_quant_param(scheme.param);
+    let (range_min, range_max) = affine_range(scheme.value);
+
+    match scheme {
+        QuantScheme {
+            level: QuantLevel::Tensor | QuantLevel::Block(_),
+            store: QuantStore::PackedU32(_),
+            ..
+        } => {
+            check_block_size_compat(scheme, scheme.num_quants())?;
+
+            let (input, line_size) = packed_input(client, input, scheme, input_elem)?;
+            let num_elems: usize = input.shape.iter().product();
+
+            let working_units = num_elems.div_ceil(line_size);
+            let cube_dim = CubeDim::new(client, working_units);
+            let cube_count = calculate_cube_count_elemwise(client, working_units, cube_dim);
+
+            unsafe {
+                quantize_affine_packed_kernel::launch_unchecked(
+                    client,
+                    cube_count,
+                    cube_dim,
+                    linear_view(client, &input.as_ref(), line_size),
+                    scales_view(client, output, scale, 1, scheme),
+                    scales_view(client, output, zero_point, 1, scheme),
+                    InputScalar::new(range_min, input_elem),
+                    InputScalar::new(range_max, input_elem),
+                    linear_view(client, output, 1),
+                    scales_view(client, output, out_scale, 1, scheme),
+                    scales_view(client, output, out_zero_point, 1, scheme),
+                    scales_layout(client, output, scale, 1, scheme),
+                    *scheme,
+                    [input_elem.into(), param_elem.into()],
+                )
+                .map_err(QuantError::Launch)
+            }
+        }
+        QuantScheme {
+            level: QuantLevel::Tensor | QuantLevel::Block(_),
+            value: QuantValue::Q8F | QuantValue::Q8S,
+            store: QuantStore::Native,
+            ..
+        } => {
+            if !u8::supported_uses(client).contains(TypeUsage::Conversion) {
+                return Err(QuantError::MissingTypeSupport(
+                    u8::as_type_native_unchecked(),
+                ));
+            }
+
+            let num_elems: usize = input.shape.iter().product();
+            let line_size = tensor_line_size_parallel(
+                client.io_optimized_line_sizes_unchecked(input.elem_size),
+                input.shape,
+                input.strides,
+                input.shape.len() - 1,
+            );
+            let working_units = num_elems / line_size;
+            let cube_dim = CubeDim::new(client, working_units);
+            let cube_count = calculate_cube_count_elemwise(client, working_units, cube_dim);
+
+            check_block_size_compat(scheme, line_size)?;
+            unsafe {
+                quantize_affine_native_kernel::launch_unchecked(
+                    client,
+                    cube_count,
+                    cube_dim,
+                    linear_view(client, input, line_size),
+                    scales_view(client, output, scale, 1, scheme),
+                    scales_view(client, output, zero_point, 1, scheme),
+                    InputScalar::new(range_min, input_elem),
+                    InputScalar::new(range_max, input_elem),
+                    linear_view(client, output, line_size),
+                    scales_view(client, output, out_scale, 1, scheme),
+                    scales_view(client, output, out_zero_point, 1, scheme),
+                    scales_layout(client, output, scale, 1, scheme),
+                    [
+                        input_elem.into(),
+                        param_elem.into(),
+                        ElemType::UInt(UIntKind::U8).into(),
+                    ],
+                )
+                .map_err(QuantError::Launch)
+            }
+        }
+        _ => Err(QuantError::UnsupportedScheme(*scheme)),
+    }
+}
+
+/// Input of the packed kernels with its line size, made contiguous when the packing dimension is
+/// strided and the tensor large enough for a copy to be faster than scalar reads.
+pub(crate) fn packed_input<R: Runtime>(
+    client: &ComputeClient<R>,
+    input: &TensorHandleRef<R>,
+    scheme: &QuantScheme,
+    dtype_input: ElemType,
+) -> Result<(TensorHandle<R>, usize), QuantError> {
+    let num_elems: usize = input.shape.iter().product();
+
+    // Check if packing dim is contiguous
+    let QuantStore::PackedU32(dim) = scheme.store else {
+        return Err(QuantError::UnsupportedScheme(*scheme));
+    };
+    let ndims = input.shape.len();
+    let mut can_vectorize = input.strides[ndims - 1 - dim] == 1;
+
+    // For larger tensors, copying to contiguous memory should be faster than scalar reads.
+    // 2048 is a conservative floor for the threshold, could be tuned.
+    let input = if !can_vectorize && num_elems >= 2048 {
+        can_vectorize = true;
+        into_contiguous_ref(client, input, dtype_input.into()).map_err(QuantError::Launch)?
+    } else {
+        TensorHandle::from_ref(input, dtype_input.into())
+    };
+
+    // Elements to pack are strided, require scalar reads + manual gather
+    let line_size = if can_vectorize {
+        scheme.num_quants()
+    } else {
+        1
+    };
+
+    Ok((input, line_size))
+}
+
+/// Maximum number of units reducing a single block in [quantize_dynamic].
+const MAX_UNITS_PER_BLOCK: usize = 256;
+
+/// Quantize the input tensor with scales computed on the device from the maximum absolute value of
+/// each tensor or block, `scale = absmax / ((q_max - q_min) / 2)`.
+///
+/// The scales are written to `out_scale` with the same shape as the scales of [launch_ref], which
+/// is then used to quantize the values. Only the symmetric mode is supported.
+#[allow(clippy::result_large_err)]
+pub fn quantize_dynamic<R: Runtime>(
+    client: &ComputeClient<R>,
+    input: &TensorHandleRef<R>,
+    output: &TensorHandleRef<R>,
+    out_scale: &TensorHandleRef<'_, R>,
+    scheme: &QuantScheme,
+    input_elem: ElemType,
+) -> Result<(), QuantError> {
+    if scheme.mode != QuantMode::Symmetric {
+        return Err(QuantError::UnsupportedScheme(*scheme));
+    }
+
+    let (q_min, q_max) = scheme.value.range();
+    let half_range = (q_max - q_min) / 2.0;
+
+    // Scales are first computed in the input precision, then converted to the scale parameter type
+    // when written by the quantize kernel.
+    let scale = TensorHandle::<R>::empty(client, out_scale.shape.to_vec(), input_elem.into());
+
+    launch_absmax_scale(
+        client,
+        input,
+        output,
+        &scale.as_ref(),
+        scheme,
+        half_range,
+        input_elem,
+        input_elem,
+    )?;
+
+    launch_ref(
+        client,
+        input,
+        output,
+        &scale.as_ref(),
+        out_scale,
+        scheme,
+        input_elem,
+    )
+}
+
+/// Write `absmax / half_range` for each tensor or block of the scheme to `out_scale`, in
+/// `scale_elem` precision. `output` is the quantized tensor the scales are laid out for.
+#[allow(clippy::result_large_err, clippy::too_many_arguments)]
+pub(crate) fn launch_absmax_scale<R: Runtime>(
+    client: &ComputeClient<R>,
+    input: &TensorHandleRef<R>,
+    output: &TensorHandleRef<R>,
+    out_scale: &TensorHandleRef<'_, R>,
+    scheme: &QuantScheme,
+    half_range: f32,
+    input_elem: ElemType,
+    scale_elem: ElemType,
+) -> Result<(), QuantError> {
+    let blocks = BlockReduction::new(client, input.shape, scheme)?;
+
+    unsafe {
+        absmax_scale_kernel::launch_unchecked(
+            client,
+            blocks.cube_count(client),
+            blocks.cube_dim(),
+            linear_view(client, input, 1),
+            scales_view(client, output, out_scale, 1, scheme),
+            blocks.blocks_shape,
+            blocks.blocks_strides,
+            blocks.block_shape,
+            blocks.strides,
+            ScalarArg::new(blocks.num_blocks),
+            ScalarArg::new(blocks.block_len),
+            InputScalar::new(half_range, scale_elem),
+            blocks.cube_size,
+            [input_elem.into(), scale_elem.into()],
+        )
+        .map_err(QuantError::Launch)
+    }
+}
+
+/// Launch arguments of the kernels reducing each tensor or block of a scheme with a single cube,
+/// over a contiguous tensor.
+pub(crate) struct BlockReduction<'a, R: Runtime> {
+    pub blocks_shape: SequenceArg<'a, R, FastDivmod<usize>>,
+    pub blocks_strides: SequenceArg<'a, R, usize>,
+    pub block_shape: SequenceArg<'a, R, FastDivmod<usize>>,
+    pub strides: SequenceArg<'a, R, usize>,
+    pub num_blocks: usize,
+    pub block_len: usize,
+    pub cube_size: usize,
+}
+
+impl<'a, R: Runtime> BlockReduction<'a, R> {
+    #[allow(clippy::result_large_err)]
+    pub fn new(
+        client: &ComputeClient<R>,
+        shape: &[usize],
+        scheme: &QuantScheme,
+    ) -> Result<Self, QuantError> {
+        check_rank(shape)?;
+
+        let rank = shape.len();
+        let block_shape: Vec<usize> = match &scheme.level {
+            QuantLevel::Tensor => shape.to_vec(),
+            QuantLevel::Block(block_size) => block_size
+                .to_dim_vec(rank)
+                .into_iter()
+                .map(|size| size as usize)
+                .collect(),
+        };
+        for (dim, (size, block)) in shape.iter().zip(&block_shape).enumerate() {
+            if !size.is_multiple_of(*block) {
+                return Err(QuantError::ShapeMismatch {
+                    details: format!(
+                        "Block size must divide the shape, got block_size={block} for dim {dim} of size {size}"
+                    ),
+                });
+            }
+        }
+
+        let mut strides = vec![1; rank];
+        for dim in (0..rank - 1).rev() {
+            strides[dim] = strides[dim + 1] * shape[dim + 1];
+        }
+
+        let mut blocks_shape = SequenceArg::new();
+        let mut blocks_strides = SequenceArg::new();
+        let mut block_shape_arg = SequenceArg::new();
+        let mut strides_arg = SequenceArg::new();
+        for dim in 0..rank {
+            let block = block_shape[dim];
+            blocks_shape.push(FastDivmodArgs::<usize>::new(client, shape[dim] / block));
+            blocks_strides.push(ScalarArg::new(block * strides[dim]));
+            block_shape_arg.push(FastDivmodArgs::<usize>::new(client, block));
+            strides_arg.push(ScalarArg::new(strides[dim]));
+        }
+
+        let num_elems: usize = shape.iter().product();
+        let block_len: usize = block_shape.iter().product();
+        let num_blocks = num_elems / block_len;
+
+        let cube_size = block_len.next_power_of_two().min(MAX_UNITS_PER_BLOCK);
+
+        Ok(Self {
+            blocks_shape,
+            blocks_strides,
+            block_shape: block_shape_arg,
+            strides: strides_arg,
+            num_blocks,
+            block_len,
+            cube_size,
+        })
+    }
+
+    pub fn cube_dim(&self) -> CubeDim {
+        CubeDim::new_1d(self.cube_size as u32)
+    }
+
+    pub fn cube_count(&self, client: &ComputeClient<R>) -> CubeCount {
+        calculate_cube_count_elemwise(client, self.num_blocks * self.cube_size, self.cube_dim())
     }
 }
@@ -0,0 +1,284 @@
+//! Conversion of quantized tensors between schemes, such as other block sizes, values or stores.
+//! Values are dequantized in registers only, so the full-precision tensor never exists in global
+//! memory.
+
+use cubecl::ir::ElemType;
+use cubecl::prelude::*;
+use cubecl::std::FastDivmod;
+use cubecl::std::tensor::layout::linear::{LinearView, linear_view};
+
+use crate::{
+    QuantError, check_native_support,
+    dequantize::unpack_q,
+    layout::{ScalesView, scales_view},
+    mx::element_storage,
+    quantize::{BlockReduction, block_element, block_start, pack_q},
+    scheme::{QuantMode, QuantScheme, QuantStore, QuantValue},
+    utils::{check_block_size_compat, check_rank},
+};
+
+/// Dequantized value of the element at `pos`, unpacked from its stored value.
+#[cube]
+fn load_value<Q: Numeric, S: Numeric>(
+    pos: usize,
+    values: &LinearView<Line<Q>>,
+    scales: &ScalesView<S>,
+    #[comptime] scheme: QuantScheme,
+) -> f32 {
+    let num_quants = scheme.num_quants();
+    let stored = values[pos / num_quants];
+
+    let unpacked = if comptime!(matches!(scheme.store, QuantStore::PackedU32(_))) {
+        unpack_q::<f32, u32>(u32::cast_from(stored[0]), scheme.value, scheme.store, true)
+    } else {
+        Line::<f32>::cast_from(stored)
+    };
+
+    unpacked[pos % num_quants] * f32::cast_from(scales[pos])
+}
+
+/// Write the stored value of the quantized `values`, the elements from `pos`.
+#[cube]
+fn store_values<Q: Numeric>(
+    pos: usize,
+    values: Line<f32>,
+    output: &mut LinearView<Line<Q>, ReadWrite>,
+    #[comptime] scheme: QuantScheme,
+) {
+    let num_quants = scheme.num_quants();
+
+    if comptime!(matches!(scheme.store, QuantStore::PackedU32(_))) {
+        output[pos / num_quants] =
+            Line::new(Q::cast_from(pack_q::<f32, u32>(values, scheme.value)));
+    } else {
+        output[pos / num_quants] = Line::cast_from(values);
+    }
+}
+
+/// Each cube converts a single block of the output scheme like
+/// [quantize_dynamic](crate::quantize::quantize_dynamic). The input values are read twice, once to
+/// reduce the maximum absolute value of the block and once to quantize them with its scale.
+#[cube(launch_unchecked)]
+#[allow(clippy::too_many_arguments)]
+fn requantize_kernel<QI: Numeric, SI: Numeric, QO: Numeric, SO: Numeric>(
+    input: &LinearView<Line<QI>>,
+    scale: &ScalesView<SI>,
+    output: &mut LinearView<Line<QO>, ReadWrite>,
+    out_scale: &mut ScalesView<SO, ReadWrite>,
+    range_min: f32,
+    range_max: f32,
+    half_range: f32,
+    blocks_shape: Sequence<FastDivmod<usize>>,
+    blocks_strides: Sequence<usize>,
+    block_shape: Sequence<FastDivmod<usize>>,
+    strides: Sequence<usize>,
+    num_blocks: usize,
+    block_len: usize,
+    #[comptime] in_scheme: QuantScheme,
+    #[comptime] out_scheme: QuantScheme,
+    #[comptime] cube_size: usize,
+    #[define(QI, SI, QO, SO)] _dtypes: [StorageType; 4],
+) {
+    let block = CUBE_POS;
+    if block >= num_blocks {
+        terminate!();
+    }
+
+    let unit = UNIT_POS as usize;
+    let start = block_start(block, &blocks_shape, &blocks_strides);
+
+    let mut absmax = 0.0f32;
+    let mut index = unit;
+
+    loop {
+        if index >= block_len {
+            break;
+        }
+
+        let pos = block_element(start, index, &block_shape, &strides);
+        let value = load_value::<QI, SI>(pos, input, scale, in_scheme);
+        absmax = f32::max(absmax, f32::abs(value));
+        index += CUBE_DIM as usize;
+    }
+
+    let mut shared = SharedMemory::<f32>::new(cube_size);
+    shared[unit] = absmax;
+    sync_cube();
+
+    let mut stride = (cube_size / 2).runtime();
+    loop {
+        if stride == 0 {
+            break;
+        }
+
+        if unit < stride {
+            shared[unit] = f32::max(shared[unit], shared[unit + stride]);
+        }
+        sync_cube();
+
+        stride /= 2;
+    }
+
+    // A block of zeros keeps a unit scale. Values are quantized with the scale as stored, so they
+    // dequantize with the precision of the scale parameter.
+    let mut block_scale = 1.0f32;
+    if shared[0] > 0.0 {
+        block_scale = shared[0] / half_range;
+    }
+    let stored_scale = SO::cast_from(block_scale);
+
+    if unit == 0 {
+        out_scale[start] = stored_scale;
+    }
+
+    let quant_scale = f32::cast_from(stored_scale);
+    let num_quants = out_scheme.num_quants();
+    let mut index = unit * num_quants;
+
+    loop {
+        if index >= block_len {
+            break;
+        }
+
+        let pos = block_element(start, index, &block_shape, &strides);
+        let mut values = Line::<f32>::empty(num_quants);
+
+        #[unroll]
+        for i in 0..num_quants {
+            values[i] = load_value::<QI, SI>(pos + i, input, scale, in_scheme) / quant_scale;
+        }
+
+        // Float values are rounded to nearest even by their conversion
+        if comptime!(!is_float(out_scheme.value)) {
+            values = Line::round(values);
+        }
+        values = clamp(values, Line::new(range_min), Line::new(range_max));
+
+        store_values::<QO>(pos, values, output, out_scheme);
+        index += CUBE_DIM as usize * num_quants;
+    }
+}
+
+fn is_float(value: QuantValue) -> bool {
+    matches!(
+        value,
+        QuantValue::E4M3 | QuantValue::E5M2 | QuantValue::E2M1
+    )
+}
+
+/// Storage type of the values of a scheme supported by [requantize].
+fn values_storage(scheme: &QuantScheme) -> Result<StorageType, QuantError> {
+    match scheme {
+        QuantScheme {
+            mode: QuantMode::Symmetric,
+            value:
+                QuantValue::Q8F
+                | QuantValue::Q8S
+                | QuantValue::Q4F
+                | QuantValue::Q4S
+                | QuantValue::Q2F
+                | QuantValue::Q2S,
+            store: QuantStore::PackedU32(_),
+            ..
+        } => Ok(u32::as_type_native_unchecked()),
+        QuantScheme {
+            mode: QuantMode::Symmetric,
+            value: QuantValue::Q8F | QuantValue::Q8S | QuantValue::E4M3 | QuantValue::E5M2,
+            store: QuantStore::Native,
+            ..
+        } => Ok(ElemType::from_quant_value(scheme.value).into()),
+        QuantScheme {
+            mode: QuantMode::Symmetric,
+            value: QuantValue::E2M1,
+            store: QuantStore::PackedNative(_),
+            ..
+        } => Ok(element_storage(QuantValue::E2M1).0),
+        _ => Err(QuantError::UnsupportedScheme(*scheme)),
+    }
+}
+
+/// Shape of the values of a quantized tensor, unpacked along the last dimension.
+fn values_shape(shape: &[usize], scheme: &QuantScheme) -> Vec<usize> {
+    let mut shape = shape.to_vec();
+    let rank = shape.len();
+    shape[rank - 1] *= scheme.num_quants();
+    shape
+}
+
+#[allow(clippy::result_large_err, clippy::too_many_arguments)]
+/// Convert a contiguous tensor quantized with `in_scheme` to `out_scheme`, without writing its
+/// values in full precision to global memory.
+///
+/// `values` and `scale` are laid out for `in_scheme` like the inputs of
+/// [dequantize::launch_ref](crate::dequantize::launch_ref), and `output` and `out_scale` for
+/// `out_scheme`. The scales of `out_scheme` are computed from the maximum absolute value of the
+/// dequantized values, like [quantize_dynamic](crate::quantize::quantize_dynamic).
+///
+/// Both schemes must be symmetric, with integer values packed in `u32`, 8-bit values stored
+/// natively or `E2M1` values stored natively in pairs.
+pub fn requantize<R: Runtime>(
+    client: &ComputeClient<R>,
+    values: &TensorHandleRef<R>,
+    scale: &TensorHandleRef<'_, R>,
+    in_scheme: &QuantScheme,
+    output: &TensorHandleRef<R>,
+    out_scale: &TensorHandleRef<'_, R>,
+    out_scheme: &QuantScheme,
+) -> Result<(), QuantError> {
+    let in_dtype = values_storage(in_scheme)?;
+    let out_dtype = values_storage(out_scheme)?;
+    if !matches!(in_scheme.store, QuantStore::PackedU32(_))
+        || !matches!(out_scheme.store, QuantStore::PackedU32(_))
+    {
+        check_native_support(client)?;
+    }
+
+    check_rank(values.shape)?;
+    check_rank(output.shape)?;
+
+    let shape = values_shape(values.shape, in_scheme);
+    let out_shape = values_shape(output.shape, out_scheme);
+    if shape != out_shape {
+        return Err(QuantError::ShapeMismatch {
+            details: format!(
+                "The output holds values of shape {out_shape:?}, but the input has values of shape {shape:?}"
+            ),
+        });
+    }
+    check_block_size_compat(out_scheme, out_scheme.num_quants())?;
+
+    let blocks = BlockReduction::new(client, &shape, out_scheme)?;
+    let (range_min, range_max) = out_scheme.value.range();
+    let half_range = (range_max - range_min) / 2.0;
+
+    unsafe {
+        requantize_kernel::launch_unchecked(
+            client,
+            blocks.cube_count(client),
+            blocks.cube_dim(),
+            linear_view(client, values, 1),
+            scales_view(client, values, scale, 1, in_scheme),
+            linear_view(client, output, 1),
+            scales_view(client, output, out_scale, 1, out_scheme),
+            ScalarArg::new(range_min),
+            ScalarArg::new(range_max),
+            ScalarArg::new(half_range),
+            blocks.blocks_shape,
+            blocks.blocks_strides,
+            blocks.block_shape,
+            blocks.strides,
+            ScalarArg::new(blocks.num_blocks),
+            ScalarArg::new(blocks.block_len),
+            *in_scheme,
+            *out_scheme,
+            blocks.cube_size,
+            [
+                in_dtype,
+                ElemType::from_quant_param(in_scheme.param).into(),
+                out_dtype,
+                ElemType::from_quant_param(out_scheme.param).into(),
+            ],
+        )
+        .map_err(QuantError::Launch)
+    }
+}
@@ -0,0 +1,76 @@
+use alloc::vec::Vec;
+use cubecl::features::TypeUsage;
+use cubecl::ir::{ElemType, StorageType};
+use cubecl::prelude::*;
+
+use crate::{
+    QuantError,
+    scheme::{QuantLevel, QuantMode, QuantParam, QuantScheme, QuantStore, QuantValue},
+};
+
+/// Schemes that [quantize::launch_ref](crate::quantize::launch_ref) and
+/// [dequantize::launch_ref](crate::dequantize::launch_ref) support on this client, at the tensor
+/// level.
+///
+/// The block levels of the same schemes are supported as long as the last dimension of the block
+/// is a multiple of the number of values read together, the number of quants for packed stores.
+pub fn supported_schemes<R: Runtime>(client: &ComputeClient<R>) -> Vec<QuantScheme> {
+    let params = [QuantParam::F32, QuantParam::F16, QuantParam::BF16]
+        .into_iter()
+        .filter(|param| {
+            let dtype = StorageType::from(ElemType::from_quant_param(*param));
+            client
+                .properties()
+                .features
+                .type_usage(dtype)
+                .contains(TypeUsage::Conversion)
+        });
+
+    let packed = [
+        QuantValue::Q8F,
+        QuantValue::Q8S,
+        QuantValue::Q4F,
+        QuantValue::Q4S,
+        QuantValue::Q2F,
+        QuantValue::Q2S,
+    ]
+    .map(|value| (value, QuantStore::PackedU32(0)));
+    let native = [
+        QuantValue::Q8F,
+        QuantValue::Q8S,
+        QuantValue::E4M3,
+        QuantValue::E5M2,
+    ]
+    .map(|value| (value, QuantStore::Native));
+
+    let mut stores = packed.to_vec();
+    if check_native_support(client).is_ok() {
+        stores.extend(native);
+        stores.push((QuantValue::E2M1, QuantStore::PackedNative(0)));
+    }
+
+    params
+        .flat_map(|param| {
+            stores.iter().map(move |(value, store)| {
+                QuantScheme::default()
+                    .with_level(QuantLevel::Tensor)
+                    .with_mode(QuantMode::Symmetric)
+                    .with_value(*value)
+                    .with_store(*store)
+                    .with_param(param)
+            })
+        })
+        .collect()
+}
+
+/// Native values are converted through `i8`, which the client must support.
+pub(crate) fn check_native_support<R: Runtime>(
+    client: &ComputeClient<R>,
+) -> Result<(), QuantError> {
+    match i8::supported_uses(client).contains(TypeUsage::Conversion) {
+        true => Ok(()),
+        false => Err(QuantError::MissingTypeSupport(
+            i8::as_type_native_unchecked(),
+        )),
+    }
+}
@@ -0,0 +1,218 @@
+use cubecl::TestRuntime;
+use cubecl::ir::ElemType;
+use cubecl::ir::FloatKind;
+use cubecl::server::AllocationDescriptor;
+use cubecl::server::CopyDescriptor;
+use cubecl::std::tensor::TensorHandle;
+use cubek_quant::scheme::QuantScheme;
+use cubek_quant::scheme::QuantStore;
+use cubek_quant::scheme::QuantValue;
+
+#[test]
+fn test_quantization_affine_tensor() {
+    test_quantization_affine(SHAPE_X, SHAPE_Y, VALUE, None, QuantStore::PackedU32(0));
+}
+
+#[test]
+fn test_quantization_affine_block() {
+    test_quantization_affine(
+        SHAPE_X,
+        SHAPE_Y,
+        VALUE,
+        Some(SHAPE_X), // Shape x as block_size
+        QuantStore::PackedU32(0),
+    );
+}
+
+#[test]
+fn test_quantization_affine_native_tensor() {
+    if VALUE.size_bits() != 8 {
+        return;
+    }
+
+    test_quantization_affine(SHAPE_X, SHAPE_Y, VALUE, None, QuantStore::Native);
+}
+
+#[test]
+fn test_quantization_affine_native_block() {
+    if VALUE.size_bits() != 8 {
+        return;
+    }
+
+    test_quantization_affine(SHAPE_X, SHAPE_Y, VALUE, Some(SHAPE_X), QuantStore::Native);
+}
+
+fn test_quantization_affine(
+    m: usize,
+    n: usize,
+    value: QuantValue,
+    block_size: Option<usize>,
+    store: QuantStore,
+) {
+    let client = TestRuntime::client(&Default::default());
+    let shape = vec![m, n];
+
+    // Skewed data, where a symmetric scheme would waste half of its range.
+    let num_elems: usize = m * n;
+    let data: Vec<_> = (0..num_elems)
+        .map(|v| v as f32 / num_elems as f32 - 0.25)
+        .collect();
+    let input_alloc =
+        client.create_tensor_from_slice(f32::as_bytes(&data), &shape, f32::type_size());
+
+    // Values are stored unsigned, shifted by the zero-point
+    let (q_min, q_max) = (0.0, ((1u32 << value.size_bits()) - 1) as f32);
+
+    let block_size = block_size.unwrap_or(num_elems);
+    let scale_count = num_elems / block_size;
+    let shape_scale = match scale_count {
+        1 => vec![1],
+        _ => vec![m, n / block_size],
+    };
+
+    let mut scales = Vec::with_capacity(scale_count);
+    let mut zero_points = Vec::with_capacity(scale_count);
+
+    for block in data.chunks(block_size) {
+        // The range includes zero so it stays exactly representable.
+        let c_min = block.iter().copied().fold(0.0, f32::min);
+        let c_max = block.iter().copied().fold(0.0, f32::max);
+
+        let scale = (c_max - c_min) / (q_max - q_min);
+        let zero_point = (q_min - c_min / scale).round().clamp(q_min, q_max);
+        scales.push(scale);
+        zero_points.push(zero_point);
+    }
+
+    let scale_alloc =
+        client.create_tensor_from_slice(f32::as_bytes(&scales), &shape_scale, f32::type_size());
+    let zero_point_alloc = client.create_tensor_from_slice(
+        f32::as_bytes(&zero_points),
+        &shape_scale,
+        f32::type_size(),
+    );
+
+    let input = TensorHandle::new(
+        input_alloc.handle,
+        shape.clone(),
+        input_alloc.strides,
+        f32::as_type_native_unchecked(),
+    );
+    let scale = TensorHandle::new(
+        scale_alloc.handle,
+        shape_scale.clone(),
+        scale_alloc.strides,
+        f32::as_type_native_unchecked(),
+    );
+    let zero_point = TensorHandle::new(
+        zero_point_alloc.handle,
+        shape_scale.clone(),
+        zero_point_alloc.strides,
+        f32::as_type_native_unchecked(),
+    );
+    let output_f = TensorHandle::zeros(&client, shape, f32::as_type_native_unchecked());
+
+    let level = match scale_count {
+        1 => QuantLevel::Tensor,
+        _ => QuantLevel::block([block_size as u8]),
+    };
+    let scheme = QuantScheme::default()
+        .with_level(level)
+        .with_value(value)
+        .with_store(store)
+        .with_param(QuantParam::F32);
+
+    // The shape is from the POV of the stored values, packed u32s or native u8s.
+    let (shape_out, elem_out) = match store {
+        QuantStore::Native => (vec![m, n], u8::as_type_native_unchecked()),
+        _ => (
+            vec![m, n / scheme.num_quants()],
+            u32::as_type_native_unchecked(),
+        ),
+    };
+
+    let [output_alloc, output_scale_alloc, output_zero_point_alloc] = client
+        .empty_tensors(vec![
+            AllocationDescriptor {
+                kind: cubecl::server::AllocationKind::Contiguous,
+                shape: &shape_out,
+                elem_size: elem_out.size(),
+            },
+            AllocationDescriptor {
+                kind: cubecl::server::AllocationKind::Contiguous,
+                shape: &shape_scale,
+                elem_size: f32::type_size(),
+            },
+            AllocationDescriptor {
+                kind: cubecl::server::AllocationKind::Contiguous,
+                shape: &shape_scale,
+                elem_size: f32::type_size(),
+            },
+        ])
+        .try_into()
+        .unwrap();
+    let output = TensorHandle::new(
+        output_alloc.handle,
+        shape_out,
+        output_alloc.strides,
+        elem_out,
+    );
+    let output_scale = TensorHandle::new(
+        output_scale_alloc.handle,
+        shape_scale.clone(),
+        output_scale_alloc.strides,
+        f32::as_type_native_unchecked(),
+    );
+    let output_zero_point = TensorHandle::new(
+        output_zero_point_alloc.handle,
+        shape_scale,
+        output_zero_point_alloc.strides,
+        f32::as_type_native_unchecked(),
+    );
+
+    cubek_quant::quantize::launch_ref_affine(
+        &client,
+        &input.as_ref(),
+        &output.as_ref(),
+        &scale.as_ref(),
+        &zero_point.as_ref(),
+        &output_scale.as_ref(),
+        &output_zero_point.as_ref(),
+        &scheme,
+        ElemType::Float(FloatKind::Flex32),
+    )
+    .unwrap();
+
+    cubek_quant::dequantize::launch_ref_affine(
+        &client,
+        &output.as_ref(),
+        &output_f.as_ref(),
+        &output_scale.as_ref(),
+        &output_zero_point.as_ref(),
+        &scheme,
+        f32::as_type_native_unchecked(),
+    )
+    .unwrap();
+
+    let computed = client.read_one_tensor(CopyDescriptor::new(
+        output_f.handle.binding(),
+        &output_f.shape,
+        &output_f.strides,
+        core::mem::size_of::<f32>(),
+    ));
+    let data_restored = f32::from_bytes(&computed);
+
+    assert_eq!(data_restored.len(), data.len());
+    let rel_tol = 1e-4;
+    for (i, (actual, expected)) in data_restored.iter().zip(data.into_iter()).enumerate() {
+        let block = i / block_size;
+        let scale = scales[block];
+        // Max quantization error = step size / 2
+        let max_error = (scale / 2.0) * (1f32 + rel_tol);
+        let diff = f32::abs(actual - expected);
+        assert!(
+            diff <= max_error,
+            "Mismatch at {i}, Expected: {expected} | Actual: {actual} (diff {diff} > {max_error})"
+        );
+    }
+}
@@ -0,0 +1,294 @@
+use cubecl::TestRuntime;
+use cubecl::ir::ElemType;
+use cubecl::ir::FloatKind;
+use cubecl::prelude::*;
+use cubecl::std::tensor::TensorHandle;
+use cubek_quant::QuantError;
+use cubek_quant::calibration::{CalibrationMethod, Calibrator};
+use cubek_quant::scheme::{QuantLevel, QuantMode, QuantScheme, QuantStore, QuantValue};
+
+const NUM_BINS: usize = 512;
+const NUM_BATCHES: usize = 4;
+const BATCH_SHAPE: [usize; 2] = [16, 256];
+
+#[test]
+fn test_calibration_absmax() {
+    let batches = batches();
+    let absmax = batches
+        .iter()
+        .flatten()
+        .fold(0.0f32, |max, v| max.max(v.abs()));
+
+    let scale = calibrate(&batches, CalibrationMethod::Absmax);
+    assert_scale(scale, absmax / 127.0);
+}
+
+#[test]
+fn test_calibration_percentile() {
+    let batches = batches();
+    let (histogram, bin_width) = histogram(&batches);
+
+    let total: u32 = histogram.iter().sum();
+    let target = total as f32 * 99.9 / 100.0;
+    let mut cumulative = 0.0;
+    let num_kept = histogram
+        .iter()
+        .position(|count| {
+            cumulative += *count as f32;
+            cumulative >= target
+        })
+        .unwrap()
+        + 1;
+
+    let scale = calibrate(&batches, CalibrationMethod::Percentile(99.9));
+    assert_scale(scale, num_kept as f32 * bin_width / 127.0);
+}
+
+#[test]
+fn test_calibration_mse() {
+    let batches = batches();
+    let (histogram, bin_width) = histogram(&batches);
+
+    let error = |num_kept: usize| {
+        let scale = num_kept as f32 * bin_width / 127.0;
+        let max_value = 127.0 * scale;
+        histogram
+            .iter()
+            .enumerate()
+            .map(|(bin, count)| {
+                let clipped = (bin as f32 + 0.5) * bin_width - max_value;
+                let error = match clipped > 0.0 {
+                    true => clipped * clipped,
+                    false => scale * scale / 12.0,
+                };
+                *count as f32 * error
+            })
+            .sum::<f32>()
+    };
+    let best = (1..=NUM_BINS)
+        .min_by(|a, b| error(*a).total_cmp(&error(*b)))
+        .unwrap();
+
+    let scale = calibrate(&batches, CalibrationMethod::Mse);
+    let actual_error = error((scale * 127.0 / bin_width).round() as usize);
+    assert!(
+        actual_error <= error(best) * (1.0 + 1e-4),
+        "Scale {scale} isn't optimal, Error: {actual_error} | Best: {}",
+        error(best)
+    );
+}
+
+#[test]
+fn test_calibration_kl_divergence() {
+    let batches = batches();
+    let absmax = batches
+        .iter()
+        .flatten()
+        .fold(0.0f32, |max, v| max.max(v.abs()));
+
+    // The outliers are clipped
+    let scale = calibrate(&batches, CalibrationMethod::KlDivergence);
+    assert!(
+        scale > 0.0 && scale < absmax / 127.0 / 2.0,
+        "Outliers aren't clipped, Scale: {scale} | Absmax scale: {}",
+        absmax / 127.0
+    );
+}
+
+#[test]
+fn test_calibration_moments() {
+    let batches = batches();
+    let client = TestRuntime::client(&Default::default());
+    let mut calibrator = Calibrator::new(&client, NUM_BINS);
+    for batch in &batches {
+        let input = upload(&client, batch);
+        calibrator
+            .observe(&client, &input.as_ref(), ElemType::Float(FloatKind::F32))
+            .unwrap();
+    }
+
+    let values: Vec<f32> = batches.into_iter().flatten().collect();
+    let count = values.len() as f32;
+    let mean = values.iter().sum::<f32>() / count;
+    let variance = values.iter().map(|v| (v - mean) * (v - mean)).sum::<f32>() / count;
+
+    let moments = calibrator.moments(&client);
+    assert_eq!(calibrator.num_values(), values.len() as u64);
+    assert!(
+        (moments.mean - mean).abs() <= 1e-4,
+        "Mean mismatch, Actual: {} | Expected: {mean}",
+        moments.mean
+    );
+    assert!(
+        (moments.variance - variance).abs() <= variance * 1e-3,
+        "Variance mismatch, Actual: {} | Expected: {variance}",
+        moments.variance
+    );
+}
+
+#[test]
+fn test_calibration_moments_large_offset() {
+    // A mean far from zero, where the variance from the sum of squares cancels in f32
+    let batches: Vec<Vec<f32>> = batches()
+        .into_iter()
+        .map(|batch| batch.into_iter().map(|v| 10_000.0 + v).collect())
+        .collect();
+    let client = TestRuntime::client(&Default::default());
+    let mut calibrator = Calibrator::new(&client, NUM_BINS);
+    for batch in &batches {
+        let input = upload(&client, batch);
+        calibrator
+            .observe(&client, &input.as_ref(), ElemType::Float(FloatKind::F32))
+            .unwrap();
+    }
+
+    let values: Vec<f64> = batches.into_iter().flatten().map(f64::from).collect();
+    let count = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / count;
+    let variance = values.iter().map(|v| (v - mean) * (v - mean)).sum::<f64>() / count;
+
+    let moments = calibrator.moments(&client);
+    assert!(
+        (moments.mean as f64 - mean).abs() <= mean * 1e-6,
+        "Mean mismatch, Actual: {} | Expected: {mean}",
+        moments.mean
+    );
+    assert!(
+        (moments.variance as f64 - variance).abs() <= variance * 1e-2,
+        "Variance mismatch, Actual: {} | Expected: {variance}",
+        moments.variance
+    );
+}
+
+#[test]
+fn test_calibration_scalar() {
+    let client = TestRuntime::client(&Default::default());
+    let input = TensorHandle::new_contiguous(
+        vec![],
+        client.create_from_slice(f32::as_bytes(&[-3.0])),
+        f32::as_type_native_unchecked(),
+    );
+
+    let mut calibrator = Calibrator::new(&client, NUM_BINS);
+    calibrator
+        .observe(&client, &input.as_ref(), ElemType::Float(FloatKind::F32))
+        .unwrap();
+
+    let moments = calibrator.moments(&client);
+    assert_eq!(calibrator.num_values(), 1);
+    assert_eq!(moments.mean, -3.0);
+    assert_eq!(moments.variance, 0.0);
+}
+
+#[test]
+fn test_calibration_missing_histogram() {
+    let client = TestRuntime::client(&Default::default());
+    let calibrator = Calibrator::new(&client, NUM_BINS);
+    let out_scale = TensorHandle::empty(&client, vec![1], f32::as_type_native_unchecked());
+
+    let result = calibrator.compute_scale(
+        &client,
+        &out_scale.as_ref(),
+        CalibrationMethod::Mse,
+        &scheme(),
+    );
+    assert!(
+        matches!(result, Err(QuantError::MissingStatistics { .. })),
+        "Unexpected result {result:?}"
+    );
+}
+
+fn scheme() -> QuantScheme {
+    QuantScheme::default()
+        .with_level(QuantLevel::Tensor)
+        .with_mode(QuantMode::Symmetric)
+        .with_value(QuantValue::Q8S)
+        .with_store(QuantStore::PackedU32(0))
+}
+
+/// Roughly normal values, with a few large outliers.
+fn batches() -> Vec<Vec<f32>> {
+    let mut state = 0x9E37_79B9u32;
+    let mut uniform = move || {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        state as f32 / u32::MAX as f32
+    };
+
+    let num_elems = BATCH_SHAPE.iter().product::<usize>();
+    (0..NUM_BATCHES)
+        .map(|_| {
+            (0..num_elems)
+                .map(|i| {
+                    let normal = (0..4).map(|_| uniform()).sum::<f32>() - 2.0;
+                    match i % 1000 {
+                        0 => normal * 50.0,
+                        _ => normal + 0.1,
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Histogram of the absolute values over the absolute maximum, as counted on the device.
+fn histogram(batches: &[Vec<f32>]) -> (Vec<u32>, f32) {
+    let range = batches
+        .iter()
+        .flatten()
+        .fold(0.0f32, |max, v| max.max(v.abs()));
+    let bins_per_unit = NUM_BINS as f32 / range;
+
+    let mut histogram = vec![0; NUM_BINS];
+    for value in batches.iter().flatten() {
+        let bin = (value.abs() * bins_per_unit).min((NUM_BINS - 1) as f32);
+        histogram[bin as usize] += 1;
+    }
+
+    (histogram, range / NUM_BINS as f32)
+}
+
+fn calibrate(batches: &[Vec<f32>], method: CalibrationMethod) -> f32 {
+    let client = TestRuntime::client(&Default::default());
+    let input_elem = ElemType::Float(FloatKind::F32);
+    let mut calibrator = Calibrator::new(&client, NUM_BINS);
+
+    let inputs: Vec<_> = batches.iter().map(|batch| upload(&client, batch)).collect();
+    for input in &inputs {
+        calibrator
+            .observe(&client, &input.as_ref(), input_elem)
+            .unwrap();
+    }
+    for input in &inputs {
+        calibrator
+            .observe_histogram(&client, &input.as_ref(), input_elem)
+            .unwrap();
+    }
+
+    let out_scale = TensorHandle::empty(&client, vec![1], f32::as_type_native_unchecked());
+    calibrator
+        .compute_scale(&client, &out_scale.as_ref(), method, &scheme())
+        .unwrap();
+
+    let scale = client.read_one_tensor(out_scale.as_copy_descriptor());
+    f32::from_bytes(&scale)[0]
+}
+
+fn upload(client: &ComputeClient<TestRuntime>, data: &[f32]) -> TensorHandle<TestRuntime> {
+    let alloc =
+        client.create_tensor_from_slice(f32::as_bytes(data), &BATCH_SHAPE, f32::type_size());
+    TensorHandle::new(
+        alloc.handle,
+        BATCH_SHAPE.to_vec(),
+        alloc.strides,
+        f32::as_type_native_unchecked(),
+    )
+}
+
+fn assert_scale(actual: f32, expected: f32) {
+    assert!(
+        (actual - expected).abs() <= expected * 1e-5,
+        "Scale mismatch, Actual: {actual} | Expected: {expected}"
+    );
+}
@@ -0,0 +1,132 @@
+use cubecl::TestRuntime;
+use cubecl::ir::ElemType;
+use cubecl::ir::FloatKind;
+use cubecl::prelude::*;
+use cubecl::std::tensor::TensorHandle;
+use cubek_quant::codebook::{Codebook, codebook_scheme, dequantize_codebook, quantize_codebook};
+use cubek_quant::scheme::QuantLevel;
+
+#[test]
+fn test_codebook_nf4() {
+    test_codebook(Codebook::NF4);
+}
+
+#[test]
+fn test_codebook_e2m1() {
+    test_codebook(Codebook::E2M1);
+}
+
+#[test]
+fn test_codebook_custom() {
+    // Unsorted and asymmetric, with duplicated values
+    test_codebook(Codebook::new([
+        0.5, -0.25, 0.0, 1.0, -0.75, 0.125, 0.25, -0.5, 0.75, -1.5, 0.375, -0.125, 0.5, 2.0, -2.5,
+        0.0625,
+    ]));
+}
+
+fn test_codebook(codebook: Codebook) {
+    let (m, n) = (16, 128);
+    let block_size = 64;
+    let num_quants = 8;
+
+    // Different magnitudes per block, with a zero block
+    let data: Vec<f32> = (0..m * n)
+        .map(|i| {
+            let magnitude = match (i / block_size) % 9 {
+                8 => 0.0,
+                block => (block + 1) as f32 * 0.3,
+            };
+            f32::sin(i as f32 * 1.3) * magnitude
+        })
+        .collect();
+
+    let client = TestRuntime::client(&Default::default());
+    let shape = vec![m, n];
+    let scheme = codebook_scheme().with_level(QuantLevel::block([block_size as u8]));
+
+    let input_alloc =
+        client.create_tensor_from_slice(f32::as_bytes(&data), &shape, f32::type_size());
+    let input = TensorHandle::new(
+        input_alloc.handle,
+        shape.clone(),
+        input_alloc.strides,
+        f32::as_type_native_unchecked(),
+    );
+    let output = TensorHandle::empty(
+        &client,
+        vec![m, n / num_quants],
+        u32::as_type_native_unchecked(),
+    );
+    let output_scale = TensorHandle::empty(
+        &client,
+        vec![m, n / block_size],
+        f32::as_type_native_unchecked(),
+    );
+    let output_f = TensorHandle::zeros(&client, shape, f32::as_type_native_unchecked());
+
+    quantize_codebook(
+        &client,
+        &input.as_ref(),
+        &output.as_ref(),
+        &output_scale.as_ref(),
+        &codebook,
+        &scheme,
+        ElemType::Float(FloatKind::F32),
+    )
+    .unwrap();
+
+    dequantize_codebook(
+        &client,
+        &output.as_ref(),
+        &output_f.as_ref(),
+        &output_scale.as_ref(),
+        &codebook,
+        &scheme,
+        f32::as_type_native_unchecked(),
+    )
+    .unwrap();
+
+    let packed = client.read_one_tensor(output.as_copy_descriptor());
+    let packed = u32::from_bytes(&packed);
+    let scales = client.read_one_tensor(output_scale.as_copy_descriptor());
+    let scales = f32::from_bytes(&scales);
+    let restored = client.read_one_tensor(output_f.as_copy_descriptor());
+    let restored = f32::from_bytes(&restored);
+
+    let values = codebook.values();
+    for (block, chunk) in data.chunks(block_size).enumerate() {
+        let absmax = chunk.iter().fold(0.0f32, |max, v| max.max(v.abs()));
+        let expected = match absmax > 0.0 {
+            true => absmax / codebook.max_abs(),
+            false => 1.0,
+        };
+        let scale = scales[block];
+        assert!(
+            (scale - expected).abs() <= expected * 1e-4,
+            "Scale mismatch at block {block}, Actual: {scale} | Expected: {expected}"
+        );
+
+        for (i, input) in chunk.iter().enumerate() {
+            let index = block * block_size + i;
+            let code = (packed[index / num_quants] >> ((index % num_quants) * 4)) & 0xF;
+            let code_value = values[code as usize];
+
+            // The index is the nearest codebook value, up to rounding of the scaled value
+            let scaled = input / scale;
+            let nearest = values
+                .iter()
+                .fold(f32::MAX, |min, v| min.min((scaled - v).abs()));
+            assert!(
+                (scaled - code_value).abs() <= nearest + 1e-5,
+                "Index {code} isn't the nearest at {index}, Input: {input} | Scale: {scale}"
+            );
+
+            assert_eq!(
+                restored[index],
+                code_value * scale,
+                "Mismatch at {index}, Input: {input} | Scale: {scale}"
+            );
+        }
+    }
+}
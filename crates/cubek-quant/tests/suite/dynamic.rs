@@ -0,0 +1,161 @@
+use cubecl::TestRuntime;
+use cubecl::ir::ElemType;
+use cubecl::ir::FloatKind;
+use cubecl::server::AllocationDescriptor;
+use cubecl::server::CopyDescriptor;
+use cubecl::std::tensor::TensorHandle;
+use cubek_quant::scheme::QuantMode;
+use cubek_quant::scheme::QuantScheme;
+use cubek_quant::scheme::QuantStore;
+use cubek_quant::scheme::QuantValue;
+
+#[test]
+fn test_quantization_dynamic_tensor() {
+    test_quantization_dynamic(SHAPE_X, SHAPE_Y, VALUE, None);
+}
+
+#[test]
+fn test_quantization_dynamic_block() {
+    test_quantization_dynamic(SHAPE_X, SHAPE_Y, VALUE, Some(SHAPE_X));
+}
+
+fn test_quantization_dynamic(m: usize, n: usize, value: QuantValue, block_size: Option<usize>) {
+    let client = TestRuntime::client(&Default::default());
+    let shape = vec![m, n];
+
+    // Different magnitudes per row, so every block has its own scale.
+    let num_elems: usize = m * n;
+    let data: Vec<_> = (0..num_elems)
+        .map(|v| {
+            let row = (v / n) as f32 + 1.0;
+            let col = (v % n) as f32 - n as f32 / 3.0;
+            col * row / num_elems as f32
+        })
+        .collect();
+    let input_alloc =
+        client.create_tensor_from_slice(f32::as_bytes(&data), &shape, f32::type_size());
+
+    let (q_min, q_max) = value.range();
+
+    let block_size = block_size.unwrap_or(num_elems);
+    let scale_count = num_elems / block_size;
+    let shape_scale = match scale_count {
+        1 => vec![1],
+        _ => vec![m, n / block_size],
+    };
+
+    let scales: Vec<_> = data
+        .chunks(block_size)
+        .map(|block| {
+            let absmax = block.iter().fold(0.0, |max: f32, v| max.max(v.abs()));
+            absmax / ((q_max - q_min) / 2.0)
+        })
+        .collect();
+
+    let input = TensorHandle::new(
+        input_alloc.handle,
+        shape.clone(),
+        input_alloc.strides,
+        f32::as_type_native_unchecked(),
+    );
+    let output_f = TensorHandle::zeros(&client, shape, f32::as_type_native_unchecked());
+
+    let level = match scale_count {
+        1 => QuantLevel::Tensor,
+        _ => QuantLevel::block([block_size as u8]),
+    };
+    let scheme = QuantScheme::default()
+        .with_level(level)
+        .with_mode(QuantMode::Symmetric)
+        .with_value(value)
+        .with_store(QuantStore::PackedU32(0))
+        .with_param(QuantParam::F32);
+
+    // The shape is from the POV of packed u32s.
+    let shape_out = vec![m, n / scheme.num_quants()];
+
+    let [output_alloc, output_scale_alloc] = client
+        .empty_tensors(vec![
+            AllocationDescriptor {
+                kind: cubecl::server::AllocationKind::Contiguous,
+                shape: &shape_out,
+                elem_size: u32::type_size(),
+            },
+            AllocationDescriptor {
+                kind: cubecl::server::AllocationKind::Contiguous,
+                shape: &shape_scale,
+                elem_size: f32::type_size(),
+            },
+        ])
+        .try_into()
+        .unwrap();
+    let output = TensorHandle::new(
+        output_alloc.handle,
+        shape_out,
+        output_alloc.strides,
+        u32::as_type_native_unchecked(),
+    );
+    let output_scale = TensorHandle::new(
+        output_scale_alloc.handle,
+        shape_scale,
+        output_scale_alloc.strides,
+        f32::as_type_native_unchecked(),
+    );
+
+    cubek_quant::quantize::quantize_dynamic(
+        &client,
+        &input.as_ref(),
+        &output.as_ref(),
+        &output_scale.as_ref(),
+        &scheme,
+        ElemType::Float(FloatKind::Flex32),
+    )
+    .unwrap();
+
+    cubek_quant::dequantize::launch_ref(
+        &client,
+        &output.as_ref(),
+        &output_f.as_ref(),
+        &output_scale.as_ref(),
+        &scheme,
+        f32::as_type_native_unchecked(),
+    )
+    .unwrap();
+
+    let computed_scales = client.read_one_tensor(CopyDescriptor::new(
+        output_scale.handle.binding(),
+        &output_scale.shape,
+        &output_scale.strides,
+        core::mem::size_of::<f32>(),
+    ));
+    let computed_scales = f32::from_bytes(&computed_scales);
+
+    let rel_tol = 1e-4;
+    assert_eq!(computed_scales.len(), scales.len());
+    for (block, (actual, expected)) in computed_scales.iter().zip(scales.iter()).enumerate() {
+        assert!(
+            f32::abs(actual - expected) <= expected * rel_tol,
+            "Scale mismatch at block {block}, Expected: {expected} | Actual: {actual}"
+        );
+    }
+
+    let computed = client.read_one_tensor(CopyDescriptor::new(
+        output_f.handle.binding(),
+        &output_f.shape,
+        &output_f.strides,
+        core::mem::size_of::<f32>(),
+    ));
+    let data_restored = f32::from_bytes(&computed);
+
+    assert_eq!(data_restored.len(), data.len());
+    for (i, (actual, expected)) in data_restored.iter().zip(data.into_iter()).enumerate() {
+        let scale = scales[i / block_size];
+        // Max quantization error = step size / 2
+        let max_error = (scale / 2.0) * (1f32 + rel_tol);
+        let diff = f32::abs(actual - expected);
+        assert!(
+            diff <= max_error,
+            "Mismatch at {i}, Expected: {expected} | Actual: {actual} (diff {diff} > {max_error})"
+        );
+    }
+}
@@ -0,0 +1,195 @@
+use cubecl::TestRuntime;
+use cubecl::ir::ElemType;
+use cubecl::ir::FloatKind;
+use cubecl::prelude::*;
+use cubecl::std::tensor::TensorHandle;
+use cubek_quant::QuantError;
+use cubek_quant::mx;
+use cubek_quant::scheme::{QuantLevel, QuantMode, QuantParam, QuantScheme, QuantStore, QuantValue};
+use cubek_quant::supported_schemes;
+use cubek_quant::{dequantize, quantize};
+
+#[test]
+fn test_supported_schemes_quantize() {
+    let client = TestRuntime::client(&Default::default());
+    let schemes = supported_schemes(&client);
+    assert!(!schemes.is_empty());
+
+    for scheme in schemes {
+        let result = quantize(&client, &scheme, vec![4, 32]);
+        assert!(result.is_ok(), "{scheme:?} failed: {result:?}");
+    }
+}
+
+#[test]
+fn test_block_size_mismatch() {
+    let client = TestRuntime::client(&Default::default());
+    // Q2 packs 16 values per u32, which a block of 8 values can't hold
+    let scheme = QuantScheme::default()
+        .with_level(QuantLevel::block([8]))
+        .with_mode(QuantMode::Symmetric)
+        .with_value(QuantValue::Q2S)
+        .with_store(QuantStore::PackedU32(0))
+        .with_param(QuantParam::F32);
+
+    let result = quantize(&client, &scheme, vec![4, 32]);
+    assert!(
+        matches!(
+            result,
+            Err(QuantError::BlockSizeMismatch {
+                block_size: 8,
+                div: 16
+            })
+        ),
+        "Unexpected result {result:?}"
+    );
+}
+
+#[test]
+fn test_unsupported_native_value() {
+    let client = TestRuntime::client(&Default::default());
+    let scheme = QuantScheme::default()
+        .with_level(QuantLevel::Tensor)
+        .with_mode(QuantMode::Symmetric)
+        .with_value(QuantValue::Q4S)
+        .with_store(QuantStore::Native)
+        .with_param(QuantParam::F32);
+
+    let result = quantize(&client, &scheme, vec![4, 32]);
+    assert!(
+        matches!(
+            result,
+            Err(QuantError::UnsupportedScheme(_) | QuantError::MissingTypeSupport(_))
+        ),
+        "Unexpected result {result:?}"
+    );
+}
+
+#[test]
+fn test_dynamic_rank_0() {
+    let client = TestRuntime::client(&Default::default());
+    let scheme = QuantScheme::default()
+        .with_level(QuantLevel::Tensor)
+        .with_mode(QuantMode::Symmetric)
+        .with_value(QuantValue::Q8S)
+        .with_store(QuantStore::PackedU32(0))
+        .with_param(QuantParam::F32);
+
+    let input = TensorHandle::zeros(&client, vec![], f32::as_type_native_unchecked());
+    let output = TensorHandle::empty(&client, vec![], u32::as_type_native_unchecked());
+    let out_scale = TensorHandle::empty(&client, vec![1], f32::as_type_native_unchecked());
+
+    let result = quantize::quantize_dynamic(
+        &client,
+        &input.as_ref(),
+        &output.as_ref(),
+        &out_scale.as_ref(),
+        &scheme,
+        ElemType::Float(FloatKind::F32),
+    );
+    assert!(
+        matches!(result, Err(QuantError::ShapeMismatch { .. })),
+        "Unexpected result {result:?}"
+    );
+}
+
+#[test]
+fn test_launch_ref_rank_0() {
+    let client = TestRuntime::client(&Default::default());
+    let scheme = QuantScheme::default()
+        .with_level(QuantLevel::Tensor)
+        .with_mode(QuantMode::Symmetric)
+        .with_value(QuantValue::Q8S)
+        .with_store(QuantStore::PackedU32(0))
+        .with_param(QuantParam::F32);
+
+    let input = TensorHandle::zeros(&client, vec![], f32::as_type_native_unchecked());
+    let output = TensorHandle::empty(&client, vec![], u32::as_type_native_unchecked());
+    let scale = TensorHandle::zeros(&client, vec![1], f32::as_type_native_unchecked());
+    let out_scale = TensorHandle::empty(&client, vec![1], f32::as_type_native_unchecked());
+
+    let result = quantize::launch_ref(
+        &client,
+        &input.as_ref(),
+        &output.as_ref(),
+        &scale.as_ref(),
+        &out_scale.as_ref(),
+        &scheme,
+        ElemType::Float(FloatKind::F32),
+    );
+    assert!(
+        matches!(result, Err(QuantError::ShapeMismatch { .. })),
+        "Unexpected result {result:?}"
+    );
+
+    let result = dequantize::launch_ref(
+        &client,
+        &output.as_ref(),
+        &input.as_ref(),
+        &scale.as_ref(),
+        &scheme,
+        f32::as_type_native_unchecked(),
+    );
+    assert!(
+        matches!(result, Err(QuantError::ShapeMismatch { .. })),
+        "Unexpected result {result:?}"
+    );
+}
+
+#[test]
+fn test_mx_scheme_non_mx_value() {
+    assert!(matches!(
+        mx::mx_scheme(QuantValue::Q8S),
+        Err(QuantError::UnsupportedScheme(_))
+    ));
+    assert!(matches!(
+        mx::mx_emax(QuantValue::Q4F),
+        Err(QuantError::UnsupportedScheme(_))
+    ));
+}
+
+fn quantize(
+    client: &ComputeClient<TestRuntime>,
+    scheme: &QuantScheme,
+    shape: Vec<usize>,
+) -> Result<(), QuantError> {
+    let rank = shape.len();
+    let shape_scale = match scheme.level {
+        QuantLevel::Tensor => vec![1],
+        QuantLevel::Block(block_size) => shape
+            .iter()
+            .zip(block_size.to_dim_vec(rank))
+            .map(|(dim, block)| dim / block as usize)
+            .collect(),
+    };
+    // Packed values hold several quants per u32 or byte, native values one per byte
+    let (shape_out, output_dtype) = match scheme.store {
+        QuantStore::PackedU32(_) => {
+            let mut shape_out = shape.clone();
+            shape_out[rank - 1] /= scheme.num_quants();
+            (shape_out, u32::as_type_native_unchecked())
+        }
+        QuantStore::PackedNative(_) => {
+            let mut shape_out = shape.clone();
+            shape_out[rank - 1] /= scheme.num_quants();
+            (shape_out, u8::as_type_native_unchecked())
+        }
+        _ => (shape.clone(), u8::as_type_native_unchecked()),
+    };
+    let param_dtype = StorageType::from(ElemType::from_quant_param(scheme.param));
+
+    let input = TensorHandle::zeros(client, shape, f32::as_type_native_unchecked());
+    let scale = TensorHandle::zeros(client, shape_scale.clone(), param_dtype);
+    let out_scale = TensorHandle::empty(client, shape_scale, param_dtype);
+    let output = TensorHandle::empty(client, shape_out, output_dtype);
+
+    quantize::launch_ref(
+        client,
+        &input.as_ref(),
+        &output.as_ref(),
+        &scale.as_ref(),
+        &out_scale.as_ref(),
+        scheme,
+        ElemType::Float(FloatKind::F32),
+    )
+}
@@ -0,0 +1,207 @@
+use cubecl::TestRuntime;
+use cubecl::ir::ElemType;
+use cubecl::ir::FloatKind;
+use cubecl::prelude::*;
+use cubecl::std::tensor::TensorHandle;
+use cubek_quant::fake_quantize::{fake_quantize, fake_quantize_backward};
+use cubek_quant::scheme::{QuantLevel, QuantMode, QuantScheme, QuantStore, QuantValue};
+
+const M: usize = 8;
+const N: usize = 64;
+
+#[test]
+fn test_fake_quantize_tensor_q8s() {
+    test_fake_quantize(QuantValue::Q8S, None);
+}
+
+#[test]
+fn test_fake_quantize_block_q4f() {
+    test_fake_quantize(QuantValue::Q4F, Some(32));
+}
+
+#[test]
+fn test_fake_quantize_backward_tensor_q8s() {
+    test_fake_quantize_backward(QuantValue::Q8S, None);
+}
+
+#[test]
+fn test_fake_quantize_backward_block_q2s() {
+    test_fake_quantize_backward(QuantValue::Q2S, Some(16));
+}
+
+#[test]
+fn test_fake_quantize_scalar() {
+    let client = TestRuntime::client(&Default::default());
+    let scheme = scheme(QuantValue::Q8S, None);
+    let input = upload(&client, &[0.7], vec![]);
+    let scale = upload(&client, &[0.25], vec![1]);
+    let output = TensorHandle::zeros(&client, vec![], f32::as_type_native_unchecked());
+
+    fake_quantize(
+        &client,
+        &input.as_ref(),
+        &output.as_ref(),
+        &scale.as_ref(),
+        &scheme,
+        ElemType::Float(FloatKind::F32),
+    )
+    .unwrap();
+
+    let actual = client.read_one_tensor(output.as_copy_descriptor());
+    assert_eq!(f32::from_bytes(&actual), [0.75]);
+}
+
+fn test_fake_quantize(value: QuantValue, block_size: Option<usize>) {
+    let client = TestRuntime::client(&Default::default());
+    let (data, scales) = inputs(value, block_size);
+    let scheme = scheme(value, block_size);
+
+    let input = upload(&client, &data, vec![M, N]);
+    let scale = upload(&client, &scales, scale_shape(block_size));
+    let output = TensorHandle::zeros(&client, vec![M, N], f32::as_type_native_unchecked());
+
+    fake_quantize(
+        &client,
+        &input.as_ref(),
+        &output.as_ref(),
+        &scale.as_ref(),
+        &scheme,
+        ElemType::Float(FloatKind::F32),
+    )
+    .unwrap();
+
+    let actual = client.read_one_tensor(output.as_copy_descriptor());
+    let actual = f32::from_bytes(&actual);
+
+    let (q_min, q_max) = value.range();
+    let block_len = block_size.unwrap_or(M * N);
+    for (i, (x, actual)) in data.iter().zip(actual).enumerate() {
+        let scale = scales[i / block_len];
+        let expected = (x / scale).round().clamp(q_min, q_max) * scale;
+        assert_eq!(*actual, expected, "Mismatch at {i}, Input: {x}");
+    }
+}
+
+fn test_fake_quantize_backward(value: QuantValue, block_size: Option<usize>) {
+    let client = TestRuntime::client(&Default::default());
+    let (data, scales) = inputs(value, block_size);
+    let grad: Vec<f32> = (0..M * N).map(|i| f32::cos(i as f32 * 0.7)).collect();
+    let scheme = scheme(value, block_size);
+
+    let input = upload(&client, &data, vec![M, N]);
+    let grad_output = upload(&client, &grad, vec![M, N]);
+    let scale = upload(&client, &scales, scale_shape(block_size));
+    let grad_input = TensorHandle::zeros(&client, vec![M, N], f32::as_type_native_unchecked());
+    let grad_input_lsq = TensorHandle::zeros(&client, vec![M, N], f32::as_type_native_unchecked());
+    let grad_scale = TensorHandle::zeros(
+        &client,
+        scale_shape(block_size),
+        f32::as_type_native_unchecked(),
+    );
+
+    fake_quantize_backward(
+        &client,
+        &input.as_ref(),
+        &grad_output.as_ref(),
+        &grad_input.as_ref(),
+        &scale.as_ref(),
+        None,
+        &scheme,
+        ElemType::Float(FloatKind::F32),
+    )
+    .unwrap();
+    fake_quantize_backward(
+        &client,
+        &input.as_ref(),
+        &grad_output.as_ref(),
+        &grad_input_lsq.as_ref(),
+        &scale.as_ref(),
+        Some(&grad_scale.as_ref()),
+        &scheme,
+        ElemType::Float(FloatKind::F32),
+    )
+    .unwrap();
+
+    let actual = client.read_one_tensor(grad_input.as_copy_descriptor());
+    let actual = f32::from_bytes(&actual);
+    let actual_lsq = client.read_one_tensor(grad_input_lsq.as_copy_descriptor());
+    let actual_lsq = f32::from_bytes(&actual_lsq);
+    let actual_scale = client.read_one_tensor(grad_scale.as_copy_descriptor());
+    let actual_scale = f32::from_bytes(&actual_scale);
+
+    let (q_min, q_max) = value.range();
+    let block_len = block_size.unwrap_or(M * N);
+    let mut expected_scale = vec![0.0; scales.len()];
+    for (i, x) in data.iter().enumerate() {
+        let scale = scales[i / block_len];
+        let scaled = x / scale;
+        let rounded = scaled.round();
+        let (expected, derivative) = match rounded {
+            r if r < q_min => (0.0, q_min),
+            r if r > q_max => (0.0, q_max),
+            r => (grad[i], r - scaled),
+        };
+        expected_scale[i / block_len] += grad[i] * derivative;
+
+        assert_eq!(actual[i], expected, "Mismatch at {i}, Input: {x}");
+        assert_eq!(actual_lsq[i], expected, "LSQ mismatch at {i}, Input: {x}");
+    }
+
+    let grad_factor = 1.0 / (block_len as f32 * q_max).sqrt();
+    for (block, (actual, expected)) in actual_scale.iter().zip(&expected_scale).enumerate() {
+        let expected = expected * grad_factor;
+        assert!(
+            (actual - expected).abs() <= 1e-4 * expected.abs().max(1.0),
+            "Scale gradient mismatch at block {block}, Actual: {actual} | Expected: {expected}"
+        );
+    }
+}
+
+fn scheme(value: QuantValue, block_size: Option<usize>) -> QuantScheme {
+    let level = match block_size {
+        Some(block_size) => QuantLevel::block([block_size as u8]),
+        None => QuantLevel::Tensor,
+    };
+
+    QuantScheme::default()
+        .with_level(level)
+        .with_mode(QuantMode::Symmetric)
+        .with_value(value)
+        .with_store(QuantStore::PackedU32(0))
+}
+
+/// Values up to 5 in magnitude, with scales that clamp the largest of them.
+fn inputs(value: QuantValue, block_size: Option<usize>) -> (Vec<f32>, Vec<f32>) {
+    let num_blocks = M * N / block_size.unwrap_or(M * N);
+    let data = (0..M * N)
+        .map(|i| f32::sin(i as f32 * 1.3) * (1 + i % 5) as f32)
+        .collect();
+
+    let (_, q_max) = value.range();
+    let scales = (0..num_blocks)
+        .map(|block| 4.0 * (1.0 + block as f32 * 0.1) / q_max)
+        .collect();
+
+    (data, scales)
+}
+
+fn scale_shape(block_size: Option<usize>) -> Vec<usize> {
+    match block_size {
+        Some(block_size) => vec![M, N / block_size],
+        None => vec![1],
+    }
+}
+
+fn upload(
+    client: &ComputeClient<TestRuntime>,
+    data: &[f32],
+    shape: Vec<usize>,
+) -> TensorHandle<TestRuntime> {
+    let alloc = client.create_tensor_from_slice(f32::as_bytes(data), &shape, f32::type_size());
+    TensorHandle::new(
+        alloc.handle,
+        shape,
+        alloc.strides,
+        f32::as_type_native_unchecked(),
+    )
+}
@@ -0,0 +1,220 @@
+use cubecl::TestRuntime;
+use cubecl::prelude::*;
+use cubecl::std::tensor::TensorHandle;
+use cubek_quant::QuantError;
+use cubek_quant::gguf::{GgufFormat, dequantize_gguf};
+use half::f16;
+
+#[test]
+fn test_gguf_q4_0() {
+    test_gguf(GgufFormat::Q4_0, reference_q4_0);
+}
+
+#[test]
+fn test_gguf_q4_1() {
+    test_gguf(GgufFormat::Q4_1, reference_q4_1);
+}
+
+#[test]
+fn test_gguf_q5_0() {
+    test_gguf(GgufFormat::Q5_0, reference_q5_0);
+}
+
+#[test]
+fn test_gguf_q8_0() {
+    test_gguf(GgufFormat::Q8_0, reference_q8_0);
+}
+
+#[test]
+fn test_gguf_q4_k() {
+    test_gguf(GgufFormat::Q4_K, reference_q4_k);
+}
+
+#[test]
+fn test_gguf_q6_k() {
+    test_gguf(GgufFormat::Q6_K, reference_q6_k);
+}
+
+#[test]
+fn test_gguf_truncated_data() {
+    let client = TestRuntime::client(&Default::default());
+    let format = GgufFormat::Q4_0;
+    let (m, n) = (8, 64);
+    // One block short of the output
+    let num_bytes = (m * n / format.block_len() - 1) * format.block_bytes();
+    let handle = client.create_from_slice(&vec![0; num_bytes.next_multiple_of(4)]);
+    let output = TensorHandle::zeros(&client, vec![m, n], f32::as_type_native_unchecked());
+
+    let result = dequantize_gguf(
+        &client,
+        &handle,
+        &output.as_ref(),
+        format,
+        f32::as_type_native_unchecked(),
+    );
+    assert!(
+        matches!(result, Err(QuantError::ShapeMismatch { .. })),
+        "Unexpected result {result:?}"
+    );
+}
+
+fn test_gguf(format: GgufFormat, reference: fn(&[u8], &mut [f32])) {
+    let (m, n) = (8, 512);
+    let num_blocks = m * n / format.block_len();
+
+    // Random bytes, with the f16 fields replaced by finite values
+    let mut state = 0x2545_F491u32;
+    let mut data: Vec<u8> = (0..num_blocks * format.block_bytes())
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state as u8
+        })
+        .collect();
+    for (i, block) in data.chunks_mut(format.block_bytes()).enumerate() {
+        let d = f16::from_f32(0.01 * (i % 7) as f32 - 0.02);
+        let m = f16::from_f32(0.003 * (i % 5) as f32);
+        let fields: &[(usize, f16)] = match format {
+            GgufFormat::Q4_1 | GgufFormat::Q4_K => &[(0, d), (2, m)],
+            GgufFormat::Q6_K => &[(208, d)],
+            _ => &[(0, d)],
+        };
+        for (offset, value) in fields {
+            block[*offset..offset + 2].copy_from_slice(&value.to_le_bytes());
+        }
+    }
+
+    let mut expected = vec![0.0; m * n];
+    for (block, values) in data
+        .chunks(format.block_bytes())
+        .zip(expected.chunks_mut(format.block_len()))
+    {
+        reference(block, values);
+    }
+
+    let client = TestRuntime::client(&Default::default());
+    let mut padded = data.clone();
+    padded.resize(data.len().next_multiple_of(4), 0);
+    let handle = client.create_from_slice(&padded);
+    let output = TensorHandle::zeros(&client, vec![m, n], f32::as_type_native_unchecked());
+
+    dequantize_gguf(
+        &client,
+        &handle,
+        &output.as_ref(),
+        format,
+        f32::as_type_native_unchecked(),
+    )
+    .unwrap();
+
+    let actual = client.read_one_tensor(output.as_copy_descriptor());
+    let actual = f32::from_bytes(&actual);
+
+    for (i, (actual, expected)) in actual.iter().zip(&expected).enumerate() {
+        let tolerance = 1e-6 * expected.abs().max(1e-3);
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "Mismatch at {i}, Actual: {actual} | Expected: {expected}"
+        );
+    }
+}
+
+// Host references following the dequantization loops of llama.cpp
+
+fn read_f16(bytes: &[u8]) -> f32 {
+    f16::from_le_bytes([bytes[0], bytes[1]]).to_f32()
+}
+
+#[allow(clippy::needless_range_loop)]
+fn reference_q4_0(block: &[u8], y: &mut [f32]) {
+    let d = read_f16(&block[0..]);
+    let qs = &block[2..];
+    for j in 0..16 {
+        y[j] = ((qs[j] & 0x0F) as i32 - 8) as f32 * d;
+        y[j + 16] = ((qs[j] >> 4) as i32 - 8) as f32 * d;
+    }
+}
+
+#[allow(clippy::needless_range_loop)]
+fn reference_q4_1(block: &[u8], y: &mut [f32]) {
+    let d = read_f16(&block[0..]);
+    let m = read_f16(&block[2..]);
+    let qs = &block[4..];
+    for j in 0..16 {
+        y[j] = (qs[j] & 0x0F) as f32 * d + m;
+        y[j + 16] = (qs[j] >> 4) as f32 * d + m;
+    }
+}
+
+#[allow(clippy::needless_range_loop)]
+fn reference_q5_0(block: &[u8], y: &mut [f32]) {
+    let d = read_f16(&block[0..]);
+    let qh = u32::from_le_bytes([block[2], block[3], block[4], block[5]]);
+    let qs = &block[6..];
+    for j in 0..16 {
+        let xh_0 = ((qh >> j) << 4) & 0x10;
+        let xh_1 = (qh >> (j + 12)) & 0x10;
+        y[j] = (((qs[j] as u32 & 0x0F) | xh_0) as i32 - 16) as f32 * d;
+        y[j + 16] = (((qs[j] as u32 >> 4) | xh_1) as i32 - 16) as f32 * d;
+    }
+}
+
+fn reference_q8_0(block: &[u8], y: &mut [f32]) {
+    let d = read_f16(&block[0..]);
+    for (y, q) in y.iter_mut().zip(&block[2..34]) {
+        *y = (*q as i8) as f32 * d;
+    }
+}
+
+fn scale_min_k4(j: usize, q: &[u8]) -> (u8, u8) {
+    if j < 4 {
+        (q[j] & 63, q[j + 4] & 63)
+    } else {
+        (
+            (q[j + 4] & 0xF) | ((q[j - 4] >> 6) << 4),
+            (q[j + 4] >> 4) | ((q[j] >> 6) << 4),
+        )
+    }
+}
+
+#[allow(clippy::needless_range_loop)]
+fn reference_q4_k(block: &[u8], y: &mut [f32]) {
+    let d = read_f16(&block[0..]);
+    let min = read_f16(&block[2..]);
+    let scales = &block[4..16];
+
+    for (is, n) in (0..256).step_by(64).enumerate() {
+        let q = &block[16 + n / 2..];
+        let (sc, m) = scale_min_k4(2 * is, scales);
+        let (d1, m1) = (d * sc as f32, min * m as f32);
+        let (sc, m) = scale_min_k4(2 * is + 1, scales);
+        let (d2, m2) = (d * sc as f32, min * m as f32);
+        for l in 0..32 {
+            y[n + l] = d1 * (q[l] & 0xF) as f32 - m1;
+            y[n + l + 32] = d2 * (q[l] >> 4) as f32 - m2;
+        }
+    }
+}
+
+#[allow(clippy::needless_range_loop)]
+fn reference_q6_k(block: &[u8], y: &mut [f32]) {
+    let d = read_f16(&block[208..]);
+
+    for n in (0..256).step_by(128) {
+        let ql = &block[n / 2..];
+        let qh = &block[128 + n / 4..];
+        let sc = &block[192 + n / 16..];
+        for l in 0..32 {
+            let is = l / 16;
+            let q1 = ((ql[l] & 0xF) | ((qh[l] & 3) << 4)) as i32 - 32;
+            let q2 = ((ql[l + 32] & 0xF) | (((qh[l] >> 2) & 3) << 4)) as i32 - 32;
+            let q3 = ((ql[l] >> 4) | (((qh[l] >> 4) & 3) << 4)) as i32 - 32;
+            let q4 = ((ql[l + 32] >> 4) | (((qh[l] >> 6) & 3) << 4)) as i32 - 32;
+            y[n + l] = d * (sc[is] as i8) as f32 * q1 as f32;
+            y[n + l + 32] = d * (sc[is + 2] as i8) as f32 * q2 as f32;
+            y[n + l + 64] = d * (sc[is + 4] as i8) as f32 * q3 as f32;
+            y[n + l + 96] = d * (sc[is + 6] as i8) as f32 * q4 as f32;
+        }
+    }
+}
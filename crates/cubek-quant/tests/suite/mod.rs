@@ -2,6 +2,15 @@ use cubecl::Runtime;
 use cubecl::prelude::*;
 use cubek_quant::scheme::{QuantLevel, QuantParam};
 
+mod calibration;
+mod codebook;
+mod errors;
+mod fake_quantize;
+mod gguf;
+mod mx;
+mod nvfp4;
+mod requantize;
+
 #[macro_export]
 macro_rules! testgen_quant {
     ($value: expr, $shape_x: expr, $shape_y: expr) => {
@@ -10,6 +19,24 @@ macro_rules! testgen_quant {
         static VALUE: QuantValue = $value;
 
         include!("symmetric.rs");
+
+        mod affine {
+            use super::*;
+
+            include!("affine.rs");
+        }
+
+        mod dynamic {
+            use super::*;
+
+            include!("dynamic.rs");
+        }
+
+        mod per_axis {
+            use super::*;
+
+            include!("per_axis.rs");
+        }
     };
 
     ($shape_x: expr, $shape_y: expr) => {
@@ -0,0 +1,220 @@
+use cubecl::TestRuntime;
+use cubecl::features::TypeUsage;
+use cubecl::ir::ElemType;
+use cubecl::ir::FloatKind;
+use cubecl::prelude::*;
+use cubecl::std::tensor::TensorHandle;
+use cubecl_common::{e2m1, e4m3, e5m2};
+use cubek_quant::mx::{MX_BLOCK_SIZE, dequantize_mx, mx_emax, quantize_mx};
+use cubek_quant::scheme::{QuantLevel, QuantMode, QuantParam, QuantScheme, QuantStore, QuantValue};
+use cubek_quant::{dequantize, quantize};
+
+#[test]
+fn test_mxfp8_e4m3() {
+    test_mx(QuantValue::E4M3);
+}
+
+#[test]
+fn test_mxfp8_e5m2() {
+    test_mx(QuantValue::E5M2);
+}
+
+#[test]
+fn test_mxfp4() {
+    test_mx(QuantValue::E2M1);
+}
+
+#[test]
+fn test_e2m1_packed_native_launch_ref() {
+    let client = TestRuntime::client(&Default::default());
+    if !i8::supported_uses(&client).contains(TypeUsage::Conversion) {
+        return;
+    }
+
+    let (m, n) = (4, 64);
+    let shape = vec![m, n];
+    let scheme = QuantScheme::default()
+        .with_level(QuantLevel::Tensor)
+        .with_mode(QuantMode::Symmetric)
+        .with_value(QuantValue::E2M1)
+        .with_store(QuantStore::PackedNative(0))
+        .with_param(QuantParam::F32);
+
+    // Multiples of the scale that E2M1 represents exactly
+    let levels = [-6.0, -4.0, -3.0, -2.0, -1.0, 0.0, 1.0, 2.0, 3.0, 4.0, 6.0];
+    let step = 0.5f32;
+    let data: Vec<f32> = (0..m * n)
+        .map(|i| levels[i % levels.len()] * step)
+        .collect();
+
+    let input_alloc =
+        client.create_tensor_from_slice(f32::as_bytes(&data), &shape, f32::type_size());
+    let input = TensorHandle::new(
+        input_alloc.handle,
+        shape.clone(),
+        input_alloc.strides,
+        f32::as_type_native_unchecked(),
+    );
+    let scale_alloc =
+        client.create_tensor_from_slice(f32::as_bytes(&[step]), &[1], f32::type_size());
+    let scale = TensorHandle::new(
+        scale_alloc.handle,
+        vec![1],
+        scale_alloc.strides,
+        f32::as_type_native_unchecked(),
+    );
+    let output = TensorHandle::empty(&client, vec![m, n / 2], u8::as_type_native_unchecked());
+    let output_scale = TensorHandle::empty(&client, vec![1], f32::as_type_native_unchecked());
+    let output_f = TensorHandle::zeros(&client, shape, f32::as_type_native_unchecked());
+
+    quantize::launch_ref(
+        &client,
+        &input.as_ref(),
+        &output.as_ref(),
+        &scale.as_ref(),
+        &output_scale.as_ref(),
+        &scheme,
+        ElemType::Float(FloatKind::F32),
+    )
+    .unwrap();
+    dequantize::launch_ref(
+        &client,
+        &output.as_ref(),
+        &output_f.as_ref(),
+        &output_scale.as_ref(),
+        &scheme,
+        f32::as_type_native_unchecked(),
+    )
+    .unwrap();
+
+    // Two elements per byte, the first in the low nibble
+    let values = client.read_one_tensor(output.as_copy_descriptor());
+    for (i, input) in data.iter().enumerate() {
+        let expected = e2m1::from_f32(input / step).to_bits();
+        let actual = (values[i / 2] >> (4 * (i % 2))) & 0xF;
+        assert_eq!(actual, expected, "Mismatch at {i}, Input: {input}");
+    }
+
+    let restored = client.read_one_tensor(output_f.as_copy_descriptor());
+    assert_eq!(f32::from_bytes(&restored), data);
+}
+
+fn test_mx(value: QuantValue) {
+    let (m, n) = (16, 128);
+    let num_blocks = m * n / MX_BLOCK_SIZE;
+
+    // Every block has its own magnitude, with a zero block to check the smallest scale.
+    let data: Vec<f32> = (0..m * n)
+        .map(|i| {
+            let block = i / MX_BLOCK_SIZE;
+            let magnitude = match block % 11 {
+                10 => 0.0,
+                exponent => 2f32.powi(exponent as i32 * 3 - 15),
+            };
+            f32::sin(i as f32 * 0.7) * magnitude
+        })
+        .collect();
+
+    let (values, scales, restored) = quantize_dequantize(&data, m, n, value);
+
+    // Scales are the biased exponents of floor(log2(amax)) - emax
+    for (block, chunk) in data.chunks(MX_BLOCK_SIZE).enumerate() {
+        let amax = chunk.iter().fold(0.0f32, |max, v| max.max(v.abs()));
+        let exponent =
+            ((amax.to_bits() >> 23) as i32 - 127 - mx_emax(value).unwrap()).clamp(-127, 127);
+        assert_eq!(
+            scales[block] as i32,
+            exponent + 127,
+            "Scale mismatch at block {block}"
+        );
+    }
+    assert_eq!(scales.len(), num_blocks);
+
+    // Elements are the scaled values rounded to nearest even, saturated to the largest normal
+    let (_, range_max) = value.range();
+    for (i, (actual, input)) in restored.iter().zip(&data).enumerate() {
+        let scale = scale_value(scales[i / MX_BLOCK_SIZE]);
+        let scaled = (input / scale).clamp(-range_max, range_max);
+        let element = match value {
+            QuantValue::E4M3 => e4m3::from_f32(scaled).to_f32(),
+            QuantValue::E5M2 => e5m2::from_f32(scaled).to_f32(),
+            _ => e2m1::from_f32(scaled).to_f32(),
+        };
+        assert_eq!(
+            *actual,
+            element * scale,
+            "Mismatch at {i}, Input: {input} | Scale: {scale}"
+        );
+    }
+
+    // Quantizing the dequantized values gives back the same bits
+    let (values_again, scales_again, _) = quantize_dequantize(&restored, m, n, value);
+    assert_eq!(values, values_again);
+    assert_eq!(scales, scales_again);
+}
+
+fn scale_value(biased_exponent: u8) -> f32 {
+    match biased_exponent {
+        0 => f32::from_bits(0x0040_0000),
+        exponent => f32::from_bits((exponent as u32) << 23),
+    }
+}
+
+/// Quantize to an MX format, returning the raw values and scales along with the dequantized
+/// values.
+fn quantize_dequantize(
+    data: &[f32],
+    m: usize,
+    n: usize,
+    value: QuantValue,
+) -> (Vec<u8>, Vec<u8>, Vec<f32>) {
+    let client = TestRuntime::client(&Default::default());
+    let shape = vec![m, n];
+    let packing = match value {
+        QuantValue::E2M1 => 2,
+        _ => 1,
+    };
+
+    let input_alloc =
+        client.create_tensor_from_slice(f32::as_bytes(data), &shape, f32::type_size());
+    let input = TensorHandle::new(
+        input_alloc.handle,
+        shape.clone(),
+        input_alloc.strides,
+        f32::as_type_native_unchecked(),
+    );
+
+    let byte_dtype = u8::as_type_native_unchecked();
+    let output = TensorHandle::empty(&client, vec![m, n / packing], byte_dtype);
+    let output_scale = TensorHandle::empty(&client, vec![m, n / MX_BLOCK_SIZE], byte_dtype);
+    let output_f = TensorHandle::zeros(&client, shape, f32::as_type_native_unchecked());
+
+    quantize_mx(
+        &client,
+        &input.as_ref(),
+        &output.as_ref(),
+        &output_scale.as_ref(),
+        value,
+        ElemType::Float(FloatKind::F32),
+    )
+    .unwrap();
+
+    dequantize_mx(
+        &client,
+        &output.as_ref(),
+        &output_f.as_ref(),
+        &output_scale.as_ref(),
+        value,
+        f32::as_type_native_unchecked(),
+    )
+    .unwrap();
+
+    let values = client.read_one_tensor(output.as_copy_descriptor()).to_vec();
+    let scales = client
+        .read_one_tensor(output_scale.as_copy_descriptor())
+        .to_vec();
+    let restored = client.read_one_tensor(output_f.as_copy_descriptor());
+    let restored = f32::from_bytes(&restored).to_vec();
+
+    (values, scales, restored)
+}
@@ -0,0 +1,129 @@
+use cubecl::TestRuntime;
+use cubecl::ir::ElemType;
+use cubecl::ir::FloatKind;
+use cubecl::prelude::*;
+use cubecl::std::tensor::TensorHandle;
+use cubecl_common::{e2m1, e4m3};
+use cubek_quant::nvfp4::{NVFP4_BLOCK_SIZE, dequantize_nvfp4, quantize_nvfp4};
+
+#[test]
+fn test_nvfp4() {
+    let (m, n) = (16, 128);
+
+    // Block magnitudes span more than the range of E4M3, so the smallest blocks get flushed to zero
+    // and a zero block checks the smallest block scale.
+    let data: Vec<f32> = (0..m * n)
+        .map(|i| {
+            let block = i / NVFP4_BLOCK_SIZE;
+            let magnitude = match block % 13 {
+                12 => 0.0,
+                exponent => 2f32.powi(exponent as i32 * 2 - 18) * 1.3,
+            };
+            f32::sin(i as f32 * 0.7) * magnitude
+        })
+        .collect();
+
+    let quantized = quantize_dequantize(&data, m, n);
+
+    let amax = data.iter().fold(0.0f32, |max, v| max.max(v.abs()));
+    let global = amax / (448.0 * 6.0);
+    assert_eq!(quantized.global_scale, global);
+
+    let mut elements = Vec::with_capacity(m * n);
+    for (block, chunk) in data.chunks(NVFP4_BLOCK_SIZE).enumerate() {
+        let amax = chunk.iter().fold(0.0f32, |max, v| max.max(v.abs()));
+        let block_scale = e4m3::from_f32((amax / (6.0 * global)).min(448.0));
+        assert_eq!(
+            quantized.scales[block],
+            block_scale.to_bits(),
+            "Scale mismatch at block {block}"
+        );
+
+        let scale = block_scale.to_f32() * global;
+        for (i, input) in chunk.iter().enumerate() {
+            let scaled = match scale > 0.0 {
+                true => (input / scale).clamp(-6.0, 6.0),
+                false => 0.0,
+            };
+            let element = e2m1::from_f32(scaled);
+            let index = block * NVFP4_BLOCK_SIZE + i;
+            assert_eq!(
+                quantized.restored[index],
+                element.to_f32() * scale,
+                "Mismatch at {index}, Input: {input} | Scale: {scale}"
+            );
+            elements.push(element.to_bits());
+        }
+    }
+
+    // Two elements per byte, the first one in the low nibble
+    for (i, byte) in quantized.values.iter().enumerate() {
+        let expected = elements[2 * i] | (elements[2 * i + 1] << 4);
+        assert_eq!(*byte, expected, "Packing mismatch at byte {i}");
+    }
+}
+
+struct Quantized {
+    values: Vec<u8>,
+    scales: Vec<u8>,
+    global_scale: f32,
+    restored: Vec<f32>,
+}
+
+/// Quantize to NVFP4, returning the raw values and scales along with the dequantized values.
+fn quantize_dequantize(data: &[f32], m: usize, n: usize) -> Quantized {
+    let client = TestRuntime::client(&Default::default());
+    let shape = vec![m, n];
+
+    let input_alloc =
+        client.create_tensor_from_slice(f32::as_bytes(data), &shape, f32::type_size());
+    let input = TensorHandle::new(
+        input_alloc.handle,
+        shape.clone(),
+        input_alloc.strides,
+        f32::as_type_native_unchecked(),
+    );
+
+    let byte_dtype = u8::as_type_native_unchecked();
+    let output = TensorHandle::empty(&client, vec![m, n / 2], byte_dtype);
+    let output_scale = TensorHandle::empty(&client, vec![m, n / NVFP4_BLOCK_SIZE], byte_dtype);
+    let output_global_scale =
+        TensorHandle::empty(&client, vec![1], f32::as_type_native_unchecked());
+    let output_f = TensorHandle::zeros(&client, shape, f32::as_type_native_unchecked());
+
+    quantize_nvfp4(
+        &client,
+        &input.as_ref(),
+        &output.as_ref(),
+        &output_scale.as_ref(),
+        &output_global_scale.as_ref(),
+        ElemType::Float(FloatKind::F32),
+    )
+    .unwrap();
+
+    dequantize_nvfp4(
+        &client,
+        &output.as_ref(),
+        &output_f.as_ref(),
+        &output_scale.as_ref(),
+        &output_global_scale.as_ref(),
+        f32::as_type_native_unchecked(),
+    )
+    .unwrap();
+
+    let values = client.read_one_tensor(output.as_copy_descriptor()).to_vec();
+    let scales = client
+        .read_one_tensor(output_scale.as_copy_descriptor())
+        .to_vec();
+    let global_scale = client.read_one_tensor(output_global_scale.as_copy_descriptor());
+    let global_scale = f32::from_bytes(&global_scale)[0];
+    let restored = client.read_one_tensor(output_f.as_copy_descriptor());
+    let restored = f32::from_bytes(&restored).to_vec();
+
+    Quantized {
+        values,
+        scales,
+        global_scale,
+        restored,
+    }
+}
@@ -0,0 +1,201 @@
+use cubecl::TestRuntime;
+use cubecl::ir::ElemType;
+use cubecl::ir::FloatKind;
+use cubecl::server::AllocationDescriptor;
+use cubecl::server::CopyDescriptor;
+use cubecl::std::tensor::TensorHandle;
+use cubek_quant::scheme::QuantMode;
+use cubek_quant::scheme::QuantScheme;
+use cubek_quant::scheme::QuantStore;
+use cubek_quant::scheme::QuantValue;
+
+#[test]
+fn test_quantization_per_axis_rows() {
+    test_quantization_per_axis(vec![SHAPE_X, SHAPE_Y], VALUE, 0, QuantStore::PackedU32(0));
+}
+
+#[test]
+fn test_quantization_per_axis_rank3() {
+    test_quantization_per_axis(vec![2, 3, 64], VALUE, 1, QuantStore::PackedU32(0));
+}
+
+#[test]
+fn test_quantization_per_axis_native_rows() {
+    if VALUE.size_bits() != 8 {
+        return;
+    }
+
+    test_quantization_per_axis(vec![SHAPE_X, SHAPE_Y], VALUE, 0, QuantStore::Native);
+}
+
+#[test]
+fn test_quantization_per_axis_native_cols() {
+    if VALUE.size_bits() != 8 {
+        return;
+    }
+
+    test_quantization_per_axis(vec![SHAPE_X, SHAPE_Y], VALUE, 1, QuantStore::Native);
+}
+
+#[test]
+fn test_quantization_per_axis_native_rank3_middle() {
+    if VALUE.size_bits() != 8 {
+        return;
+    }
+
+    test_quantization_per_axis(vec![4, 8, 3], VALUE, 1, QuantStore::Native);
+}
+
+#[test]
+fn test_quantization_per_axis_native_rank3_last() {
+    if VALUE.size_bits() != 8 {
+        return;
+    }
+
+    test_quantization_per_axis(vec![2, 3, 64], VALUE, 2, QuantStore::Native);
+}
+
+fn test_quantization_per_axis(
+    shape: Vec<usize>,
+    value: QuantValue,
+    axis: usize,
+    store: QuantStore,
+) {
+    let client = TestRuntime::client(&Default::default());
+    let rank = shape.len();
+    let n = shape[rank - 1];
+
+    // The magnitude depends on all coordinates, so every channel has its own range.
+    let num_elems: usize = shape.iter().product();
+    let data: Vec<_> = (0..num_elems)
+        .map(|v| {
+            let (row, col) = ((v / n) as f32, (v % n) as f32);
+            (col - n as f32 / 2.0 + 0.5) * (row + 1.0) / num_elems as f32
+        })
+        .collect();
+    let input_alloc =
+        client.create_tensor_from_slice(f32::as_bytes(&data), &shape, f32::type_size());
+
+    let (q_min, q_max) = value.range();
+
+    let inner_len: usize = shape[axis + 1..].iter().product();
+    let num_channels = shape[axis];
+    let channel = |i: usize| (i / inner_len) % num_channels;
+    let mut shape_scale = vec![1; rank];
+    shape_scale[axis] = num_channels;
+
+    let mut absmax = vec![0.0f32; num_channels];
+    for (i, value) in data.iter().enumerate() {
+        absmax[channel(i)] = absmax[channel(i)].max(value.abs());
+    }
+    let scales: Vec<_> = absmax
+        .iter()
+        .map(|absmax| 2.0 * absmax / (q_max - q_min))
+        .collect();
+
+    let scale_alloc =
+        client.create_tensor_from_slice(f32::as_bytes(&scales), &shape_scale, f32::type_size());
+
+    let input = TensorHandle::new(
+        input_alloc.handle,
+        shape.clone(),
+        input_alloc.strides,
+        f32::as_type_native_unchecked(),
+    );
+    let scale = TensorHandle::new(
+        scale_alloc.handle,
+        shape_scale.clone(),
+        scale_alloc.strides,
+        f32::as_type_native_unchecked(),
+    );
+    let output_f = TensorHandle::zeros(&client, shape.clone(), f32::as_type_native_unchecked());
+
+    let scheme = QuantScheme::default()
+        .with_level(QuantLevel::Tensor)
+        .with_mode(QuantMode::Symmetric)
+        .with_value(value)
+        .with_store(store)
+        .with_param(QuantParam::F32);
+
+    // The shape is from the POV of the stored values, packed u32s or native i8s.
+    let mut shape_out = shape;
+    let elem_out = match store {
+        QuantStore::Native => i8::as_type_native_unchecked(),
+        _ => {
+            shape_out[rank - 1] /= scheme.num_quants();
+            u32::as_type_native_unchecked()
+        }
+    };
+
+    let [output_alloc, output_scale_alloc] = client
+        .empty_tensors(vec![
+            AllocationDescriptor {
+                kind: cubecl::server::AllocationKind::Contiguous,
+                shape: &shape_out,
+                elem_size: elem_out.size(),
+            },
+            AllocationDescriptor {
+                kind: cubecl::server::AllocationKind::Contiguous,
+                shape: &shape_scale,
+                elem_size: f32::type_size(),
+            },
+        ])
+        .try_into()
+        .unwrap();
+    let output = TensorHandle::new(
+        output_alloc.handle,
+        shape_out,
+        output_alloc.strides,
+        elem_out,
+    );
+    let output_scale = TensorHandle::new(
+        output_scale_alloc.handle,
+        shape_scale,
+        output_scale_alloc.strides,
+        f32::as_type_native_unchecked(),
+    );
+
+    cubek_quant::quantize::launch_ref_per_axis(
+        &client,
+        &input.as_ref(),
+        &output.as_ref(),
+        &scale.as_ref(),
+        &output_scale.as_ref(),
+        &scheme,
+        axis,
+        ElemType::Float(FloatKind::Flex32),
+    )
+    .unwrap();
+
+    cubek_quant::dequantize::launch_ref_per_axis(
+        &client,
+        &output.as_ref(),
+        &output_f.as_ref(),
+        &output_scale.as_ref(),
+        &scheme,
+        axis,
+        f32::as_type_native_unchecked(),
+    )
+    .unwrap();
+
+    let computed = client.read_one_tensor(CopyDescriptor::new(
+        output_f.handle.binding(),
+        &output_f.shape,
+        &output_f.strides,
+        core::mem::size_of::<f32>(),
+    ));
+    let data_restored = f32::from_bytes(&computed);
+
+    assert_eq!(data_restored.len(), data.len());
+    let rel_tol = 1e-4;
+    for (i, (actual, expected)) in data_restored.iter().zip(data.into_iter()).enumerate() {
+        let scale = scales[channel(i)];
+        // Max quantization error = step size / 2
+        let max_error = (scale / 2.0) * (1f32 + rel_tol);
+        let diff = f32::abs(actual - expected);
+        assert!(
+            diff <= max_error,
+            "Mismatch at {i}, Expected: {expected} | Actual: {actual} (diff {diff} > {max_error})"
+        );
+    }
+}
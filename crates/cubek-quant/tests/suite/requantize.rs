@@ -0,0 +1,213 @@
+use cubecl::TestRuntime;
+use cubecl::ir::ElemType;
+use cubecl::ir::FloatKind;
+use cubecl::prelude::*;
+use cubecl::std::tensor::TensorHandle;
+use cubecl_common::e2m1;
+use cubek_quant::QuantError;
+use cubek_quant::requantize::requantize;
+use cubek_quant::scheme::{QuantLevel, QuantMode, QuantParam, QuantScheme, QuantStore, QuantValue};
+use cubek_quant::{dequantize, quantize};
+
+const M: usize = 8;
+const N: usize = 256;
+
+#[test]
+fn test_requantize_q8s_block_32_to_q4s_block_128() {
+    let client = TestRuntime::client(&Default::default());
+    let in_scheme = scheme(QuantValue::Q8S, QuantStore::PackedU32(0), 32);
+    let out_scheme = scheme(QuantValue::Q4S, QuantStore::PackedU32(0), 128);
+    let (values, scale) = quantize(&client, &data(), &in_scheme);
+
+    let output = empty_values(&client, &out_scheme);
+    let out_scale = empty_scales(&client, &out_scheme);
+    requantize(
+        &client,
+        &values.as_ref(),
+        &scale.as_ref(),
+        &in_scheme,
+        &output.as_ref(),
+        &out_scale.as_ref(),
+        &out_scheme,
+    )
+    .unwrap();
+
+    // Same bits as quantizing the dequantized values
+    let restored = dequantize(&client, &values, &scale, &in_scheme);
+    let (expected, expected_scale) = quantize(&client, &restored, &out_scheme);
+
+    let actual = client.read_one_tensor(output.as_copy_descriptor());
+    let expected = client.read_one_tensor(expected.as_copy_descriptor());
+    assert_eq!(u32::from_bytes(&actual), u32::from_bytes(&expected));
+    assert_eq!(
+        read_f32(&client, &out_scale),
+        read_f32(&client, &expected_scale)
+    );
+}
+
+#[test]
+fn test_requantize_packed_u32_to_packed_native_e2m1() {
+    let client = TestRuntime::client(&Default::default());
+    let in_scheme = scheme(QuantValue::Q4S, QuantStore::PackedU32(0), 32);
+    let out_scheme = scheme(QuantValue::E2M1, QuantStore::PackedNative(0), 32);
+    let (values, scale) = quantize(&client, &data(), &in_scheme);
+
+    let output = empty_values(&client, &out_scheme);
+    let out_scale = empty_scales(&client, &out_scheme);
+    requantize(
+        &client,
+        &values.as_ref(),
+        &scale.as_ref(),
+        &in_scheme,
+        &output.as_ref(),
+        &out_scale.as_ref(),
+        &out_scheme,
+    )
+    .unwrap();
+
+    let restored = dequantize(&client, &values, &scale, &in_scheme);
+    let actual = client.read_one_tensor(output.as_copy_descriptor());
+    let actual_scale = read_f32(&client, &out_scale);
+
+    let (_, range_max) = QuantValue::E2M1.range();
+    for (block, chunk) in restored.chunks(32).enumerate() {
+        let absmax = chunk.iter().fold(0.0f32, |max, v| max.max(v.abs()));
+        assert_eq!(
+            actual_scale[block],
+            absmax / range_max,
+            "Scale mismatch at block {block}"
+        );
+    }
+
+    // Two elements per byte, the first in the low nibble
+    for (i, input) in restored.iter().enumerate() {
+        let scale = actual_scale[i / 32];
+        let expected = e2m1::from_f32((input / scale).clamp(-range_max, range_max)).to_bits();
+        let actual = (actual[i / 2] >> (4 * (i % 2))) & 0xF;
+        assert_eq!(
+            actual, expected,
+            "Mismatch at {i}, Input: {input} | Scale: {scale}"
+        );
+    }
+}
+
+#[test]
+fn test_requantize_unsupported_scheme() {
+    let client = TestRuntime::client(&Default::default());
+    let in_scheme = scheme(QuantValue::Q8S, QuantStore::PackedU32(0), 32);
+    // 4-bit values can't be stored natively
+    let out_scheme = scheme(QuantValue::Q4S, QuantStore::Native, 32);
+    let (values, scale) = quantize(&client, &data(), &in_scheme);
+
+    let output = TensorHandle::empty(&client, vec![M, N], u8::as_type_native_unchecked());
+    let out_scale = empty_scales(&client, &out_scheme);
+    let result = requantize(
+        &client,
+        &values.as_ref(),
+        &scale.as_ref(),
+        &in_scheme,
+        &output.as_ref(),
+        &out_scale.as_ref(),
+        &out_scheme,
+    );
+    assert!(
+        matches!(result, Err(QuantError::UnsupportedScheme(_))),
+        "Unexpected result {result:?}"
+    );
+}
+
+fn scheme(value: QuantValue, store: QuantStore, block_size: u8) -> QuantScheme {
+    QuantScheme::default()
+        .with_level(QuantLevel::block([block_size]))
+        .with_mode(QuantMode::Symmetric)
+        .with_value(value)
+        .with_store(store)
+        .with_param(QuantParam::F32)
+}
+
+/// Different magnitudes per row, so every block has its own scale.
+fn data() -> Vec<f32> {
+    (0..M * N)
+        .map(|i| f32::sin(i as f32 * 0.37) * (1 + i / N) as f32)
+        .collect()
+}
+
+fn empty_values(
+    client: &ComputeClient<TestRuntime>,
+    scheme: &QuantScheme,
+) -> TensorHandle<TestRuntime> {
+    let dtype = match scheme.store {
+        QuantStore::PackedNative(_) => StorageType::Packed(ElemType::Float(FloatKind::E2M1), 2),
+        _ => u32::as_type_native_unchecked(),
+    };
+    TensorHandle::empty(client, vec![M, N / scheme.num_quants()], dtype)
+}
+
+fn empty_scales(
+    client: &ComputeClient<TestRuntime>,
+    scheme: &QuantScheme,
+) -> TensorHandle<TestRuntime> {
+    let QuantLevel::Block(block_size) = scheme.level else {
+        unreachable!()
+    };
+    let block_size = block_size.as_slice()[0] as usize;
+    TensorHandle::empty(
+        client,
+        vec![M, N / block_size],
+        f32::as_type_native_unchecked(),
+    )
+}
+
+/// Quantize the values with the scales of their absolute maximum.
+fn quantize(
+    client: &ComputeClient<TestRuntime>,
+    data: &[f32],
+    scheme: &QuantScheme,
+) -> (TensorHandle<TestRuntime>, TensorHandle<TestRuntime>) {
+    let alloc = client.create_tensor_from_slice(f32::as_bytes(data), &[M, N], f32::type_size());
+    let input = TensorHandle::new(
+        alloc.handle,
+        vec![M, N],
+        alloc.strides,
+        f32::as_type_native_unchecked(),
+    );
+    let values = empty_values(client, scheme);
+    let scale = empty_scales(client, scheme);
+
+    quantize::quantize_dynamic(
+        client,
+        &input.as_ref(),
+        &values.as_ref(),
+        &scale.as_ref(),
+        scheme,
+        ElemType::Float(FloatKind::F32),
+    )
+    .unwrap();
+
+    (values, scale)
+}
+
+fn dequantize(
+    client: &ComputeClient<TestRuntime>,
+    values: &TensorHandle<TestRuntime>,
+    scale: &TensorHandle<TestRuntime>,
+    scheme: &QuantScheme,
+) -> Vec<f32> {
+    let output = TensorHandle::empty(client, vec![M, N], f32::as_type_native_unchecked());
+    dequantize::launch_ref(
+        client,
+        &values.as_ref(),
+        &output.as_ref(),
+        &scale.as_ref(),
+        scheme,
+        f32::as_type_native_unchecked(),
+    )
+    .unwrap();
+
+    read_f32(client, &output)
+}
+
+fn read_f32(client: &ComputeClient<TestRuntime>, tensor: &TensorHandle<TestRuntime>) -> Vec<f32> {
+    let bytes = client.read_one_tensor(tensor.as_copy_descriptor());
+    f32::from_bytes(&bytes).to_vec()
+}
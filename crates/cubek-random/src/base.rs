@@ -1,48 +1,67 @@
+use cubecl::calculate_cube_count_elemwise;
 use cubecl::prelude::*;
 use cubecl::std::tensor::{
     View,
     layout::{
-        Coords1d,
-        linear::{LinearView, linear_view},
+        Coords1d, Layout, LayoutExpand,
+        linear::{LinearView, LinearViewLaunch},
     },
 };
-use cubecl_common::{rand::get_seeded_rng, stub::Mutex};
-use rand::{RngExt, SeedableRng, rngs::StdRng};
+use cubecl::std::{FastDivmod, FastDivmodArgs};
+use cubecl::tensor_line_size_parallel;
 
-pub(crate) const N_VALUES_PER_THREAD: usize = 128;
+use crate::RandomGenerator;
 
-static SEED: Mutex<Option<StdRng>> = Mutex::new(None);
+pub(crate) const N_VALUES_PER_THREAD: usize = 128;
 
-pub fn seed(seed: u64) {
-    let rng = StdRng::seed_from_u64(seed);
-    let mut seed = SEED.lock().unwrap();
-    *seed = Some(rng);
-}
+/// The number of streams is a multiple of every supported line size, so that consecutive lanes
+/// always write consecutive elements.
+pub(crate) const STREAM_ALIGNMENT: usize = 16;
 
 /// Pseudo-random generator
+///
+/// The output may be any strided view, like a slice of a larger buffer or a transposed tensor.
+/// Values are a pure function of the generator state and of the logical row-major index of the
+/// element: with `S` streams, only depending on the number of elements, element `i` is value
+/// `i / S` of stream `i % S`. The line size and cube dim only change how streams are assigned to
+/// units, so two outputs of the same shape get the same values whatever their layout. Dimensions
+/// with a zero stride are broadcast, and every element of the buffer is written once, with the
+/// value of its first logical index.
 pub(crate) fn random<F: RandomFamily, R: Runtime>(
     client: &ComputeClient<R>,
+    generator: &mut RandomGenerator,
     prng: F::Runtime,
     output: TensorHandleRef<'_, R>,
     dtype: StorageType,
 ) -> Result<(), LaunchError> {
-    let seeds = get_seeds();
+    let seeds = generator.next_seeds();
     let args = prng.args();
 
-    let cube_dim = CubeDim::new(client, output.size().div_ceil(N_VALUES_PER_THREAD));
-    let cube_count = prng_cube_count(output.size(), cube_dim, N_VALUES_PER_THREAD);
+    let num_elems = output.size();
+    if num_elems == 0 {
+        return Ok(());
+    }
+
+    // Each lane owns its own generator state, so adjacent values in a line are not correlated
+    // more than values written by adjacent units. A scalar has no dimension to vectorize along.
+    let output_line_size = match output.shape.is_empty() {
+        true => 1,
+        false => tensor_line_size_parallel(
+            client.io_optimized_line_sizes_unchecked(output.elem_size),
+            output.shape,
+            output.strides,
+            output.strides.len() - 1,
+        )
+        .min(STREAM_ALIGNMENT),
+    };
+
+    // Every unit drives one stream per lane
+    let num_units = prng_stream_count(num_elems) / output_line_size;
 
-    let output_line_size = 1;
-    // TODO: Higher vectorization can add some correlation locally.
-    //
-    // let output_line_size = tensor_line_size_parallel(
-    //     R::line_size_elem(&E::as_elem_native_unchecked()),
-    //     output.shape,
-    //     output.strides,
-    //     output.strides.len() - 1,
-    // );
+    let cube_dim = CubeDim::new(client, num_units);
+    let cube_count = calculate_cube_count_elemwise(client, num_units, cube_dim);
 
-    let output = linear_view(client, &output, output_line_size);
+    let output = output_view(client, &output, output_line_size);
 
     prng_kernel::launch::<F, R>(
         client,
@@ -53,35 +72,119 @@ pub(crate) fn random<F: RandomFamily, R: Runtime>(
         ScalarArg::new(seeds[1]),
         ScalarArg::new(seeds[2]),
         ScalarArg::new(seeds[3]),
+        ScalarArg::new(num_units),
         args,
-        N_VALUES_PER_THREAD,
+        N_VALUES_PER_THREAD * output_line_size,
         output_line_size,
         dtype,
     )
 }
 
-fn prng_cube_count(num_elems: usize, cube_dim: CubeDim, n_values_per_thread: usize) -> CubeCount {
-    let num_threads = f32::ceil(num_elems as f32 / n_values_per_thread as f32);
-    let num_invocations = f32::ceil(num_threads / cube_dim.num_elems() as f32);
-    let cubes_x = f32::ceil(f32::sqrt(num_invocations));
-    let cubes_y = f32::ceil(num_invocations / cubes_x);
-
-    CubeCount::Static(cubes_x as u32, cubes_y as u32, 1)
+/// Number of streams of [random] for an output of `num_elems` elements, each generating
+/// `N_VALUES_PER_THREAD` values.
+pub(crate) fn prng_stream_count(num_elems: usize) -> usize {
+    num_elems
+        .div_ceil(N_VALUES_PER_THREAD)
+        .next_multiple_of(STREAM_ALIGNMENT)
 }
 
-pub(crate) fn get_seeds() -> [u32; 4] {
-    let mut seed = SEED.lock().unwrap();
-    let mut rng: StdRng = match seed.take() {
-        Some(rng_seeded) => rng_seeded,
-        None => get_seeded_rng(),
+/// Creates a view of a possibly strided and broadcast output, see [OutputLayout].
+fn output_view<'a, R: Runtime>(
+    client: &ComputeClient<R>,
+    handle: &'a TensorHandleRef<'a, R>,
+    line_size: LineSize,
+) -> LinearViewLaunch<'a, R> {
+    // The buffer must cover the furthest element, which can be past the logical size
+    let extent = 1 + handle
+        .shape
+        .iter()
+        .zip(handle.strides)
+        .map(|(shape, stride)| (shape - 1) * stride)
+        .sum::<usize>();
+
+    let layout = OutputLayoutLaunch::new(
+        handle
+            .shape
+            .iter()
+            .map(|shape| FastDivmodArgs::<usize>::new(client, *shape))
+            .collect(),
+        handle
+            .strides
+            .iter()
+            .map(|stride| ScalarArg::new(*stride))
+            .collect(),
+        ScalarArg::new(handle.size() / line_size),
+        line_size,
+    );
+    let buffer = unsafe {
+        ArrayArg::from_raw_parts_and_size(handle.handle, extent, line_size, handle.elem_size)
     };
-    let mut seeds: Vec<u32> = Vec::with_capacity(4);
-    for _ in 0..4 {
-        seeds.push(rng.random());
+
+    LinearViewLaunch::new::<OutputLayout>(buffer, layout)
+}
+
+/// Maps line indices over the logical shape of the output to offsets in its buffer.
+///
+/// Only the first coordinate of a dimension with a zero stride is in bounds, so broadcast
+/// elements are written once instead of racing between units.
+#[derive(CubeType, CubeLaunch, Clone)]
+pub(crate) struct OutputLayout {
+    shape: Sequence<FastDivmod<usize>>,
+    strides: Sequence<usize>,
+    len: usize,
+    #[cube(comptime)]
+    line_size: LineSize,
+}
+
+#[cube]
+impl OutputLayout {
+    /// Offset of the line in the buffer, and whether it is the first logical index of its
+    /// broadcast elements.
+    fn offset_and_canonical(&self, pos: usize) -> (usize, bool) {
+        let rank = self.shape.len().comptime();
+
+        let mut remainder = pos * self.line_size;
+        let mut offset = 0;
+        let mut canonical = true;
+
+        #[unroll]
+        for i in 0..rank {
+            let dim = rank - i - 1;
+
+            let (rem, coordinate) = self.shape[dim].div_mod(remainder);
+            let stride = self.strides[dim];
+            offset += coordinate * stride;
+            canonical = canonical && (stride != 0 || coordinate == 0);
+            remainder = rem;
+        }
+
+        (offset / self.line_size, canonical)
     }
-    *seed = Some(rng);
+}
+
+#[cube]
+impl Layout for OutputLayout {
+    type Coordinates = Coords1d;
+    type SourceCoordinates = Coords1d;
 
-    seeds.try_into().unwrap()
+    fn to_source_pos(&self, pos: Self::Coordinates) -> usize {
+        let (offset, _) = self.offset_and_canonical(pos);
+        offset
+    }
+
+    fn to_source_pos_checked(&self, pos: Self::Coordinates) -> (usize, bool) {
+        let (offset, canonical) = self.offset_and_canonical(pos);
+        (offset, canonical && pos < self.len)
+    }
+
+    fn shape(&self) -> Self::Coordinates {
+        self.len
+    }
+
+    fn is_in_bounds(&self, pos: Self::Coordinates) -> bool {
+        let (_, canonical) = self.offset_and_canonical(pos);
+        canonical && pos < self.len
+    }
 }
 
 pub(crate) trait PrngArgs: Send + Sync + 'static {
@@ -103,10 +206,10 @@ pub(crate) trait PrngRuntime: Send + Sync + 'static + PrngArgs {
         n_invocations: u32,
         #[comptime] n_values_per_thread: usize,
         #[comptime] line_size: usize,
-        state_0: &mut u32,
-        state_1: &mut u32,
-        state_2: &mut u32,
-        state_3: &mut u32,
+        state_0: &mut Line<u32>,
+        state_1: &mut Line<u32>,
+        state_2: &mut Line<u32>,
+        state_3: &mut Line<u32>,
         output: &mut View<Line<E>, Coords1d, ReadWrite>,
     );
 }
@@ -120,29 +223,40 @@ fn prng_kernel<F: RandomFamily, E: Numeric>(
     seed_1: u32,
     seed_2: u32,
     seed_3: u32,
+    num_units: usize,
     args: Args<F>,
     #[comptime] n_values_per_thread: usize,
     #[comptime] line_size: usize,
     #[define(E)] _dtype: StorageType,
 ) {
-    let cube_offset = CUBE_POS * CUBE_DIM as usize;
-
-    let write_index_base = cube_offset * n_values_per_thread / line_size + UNIT_POS as usize;
+    // Extra units would wrap around to the lines of the first streams
+    if ABSOLUTE_POS >= num_units {
+        terminate!();
+    }
 
-    // Truncating position should be fine here, it's no issue if the seed repeats
-    #[allow(arithmetic_overflow)]
-    let thread_seed = 1000000007u32 * ABSOLUTE_POS as u32;
+    let mut state_0 = Line::empty(line_size);
+    let mut state_1 = Line::empty(line_size);
+    let mut state_2 = Line::empty(line_size);
+    let mut state_3 = Line::empty(line_size);
 
-    let mut state_0 = thread_seed + seed_0;
-    let mut state_1 = thread_seed + seed_1;
-    let mut state_2 = thread_seed + seed_2;
-    let mut state_3 = thread_seed + seed_3;
+    seed_lane_states(
+        &mut state_0,
+        &mut state_1,
+        &mut state_2,
+        &mut state_3,
+        seed_0,
+        seed_1,
+        seed_2,
+        seed_3,
+        line_size,
+    );
 
-    // Creation of n_values_per_thread values, specific to the distribution
+    // Creation of n_values_per_thread values, specific to the distribution. Value `k` of the
+    // streams of this unit goes to line `k * num_units + ABSOLUTE_POS`.
     F::Runtime::inner_loop(
         args,
-        write_index_base,
-        CUBE_DIM,
+        ABSOLUTE_POS,
+        num_units as u32,
         n_values_per_thread,
         line_size,
         &mut state_0,
@@ -153,6 +267,51 @@ fn prng_kernel<F: RandomFamily, E: Numeric>(
     );
 }
 
+/// Seeds the generator state of every lane of the current unit.
+#[cube]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn seed_lane_states(
+    state_0: &mut Line<u32>,
+    state_1: &mut Line<u32>,
+    state_2: &mut Line<u32>,
+    state_3: &mut Line<u32>,
+    seed_0: u32,
+    seed_1: u32,
+    seed_2: u32,
+    seed_3: u32,
+    #[comptime] line_size: usize,
+) {
+    // Every lane gets its own stream, seeded as if it was its own unit
+    #[unroll]
+    for lane in 0..line_size {
+        // Truncating position should be fine here, it's no issue if the seed repeats
+        #[allow(arithmetic_overflow)]
+        let lane_seed = 1000000007u32 * (ABSOLUTE_POS * line_size + lane) as u32;
+
+        state_0[lane] = lane_seed + seed_0;
+        state_1[lane] = lane_seed + seed_1;
+        state_2[lane] = lane_seed + seed_2;
+        state_3[lane] = lane_seed + seed_3;
+    }
+}
+
+/// Advances the generator of a single lane and returns its next random word.
+#[cube]
+pub(crate) fn next_random_word(
+    state_0: &mut Line<u32>,
+    state_1: &mut Line<u32>,
+    state_2: &mut Line<u32>,
+    state_3: &mut Line<u32>,
+    #[comptime] lane: usize,
+) -> u32 {
+    state_0[lane] = taus_step_0(state_0[lane]);
+    state_1[lane] = taus_step_1(state_1[lane]);
+    state_2[lane] = taus_step_2(state_2[lane]);
+    state_3[lane] = lcg_step(state_3[lane]);
+
+    state_0[lane] ^ state_1[lane] ^ state_2[lane] ^ state_3[lane]
+}
+
 #[cube]
 pub(crate) fn taus_step_0(z: u32) -> u32 {
     taus_step(z, 13u32, 19u32, 12u32, 4294967294u32)
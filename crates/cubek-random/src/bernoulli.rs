@@ -2,12 +2,9 @@ use cubecl::prelude::*;
 use cubecl::std::tensor::View;
 use cubecl::{CubeType, Runtime};
 
-use crate::RandomFamily;
+use crate::{PhiloxFamily, PhiloxRuntime, RandomFamily, RandomGenerator, random_philox};
 
-use super::{
-    PrngArgs, PrngRuntime, lcg_step, random, taus_step_0, taus_step_1, taus_step_2,
-    to_unit_interval_closed_open,
-};
+use super::{PrngArgs, PrngRuntime, next_random_word, random, to_unit_interval_closed_open};
 
 #[derive(CubeLaunch, CubeType)]
 pub(crate) struct Bernoulli {
@@ -21,6 +18,10 @@ impl RandomFamily for BernoulliFamily {
     type Runtime = Bernoulli;
 }
 
+impl PhiloxFamily for BernoulliFamily {
+    type Runtime = Bernoulli;
+}
+
 #[cube]
 impl PrngRuntime for Bernoulli {
     fn inner_loop<E: Numeric>(
@@ -29,10 +30,10 @@ impl PrngRuntime for Bernoulli {
         n_invocations: u32,
         #[comptime] n_values_per_thread: usize,
         #[comptime] line_size: LineSize,
-        state_0: &mut u32,
-        state_1: &mut u32,
-        state_2: &mut u32,
-        state_3: &mut u32,
+        state_0: &mut Line<u32>,
+        state_1: &mut Line<u32>,
+        state_2: &mut Line<u32>,
+        state_3: &mut Line<u32>,
         output: &mut View<Line<E>, usize, ReadWrite>,
     ) {
         let prob = args.probability;
@@ -45,22 +46,34 @@ impl PrngRuntime for Bernoulli {
             // vectorization
             #[unroll]
             for i in 0..line_size {
-                *state_0 = taus_step_0(*state_0);
-                *state_1 = taus_step_1(*state_1);
-                *state_2 = taus_step_2(*state_2);
-                *state_3 = lcg_step(*state_3);
-
-                let int_random = *state_0 ^ *state_1 ^ *state_2 ^ *state_3;
+                let int_random = next_random_word(state_0, state_1, state_2, state_3, i);
                 let float_random = to_unit_interval_closed_open(int_random);
                 output_line[i] = E::cast_from(float_random < prob);
             }
             let write_index = line_index * n_invocations as usize + write_index_base;
 
-            output[write_index] = output_line;
+            output.write_checked(write_index, output_line);
         }
     }
 }
 
+#[cube]
+impl PhiloxRuntime for Bernoulli {
+    fn transform_block<E: Numeric>(args: Bernoulli, words: Line<u32>) -> Line<E> {
+        let line_size = words.line_size();
+
+        let mut output_line = Line::empty(line_size);
+
+        #[unroll]
+        for i in 0..line_size {
+            let float_random = to_unit_interval_closed_open(words[i]);
+            output_line[i] = E::cast_from(float_random < args.probability);
+        }
+
+        output_line
+    }
+}
+
 impl PrngArgs for Bernoulli {
     type Args = Self;
 
@@ -72,6 +85,28 @@ impl PrngArgs for Bernoulli {
 /// Pseudo-random generator with bernoulli distribution
 pub fn random_bernoulli<R: Runtime>(
     client: &ComputeClient<R>,
+    generator: &mut RandomGenerator,
+    probability: f32,
+    out: TensorHandleRef<R>,
+    dtype: StorageType,
+) -> Result<(), LaunchError> {
+    assert_eq!(
+        out.elem_size,
+        dtype.size(),
+        "Tensor element type must be the same as type E"
+    );
+
+    random::<BernoulliFamily, R>(client, generator, Bernoulli { probability }, out, dtype)
+}
+
+/// Counter-based pseudo-random generator with bernoulli distribution.
+///
+/// The output only depends on `seed`, `offset` and the element index, so the same pair always
+/// produces the same values. Use a different `offset` for every launch sharing a `seed`.
+pub fn random_bernoulli_philox<R: Runtime>(
+    client: &ComputeClient<R>,
+    seed: u64,
+    offset: u64,
     probability: f32,
     out: TensorHandleRef<R>,
     dtype: StorageType,
@@ -82,5 +117,5 @@ pub fn random_bernoulli<R: Runtime>(
         "Tensor element type must be the same as type E"
     );
 
-    random::<BernoulliFamily, R>(client, Bernoulli { probability }, out, dtype)
+    random_philox::<BernoulliFamily, R>(client, seed, offset, Bernoulli { probability }, out, dtype)
 }
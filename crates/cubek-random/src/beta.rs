@@ -0,0 +1,86 @@
+use cubecl::prelude::*;
+use cubecl::std::tensor::View;
+
+use super::{PrngArgs, PrngRuntime, random};
+
+use crate::{RandomFamily, RandomGenerator, sample_standard_gamma};
+
+#[derive(CubeLaunch, CubeType)]
+pub(crate) struct Beta {
+    alpha: f32,
+    beta: f32,
+}
+
+#[derive(Debug)]
+struct BetaFamily;
+
+impl RandomFamily for BetaFamily {
+    type Runtime = Beta;
+}
+
+#[cube]
+impl PrngRuntime for Beta {
+    fn inner_loop<E: Numeric>(
+        args: Beta,
+        write_index_base: usize,
+        n_invocations: u32,
+        #[comptime] n_values_per_thread: usize,
+        #[comptime] line_size: LineSize,
+        state_0: &mut Line<u32>,
+        state_1: &mut Line<u32>,
+        state_2: &mut Line<u32>,
+        state_3: &mut Line<u32>,
+        output: &mut View<Line<E>, usize, ReadWrite>,
+    ) {
+        let alpha = args.alpha;
+        let beta = args.beta;
+
+        let mut output_line = Line::empty(line_size);
+
+        let num_iterations = n_values_per_thread / line_size;
+        #[unroll(num_iterations <= 8)]
+        for line_index in 0..num_iterations {
+            // vectorization
+            #[unroll]
+            for i in 0..line_size {
+                // X / (X + Y) with X ~ Gamma(alpha) and Y ~ Gamma(beta)
+                let x = sample_standard_gamma(alpha, state_0, state_1, state_2, state_3, i);
+                let y = sample_standard_gamma(beta, state_0, state_1, state_2, state_3, i);
+
+                output_line[i] = E::cast_from(x / (x + y));
+            }
+
+            let write_index = line_index * n_invocations as usize + write_index_base;
+
+            output.write_checked(write_index, output_line);
+        }
+    }
+}
+
+impl PrngArgs for Beta {
+    type Args = Self;
+
+    fn args<'a, R: Runtime>(self) -> BetaLaunch<'a, R> {
+        BetaLaunch::new(ScalarArg::new(self.alpha), ScalarArg::new(self.beta))
+    }
+}
+
+/// Pseudo-random generator with beta distribution, built from two gamma variables.
+pub fn random_beta<R: Runtime>(
+    client: &ComputeClient<R>,
+    generator: &mut RandomGenerator,
+    alpha: f32,
+    beta: f32,
+    out: TensorHandleRef<R>,
+    dtype: StorageType,
+) -> Result<(), LaunchError> {
+    assert_eq!(
+        out.elem_size,
+        dtype.size(),
+        "Tensor element type must be the same as type E"
+    );
+    assert!(alpha > 0.0, "Alpha must be positive, got {alpha}");
+    assert!(beta > 0.0, "Beta must be positive, got {beta}");
+
+    random::<BetaFamily, R>(client, generator, Beta { alpha, beta }, out, dtype)
+}
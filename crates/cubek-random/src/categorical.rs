@@ -0,0 +1,371 @@
+use cubecl::calculate_cube_count_elemwise;
+use cubecl::prelude::*;
+
+use crate::{
+    RandomGenerator, philox4x32_10_block, to_unit_interval_closed_open, to_unit_interval_open,
+};
+
+/// Maximum number of units cooperating on a single row.
+const MAX_UNITS_PER_ROW: usize = 256;
+
+/// How the values of the input of [random_categorical] are interpreted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CategoricalInput {
+    /// Non-negative weights, not necessarily normalized.
+    Probabilities,
+    /// Unnormalized log-probabilities.
+    Logits,
+}
+
+/// Element types of the tensors used by [random_categorical].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CategoricalDtypes {
+    /// Float type of the probabilities or logits.
+    pub input: StorageType,
+    /// Numeric type of the mask, ignored when no mask is given.
+    pub mask: StorageType,
+    /// Integer type of the sampled indices, usually `u32` or `i64`.
+    pub output: StorageType,
+}
+
+/// Samples category indices for every row of a `[batch, vocab]` tensor.
+///
+/// The output has shape `[batch, num_samples]`. Logits are sampled with the Gumbel-max trick,
+/// probabilities with the inverse CDF when sampling with replacement. Without replacement, both
+/// use the Gumbel-top-k trick, which returns distinct indices in the same order as sequential
+/// sampling would.
+///
+/// The optional `mask` has the same shape as `input`, and categories where it is zero are never
+/// sampled, which is how top-k and top-p filtering are applied. A row without any remaining
+/// category, or without enough of them to draw `num_samples` without replacement, gets indices out
+/// of the `[0, vocab)` range.
+///
+/// Each row is handled by a single cube and the whole draw stays on the device, so no host sync is
+/// needed between steps.
+#[allow(clippy::too_many_arguments)]
+pub fn random_categorical<R: Runtime>(
+    client: &ComputeClient<R>,
+    generator: &mut RandomGenerator,
+    input: TensorHandleRef<R>,
+    kind: CategoricalInput,
+    mask: Option<TensorHandleRef<R>>,
+    replacement: bool,
+    output: TensorHandleRef<R>,
+    dtypes: CategoricalDtypes,
+) -> Result<(), LaunchError> {
+    assert_eq!(
+        input.elem_size,
+        dtypes.input.size(),
+        "Input element type must be the same as type F"
+    );
+    assert_eq!(
+        output.elem_size,
+        dtypes.output.size(),
+        "Output element type must be the same as type I"
+    );
+    assert_eq!(input.shape.len(), 2, "Input must have shape [batch, vocab]");
+    assert_eq!(
+        output.shape.len(),
+        2,
+        "Output must have shape [batch, num_samples]"
+    );
+    assert_eq!(
+        input.shape[0], output.shape[0],
+        "Input and output must have the same batch size"
+    );
+    assert!(
+        replacement || output.shape[1] <= input.shape[1],
+        "Cannot draw {} samples out of {} categories without replacement",
+        output.shape[1],
+        input.shape[1]
+    );
+    if let Some(mask) = &mask {
+        assert_eq!(
+            mask.shape, input.shape,
+            "Mask must have the same shape as the input"
+        );
+        assert_eq!(
+            mask.elem_size,
+            dtypes.mask.size(),
+            "Mask element type must be the same as type M"
+        );
+    }
+
+    let seeds = generator.next_seeds();
+
+    let batch = input.shape[0];
+    let cube_size = input.shape[1].next_power_of_two().min(MAX_UNITS_PER_ROW);
+    let cube_dim = CubeDim::new_1d(cube_size as u32);
+    let cube_count = calculate_cube_count_elemwise(client, batch * cube_size, cube_dim);
+
+    // Without a mask, the input is bound in its place and never read.
+    let has_mask = mask.is_some();
+    let (mask, mask_dtype) = match &mask {
+        Some(mask) => (mask.as_tensor_arg(1), dtypes.mask),
+        None => (input.as_tensor_arg(1), dtypes.input),
+    };
+
+    if kind == CategoricalInput::Probabilities && replacement {
+        categorical_inverse_cdf_kernel::launch::<R>(
+            client,
+            cube_count,
+            cube_dim,
+            input.as_tensor_arg(1),
+            mask,
+            output.as_tensor_arg(1),
+            ScalarArg::new(seeds[0]),
+            ScalarArg::new(seeds[1]),
+            ScalarArg::new(seeds[2]),
+            has_mask,
+            cube_size,
+            [dtypes.input, mask_dtype, dtypes.output],
+        )
+    } else {
+        categorical_gumbel_kernel::launch::<R>(
+            client,
+            cube_count,
+            cube_dim,
+            input.as_tensor_arg(1),
+            mask,
+            output.as_tensor_arg(1),
+            ScalarArg::new(seeds[0]),
+            ScalarArg::new(seeds[1]),
+            ScalarArg::new(seeds[2]),
+            kind == CategoricalInput::Probabilities,
+            has_mask,
+            replacement,
+            cube_size,
+            [dtypes.input, mask_dtype, dtypes.output],
+        )
+    }
+}
+
+#[cube(launch)]
+#[allow(clippy::too_many_arguments)]
+fn categorical_gumbel_kernel<F: Float, M: Numeric, I: Int>(
+    input: &Tensor<F>,
+    mask: &Tensor<M>,
+    output: &mut Tensor<I>,
+    key_0: u32,
+    key_1: u32,
+    stream: u32,
+    #[comptime] log_input: bool,
+    #[comptime] has_mask: bool,
+    #[comptime] replacement: bool,
+    #[comptime] cube_size: usize,
+    #[define(F, M, I)] _dtypes: [StorageType; 3],
+) {
+    let row = CUBE_POS;
+    if row >= input.shape(0) {
+        terminate!();
+    }
+
+    let vocab = input.shape(1);
+    let num_samples = output.shape(1);
+    let unit = UNIT_POS as usize;
+
+    let mut shared_keys = SharedMemory::<f32>::new(cube_size);
+    let mut shared_indices = SharedMemory::<u32>::new(cube_size);
+
+    // Last selected (key, index), every following pick must rank strictly below it
+    let mut threshold_key = f32::max_value().runtime();
+    let mut threshold_index = 0u32.runtime();
+
+    for sample in 0..num_samples {
+        // The same noise is reused across samples without replacement, so that the picks are the
+        // top-k of a single perturbation.
+        let noise_sample = if replacement { sample as u32 } else { 0u32 };
+
+        let mut best_key = f32::min_value().runtime();
+        let mut best_index = u32::MAX.runtime();
+
+        let mut col = unit;
+        loop {
+            if col >= vocab {
+                break;
+            }
+
+            let value = f32::cast_from(input[row * input.stride(0) + col * input.stride(1)]);
+            let logit = if log_input { f32::ln(value) } else { value };
+
+            let word =
+                philox4x32_10_block(col as u32, row as u32, noise_sample, stream, key_0, key_1)[0];
+            let gumbel = -f32::ln(-f32::ln(to_unit_interval_open(word)));
+            let key = logit + gumbel;
+            let index = col as u32;
+
+            let mut valid = key > f32::min_value();
+            if has_mask {
+                valid =
+                    valid && mask[row * mask.stride(0) + col * mask.stride(1)] != M::from_int(0);
+            }
+            if !replacement {
+                if sample > 0 {
+                    valid = valid && ranks_above(threshold_key, threshold_index, key, index);
+                }
+            }
+
+            if valid && ranks_above(key, index, best_key, best_index) {
+                best_key = key;
+                best_index = index;
+            }
+
+            col += CUBE_DIM as usize;
+        }
+
+        shared_keys[unit] = best_key;
+        shared_indices[unit] = best_index;
+        sync_cube();
+
+        let mut stride = (cube_size / 2).runtime();
+        loop {
+            if stride == 0 {
+                break;
+            }
+
+            if unit < stride {
+                let other_key = shared_keys[unit + stride];
+                let other_index = shared_indices[unit + stride];
+                if ranks_above(
+                    other_key,
+                    other_index,
+                    shared_keys[unit],
+                    shared_indices[unit],
+                ) {
+                    shared_keys[unit] = other_key;
+                    shared_indices[unit] = other_index;
+                }
+            }
+            sync_cube();
+
+            stride /= 2;
+        }
+
+        threshold_key = shared_keys[0];
+        threshold_index = shared_indices[0];
+
+        if unit == 0 {
+            output[row * output.stride(0) + sample * output.stride(1)] =
+                I::cast_from(threshold_index);
+        }
+        sync_cube();
+    }
+}
+
+#[cube(launch)]
+#[allow(clippy::too_many_arguments)]
+fn categorical_inverse_cdf_kernel<F: Float, M: Numeric, I: Int>(
+    input: &Tensor<F>,
+    mask: &Tensor<M>,
+    output: &mut Tensor<I>,
+    key_0: u32,
+    key_1: u32,
+    stream: u32,
+    #[comptime] has_mask: bool,
+    #[comptime] cube_size: usize,
+    #[define(F, M, I)] _dtypes: [StorageType; 3],
+) {
+    let row = CUBE_POS;
+    if row >= input.shape(0) {
+        terminate!();
+    }
+
+    let vocab = input.shape(1);
+    let num_samples = output.shape(1);
+    let unit = UNIT_POS as usize;
+
+    // Each unit owns a contiguous chunk of the row
+    let chunk_size = vocab.div_ceil(cube_size);
+    let chunk_start = unit * chunk_size;
+    let chunk_end = (chunk_start + chunk_size).min(vocab);
+
+    // Last category with a non-zero probability, offset by one so that zero means none
+    let mut chunk_sum = 0.0f32;
+    let mut chunk_last = 0u32;
+    for col in chunk_start..chunk_end {
+        let probability = masked_probability(input, mask, row, col, has_mask);
+        chunk_sum += probability;
+        if probability > 0.0 {
+            chunk_last = col as u32 + 1;
+        }
+    }
+
+    let mut shared_sums = SharedMemory::<f32>::new(cube_size);
+    let mut shared_last = SharedMemory::<u32>::new(cube_size);
+    shared_sums[unit] = chunk_sum;
+    shared_last[unit] = chunk_last;
+    sync_cube();
+
+    // Every unit accumulates the sums in the same order, so the chunk intervals
+    // [prefix, prefix + chunk_sum) exactly tile [0, total).
+    let mut prefix = 0.0f32;
+    let mut total = 0.0f32;
+    let mut last = 0u32;
+    for i in 0..cube_size {
+        if i == unit {
+            prefix = total;
+        }
+        total += shared_sums[i];
+        last = last.max(shared_last[i]);
+    }
+
+    // Index written when no chunk holds the target, out of range for a fully masked row
+    let mut fallback = u32::MAX.runtime();
+    if last > 0 {
+        fallback = last - 1;
+    }
+
+    for sample in 0..num_samples {
+        let word = philox4x32_10_block(0u32, row as u32, sample as u32, stream, key_0, key_1)[0];
+        let target = to_unit_interval_closed_open(word) * total;
+
+        // The target is past every chunk when the row is fully masked, or when the product is
+        // rounded up to the total.
+        if unit == 0 && target >= total {
+            output[row * output.stride(0) + sample * output.stride(1)] = I::cast_from(fallback);
+        }
+
+        if target >= prefix && target < prefix + chunk_sum {
+            // Rounding may leave the target past the last step of the scan, fall back to the last
+            // category with a non-zero probability.
+            let mut index = u32::MAX.runtime();
+            let mut cumulative = prefix;
+
+            for col in chunk_start..chunk_end {
+                let probability = masked_probability(input, mask, row, col, has_mask);
+                if probability > 0.0 {
+                    index = col as u32;
+                    cumulative += probability;
+                    if target < cumulative {
+                        break;
+                    }
+                }
+            }
+
+            output[row * output.stride(0) + sample * output.stride(1)] = I::cast_from(index);
+        }
+    }
+}
+
+/// Whether `(key_a, index_a)` ranks above `(key_b, index_b)`, ties being broken by lowest index.
+#[cube]
+fn ranks_above(key_a: f32, index_a: u32, key_b: f32, index_b: u32) -> bool {
+    key_a > key_b || (key_a == key_b && index_a < index_b)
+}
+
+#[cube]
+fn masked_probability<F: Float, M: Numeric>(
+    input: &Tensor<F>,
+    mask: &Tensor<M>,
+    row: usize,
+    col: usize,
+    #[comptime] has_mask: bool,
+) -> f32 {
+    let mut probability = f32::cast_from(input[row * input.stride(0) + col * input.stride(1)]);
+    if has_mask {
+        if mask[row * mask.stride(0) + col * mask.stride(1)] == M::from_int(0) {
+            probability = 0.0;
+        }
+    }
+    probability
+}
@@ -0,0 +1,88 @@
+use cubecl::prelude::*;
+use cubecl::std::tensor::View;
+use std::f32::consts::PI;
+
+use super::{PrngArgs, PrngRuntime, random};
+
+use crate::{RandomFamily, RandomGenerator, next_random_word, to_unit_interval_open};
+
+#[derive(CubeLaunch, CubeType)]
+pub(crate) struct Cauchy {
+    loc: f32,
+    scale: f32,
+}
+
+#[derive(Debug)]
+struct CauchyFamily;
+
+impl RandomFamily for CauchyFamily {
+    type Runtime = Cauchy;
+}
+
+#[cube]
+impl PrngRuntime for Cauchy {
+    fn inner_loop<E: Numeric>(
+        args: Cauchy,
+        write_index_base: usize,
+        n_invocations: u32,
+        #[comptime] n_values_per_thread: usize,
+        #[comptime] line_size: LineSize,
+        state_0: &mut Line<u32>,
+        state_1: &mut Line<u32>,
+        state_2: &mut Line<u32>,
+        state_3: &mut Line<u32>,
+        output: &mut View<Line<E>, usize, ReadWrite>,
+    ) {
+        let loc = args.loc;
+        let scale = args.scale;
+
+        let mut output_line = Line::empty(line_size);
+
+        let num_iterations = n_values_per_thread / line_size;
+        #[unroll(num_iterations <= 8)]
+        for line_index in 0..num_iterations {
+            // vectorization
+            #[unroll]
+            for i in 0..line_size {
+                let int_random = next_random_word(state_0, state_1, state_2, state_3, i);
+                let unit = to_unit_interval_open(int_random);
+
+                // Inverse CDF
+                let cauchy = f32::tan(PI * (unit - 0.5)) * scale + loc;
+
+                output_line[i] = E::cast_from(cauchy);
+            }
+
+            let write_index = line_index * n_invocations as usize + write_index_base;
+
+            output.write_checked(write_index, output_line);
+        }
+    }
+}
+
+impl PrngArgs for Cauchy {
+    type Args = Self;
+
+    fn args<'a, R: Runtime>(self) -> CauchyLaunch<'a, R> {
+        CauchyLaunch::new(ScalarArg::new(self.loc), ScalarArg::new(self.scale))
+    }
+}
+
+/// Pseudo-random generator with Cauchy distribution.
+pub fn random_cauchy<R: Runtime>(
+    client: &ComputeClient<R>,
+    generator: &mut RandomGenerator,
+    loc: f32,
+    scale: f32,
+    out: TensorHandleRef<R>,
+    dtype: StorageType,
+) -> Result<(), LaunchError> {
+    assert_eq!(
+        out.elem_size,
+        dtype.size(),
+        "Tensor element type must be the same as type E"
+    );
+    assert!(scale > 0.0, "Scale must be positive, got {scale}");
+
+    random::<CauchyFamily, R>(client, generator, Cauchy { loc, scale }, out, dtype)
+}
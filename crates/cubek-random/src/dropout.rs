@@ -0,0 +1,163 @@
+use cubecl::calculate_cube_count_elemwise;
+use cubecl::prelude::*;
+use cubecl::std::tensor::layout::linear::{LinearView, linear_view};
+
+use crate::{PHILOX_WORDS, RandomGenerator, philox4x32_10_block};
+
+/// Number of elements handled by a unit, one per bit of a mask word.
+const MASK_WORD_BITS: usize = 32;
+
+/// Fused dropout, writing `input * keep / (1 - p)` to `output`, where each element is dropped with
+/// probability `p`.
+///
+/// When `mask_out` is given, the kept elements are also written to it as a packed bitmask of
+/// `ceil(n / 32)` `u32` words, where element `i` is kept if bit `i % 32` of word `i / 32` is set,
+/// and bits past the last element are zero. The mask of an element only depends on the generator
+/// state and its index, so the same state gives the same mask for any input.
+///
+/// `input` and `output` are read and written through the same logical index, so they must have the
+/// same shape and strides.
+#[allow(clippy::too_many_arguments)]
+pub fn dropout<R: Runtime>(
+    client: &ComputeClient<R>,
+    generator: &mut RandomGenerator,
+    input: TensorHandleRef<R>,
+    p: f32,
+    output: TensorHandleRef<R>,
+    mask_out: Option<TensorHandleRef<R>>,
+    dtype: StorageType,
+) -> Result<(), LaunchError> {
+    assert_eq!(
+        input.elem_size,
+        dtype.size(),
+        "Tensor element type must be the same as type F"
+    );
+    assert_eq!(
+        output.elem_size,
+        dtype.size(),
+        "Tensor element type must be the same as type F"
+    );
+    assert_eq!(
+        input.shape, output.shape,
+        "Input and output must have the same shape"
+    );
+    assert_eq!(
+        input.strides, output.strides,
+        "Input and output must have the same strides"
+    );
+    assert!(
+        (0.0..1.0).contains(&p),
+        "Dropout probability must be in [0, 1), got {p}"
+    );
+
+    let num_elems = output.size();
+    let num_words = num_elems.div_ceil(MASK_WORD_BITS);
+
+    if let Some(mask_out) = &mask_out {
+        assert_eq!(
+            mask_out.size(),
+            num_words,
+            "Mask must have one u32 word per 32 elements"
+        );
+        assert_eq!(
+            mask_out.elem_size,
+            size_of::<u32>(),
+            "Mask element type must be u32"
+        );
+    }
+
+    if num_elems == 0 {
+        return Ok(());
+    }
+
+    let seeds = generator.next_seeds();
+
+    // Elements are dropped when their random word falls below `p * 2^32`
+    let threshold = (p as f64 * 4294967296.0) as u32;
+    let scale = 1.0 / (1.0 - p);
+
+    let cube_dim = CubeDim::new(client, num_words);
+    let cube_count = calculate_cube_count_elemwise(client, num_words, cube_dim);
+
+    // Without a mask, a single unused word is bound in its place.
+    let has_mask = mask_out.is_some();
+    let placeholder = client.empty(size_of::<u32>());
+    let placeholder =
+        unsafe { TensorHandleRef::from_raw_parts(&placeholder, &[1], &[1], size_of::<u32>()) };
+    let mask = linear_view(client, mask_out.as_ref().unwrap_or(&placeholder), 1);
+
+    dropout_kernel::launch::<R>(
+        client,
+        cube_count,
+        cube_dim,
+        linear_view(client, &input, 1),
+        linear_view(client, &output, 1),
+        mask,
+        ScalarArg::new(seeds[0]),
+        ScalarArg::new(seeds[1]),
+        ScalarArg::new(seeds[2]),
+        ScalarArg::new(seeds[3]),
+        ScalarArg::new(threshold),
+        ScalarArg::new(scale),
+        has_mask,
+        dtype,
+    )
+}
+
+#[cube(launch)]
+#[allow(clippy::too_many_arguments)]
+fn dropout_kernel<F: Float>(
+    input: &LinearView<Line<F>>,
+    output: &mut LinearView<Line<F>, ReadWrite>,
+    mask: &mut LinearView<Line<u32>, ReadWrite>,
+    key_0: u32,
+    key_1: u32,
+    stream_0: u32,
+    stream_1: u32,
+    threshold: u32,
+    scale: f32,
+    #[comptime] has_mask: bool,
+    #[define(F)] _dtype: StorageType,
+) {
+    let word_index = ABSOLUTE_POS;
+    let base = word_index * MASK_WORD_BITS;
+    if !output.is_in_bounds(base) {
+        terminate!();
+    }
+
+    let scale = F::cast_from(scale);
+    let mut bits = 0u32;
+
+    #[unroll]
+    for block in 0..MASK_WORD_BITS / PHILOX_WORDS {
+        let words = philox4x32_10_block(
+            word_index as u32,
+            block as u32,
+            stream_0,
+            stream_1,
+            key_0,
+            key_1,
+        );
+
+        #[unroll]
+        for i in 0..PHILOX_WORDS {
+            let bit = block * PHILOX_WORDS + i;
+            let index = base + bit;
+            let keep = words[i] >= threshold;
+
+            if output.is_in_bounds(index) {
+                let mut value = F::new(0.0);
+                if keep {
+                    value = input[index][0] * scale;
+                }
+                output[index] = Line::new(value);
+
+                bits |= u32::cast_from(keep) << bit as u32;
+            }
+        }
+    }
+
+    if has_mask {
+        mask[word_index] = Line::new(bits);
+    }
+}
@@ -0,0 +1,82 @@
+use cubecl::prelude::*;
+use cubecl::std::tensor::View;
+
+use super::{PrngArgs, PrngRuntime, random};
+
+use crate::{RandomFamily, RandomGenerator, next_random_word, to_unit_interval_open};
+
+#[derive(CubeLaunch, CubeType)]
+pub(crate) struct Exponential {
+    rate: f32,
+}
+
+#[derive(Debug)]
+struct ExponentialFamily;
+
+impl RandomFamily for ExponentialFamily {
+    type Runtime = Exponential;
+}
+
+#[cube]
+impl PrngRuntime for Exponential {
+    fn inner_loop<E: Numeric>(
+        args: Exponential,
+        write_index_base: usize,
+        n_invocations: u32,
+        #[comptime] n_values_per_thread: usize,
+        #[comptime] line_size: LineSize,
+        state_0: &mut Line<u32>,
+        state_1: &mut Line<u32>,
+        state_2: &mut Line<u32>,
+        state_3: &mut Line<u32>,
+        output: &mut View<Line<E>, usize, ReadWrite>,
+    ) {
+        let rate = args.rate;
+
+        let mut output_line = Line::empty(line_size);
+
+        let num_iterations = n_values_per_thread / line_size;
+        #[unroll(num_iterations <= 8)]
+        for line_index in 0..num_iterations {
+            // vectorization
+            #[unroll]
+            for i in 0..line_size {
+                let int_random = next_random_word(state_0, state_1, state_2, state_3, i);
+                let unit = to_unit_interval_open(int_random);
+
+                // Inverse CDF
+                output_line[i] = E::cast_from(-f32::ln(unit) / rate);
+            }
+
+            let write_index = line_index * n_invocations as usize + write_index_base;
+
+            output.write_checked(write_index, output_line);
+        }
+    }
+}
+
+impl PrngArgs for Exponential {
+    type Args = Self;
+
+    fn args<'a, R: Runtime>(self) -> ExponentialLaunch<'a, R> {
+        ExponentialLaunch::new(ScalarArg::new(self.rate))
+    }
+}
+
+/// Pseudo-random generator with exponential distribution of the given `rate` (inverse scale).
+pub fn random_exponential<R: Runtime>(
+    client: &ComputeClient<R>,
+    generator: &mut RandomGenerator,
+    rate: f32,
+    out: TensorHandleRef<R>,
+    dtype: StorageType,
+) -> Result<(), LaunchError> {
+    assert_eq!(
+        out.elem_size,
+        dtype.size(),
+        "Tensor element type must be the same as type E"
+    );
+    assert!(rate > 0.0, "Rate must be positive, got {rate}");
+
+    random::<ExponentialFamily, R>(client, generator, Exponential { rate }, out, dtype)
+}
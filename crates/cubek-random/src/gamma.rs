@@ -0,0 +1,149 @@
+use cubecl::prelude::*;
+use cubecl::std::tensor::View;
+use std::f32::consts::PI;
+
+use super::{PrngArgs, PrngRuntime, random};
+
+use crate::{RandomFamily, RandomGenerator, next_random_word, to_unit_interval_open};
+
+#[derive(CubeLaunch, CubeType)]
+pub(crate) struct Gamma {
+    shape: f32,
+    scale: f32,
+}
+
+#[derive(Debug)]
+struct GammaFamily;
+
+impl RandomFamily for GammaFamily {
+    type Runtime = Gamma;
+}
+
+#[cube]
+impl PrngRuntime for Gamma {
+    fn inner_loop<E: Numeric>(
+        args: Gamma,
+        write_index_base: usize,
+        n_invocations: u32,
+        #[comptime] n_values_per_thread: usize,
+        #[comptime] line_size: LineSize,
+        state_0: &mut Line<u32>,
+        state_1: &mut Line<u32>,
+        state_2: &mut Line<u32>,
+        state_3: &mut Line<u32>,
+        output: &mut View<Line<E>, usize, ReadWrite>,
+    ) {
+        let shape = args.shape;
+        let scale = args.scale;
+
+        let mut output_line = Line::empty(line_size);
+
+        let num_iterations = n_values_per_thread / line_size;
+        #[unroll(num_iterations <= 8)]
+        for line_index in 0..num_iterations {
+            // vectorization
+            #[unroll]
+            for i in 0..line_size {
+                let gamma = sample_standard_gamma(shape, state_0, state_1, state_2, state_3, i);
+
+                output_line[i] = E::cast_from(gamma * scale);
+            }
+
+            let write_index = line_index * n_invocations as usize + write_index_base;
+
+            output.write_checked(write_index, output_line);
+        }
+    }
+}
+
+/// Samples a gamma variable with unit scale, with the method of Marsaglia and Tsang.
+#[cube]
+pub(crate) fn sample_standard_gamma(
+    shape: f32,
+    state_0: &mut Line<u32>,
+    state_1: &mut Line<u32>,
+    state_2: &mut Line<u32>,
+    state_3: &mut Line<u32>,
+    #[comptime] lane: usize,
+) -> f32 {
+    let mut alpha = shape;
+    let mut boost = 1.0f32;
+
+    // Shapes below 1 are sampled as Gamma(shape + 1) * U^(1 / shape)
+    if shape < 1.0 {
+        let int_random = next_random_word(state_0, state_1, state_2, state_3, lane);
+        let unit = to_unit_interval_open(int_random);
+
+        alpha = shape + 1.0;
+        boost = f32::powf(unit, 1.0 / shape);
+    }
+
+    let d = alpha - 1.0 / 3.0;
+    let c = 1.0 / f32::sqrt(9.0 * d);
+    let mut gamma = 0.0f32;
+
+    loop {
+        let normal = standard_normal(state_0, state_1, state_2, state_3, lane);
+        let v = 1.0 + c * normal;
+
+        if v > 0.0 {
+            let v = v * v * v;
+            let int_random = next_random_word(state_0, state_1, state_2, state_3, lane);
+            let unit = to_unit_interval_open(int_random);
+            let normal_sq = normal * normal;
+
+            // Cheap squeeze first, then the exact acceptance test
+            if unit < 1.0 - 0.0331 * normal_sq * normal_sq
+                || f32::ln(unit) < 0.5 * normal_sq + d * (1.0 - v + f32::ln(v))
+            {
+                gamma = d * v;
+                break;
+            }
+        }
+    }
+
+    gamma * boost
+}
+
+/// Samples a standard normal variable with the Box-Muller transform, discarding the second value.
+#[cube]
+fn standard_normal(
+    state_0: &mut Line<u32>,
+    state_1: &mut Line<u32>,
+    state_2: &mut Line<u32>,
+    state_3: &mut Line<u32>,
+    #[comptime] lane: usize,
+) -> f32 {
+    let unit_0 = to_unit_interval_open(next_random_word(state_0, state_1, state_2, state_3, lane));
+    let unit_1 = to_unit_interval_open(next_random_word(state_0, state_1, state_2, state_3, lane));
+
+    f32::sqrt(-2.0 * f32::ln(unit_0)) * f32::cos(2.0 * PI * unit_1)
+}
+
+impl PrngArgs for Gamma {
+    type Args = Self;
+
+    fn args<'a, R: Runtime>(self) -> GammaLaunch<'a, R> {
+        GammaLaunch::new(ScalarArg::new(self.shape), ScalarArg::new(self.scale))
+    }
+}
+
+/// Pseudo-random generator with gamma distribution of the given `shape` (k) and `scale` (theta).
+pub fn random_gamma<R: Runtime>(
+    client: &ComputeClient<R>,
+    generator: &mut RandomGenerator,
+    shape: f32,
+    scale: f32,
+    out: TensorHandleRef<R>,
+    dtype: StorageType,
+) -> Result<(), LaunchError> {
+    assert_eq!(
+        out.elem_size,
+        dtype.size(),
+        "Tensor element type must be the same as type E"
+    );
+    assert!(shape > 0.0, "Shape must be positive, got {shape}");
+    assert!(scale > 0.0, "Scale must be positive, got {scale}");
+
+    random::<GammaFamily, R>(client, generator, Gamma { shape, scale }, out, dtype)
+}
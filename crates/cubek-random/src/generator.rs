@@ -0,0 +1,77 @@
+use cubecl_common::rand::get_seeded_rng;
+use rand::RngExt;
+use serde::{Deserialize, Serialize};
+
+use crate::philox4x32_10;
+
+/// Domain tags keeping the words used for kernel seeds and forked seeds apart.
+const TAG_LAUNCH: u32 = 0;
+const TAG_FORK: u32 = 1;
+
+/// Random stream owned by the caller and passed to every `random_*` launch.
+///
+/// The generator is a `(seed, offset)` pair: each launch consumes one offset, and the seeds handed
+/// to the kernel are derived from that pair with [Philox4x32-10](philox4x32_10). The state is
+/// plain data, so it can be cloned, compared and checkpointed with serde to resume a stream
+/// exactly.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct RandomGenerator {
+    seed: u64,
+    offset: u64,
+}
+
+impl RandomGenerator {
+    /// Create a generator at the start of the stream for `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self::with_offset(seed, 0)
+    }
+
+    /// Create a generator for `seed`, positioned at `offset`.
+    pub fn with_offset(seed: u64, offset: u64) -> Self {
+        Self { seed, offset }
+    }
+
+    /// Create a generator from a non-deterministic seed.
+    pub fn from_entropy() -> Self {
+        Self::new(get_seeded_rng().random())
+    }
+
+    /// The seed of the stream.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// The position in the stream, incremented by every launch and fork.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Create an independent generator, advancing this one.
+    ///
+    /// The child seed is derived from the current state, so forking is deterministic.
+    pub fn fork(&mut self) -> Self {
+        let [word_0, word_1, ..] = self.next_block(TAG_FORK);
+
+        Self::new(((word_1 as u64) << 32) | word_0 as u64)
+    }
+
+    /// Create `count` independent generators, advancing this one.
+    pub fn split(&mut self, count: usize) -> Vec<Self> {
+        (0..count).map(|_| self.fork()).collect()
+    }
+
+    /// Seeds for the next kernel launch, advancing the generator like a launch would.
+    ///
+    /// Mostly useful with the [host reference](crate::reference_random_words) of the generator.
+    pub fn next_seeds(&mut self) -> [u32; 4] {
+        self.next_block(TAG_LAUNCH)
+    }
+
+    fn next_block(&mut self, tag: u32) -> [u32; 4] {
+        let counter = [self.offset as u32, (self.offset >> 32) as u32, tag, 0];
+        let key = [self.seed as u32, (self.seed >> 32) as u32];
+        self.offset = self.offset.wrapping_add(1);
+
+        philox4x32_10(counter, key)
+    }
+}
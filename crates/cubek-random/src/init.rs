@@ -0,0 +1,107 @@
+use cubecl::prelude::*;
+
+use crate::{RandomGenerator, random_normal, random_uniform};
+
+/// Non-linearity following an initialized layer, used to compute the recommended gain.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Nonlinearity {
+    /// Identity, also used for convolutions.
+    Linear,
+    Sigmoid,
+    Tanh,
+    Relu,
+    /// Leaky ReLU with the given negative slope.
+    LeakyRelu(f32),
+    Selu,
+}
+
+impl Nonlinearity {
+    /// The recommended gain, following the values used by PyTorch's `calculate_gain`.
+    pub fn gain(&self) -> f32 {
+        match self {
+            Nonlinearity::Linear | Nonlinearity::Sigmoid => 1.0,
+            Nonlinearity::Tanh => 5.0 / 3.0,
+            Nonlinearity::Relu => f32::sqrt(2.0),
+            Nonlinearity::LeakyRelu(slope) => f32::sqrt(2.0 / (1.0 + slope * slope)),
+            Nonlinearity::Selu => 3.0 / 4.0,
+        }
+    }
+}
+
+/// Computes `(fan_in, fan_out)` of a weight with shape `[out_features, in_features, ...kernel]`.
+pub fn calculate_fans(shape: &[usize]) -> (usize, usize) {
+    assert!(
+        shape.len() >= 2,
+        "Fans can only be computed for tensors with at least 2 dimensions"
+    );
+
+    let receptive_field: usize = shape[2..].iter().product();
+
+    (shape[1] * receptive_field, shape[0] * receptive_field)
+}
+
+/// Kaiming (He) initialization with uniform distribution in `[-bound, bound]`,
+/// where `bound = gain * sqrt(3 / fan)`.
+///
+/// Use `fan_in` to preserve the variance in the forward pass, or `fan_out` for the backward pass.
+pub fn kaiming_uniform<R: Runtime>(
+    client: &ComputeClient<R>,
+    generator: &mut RandomGenerator,
+    fan: usize,
+    nonlinearity: Nonlinearity,
+    out: TensorHandleRef<R>,
+    dtype: StorageType,
+) -> Result<(), LaunchError> {
+    let bound = nonlinearity.gain() * f32::sqrt(3.0 / fan as f32);
+
+    random_uniform(client, generator, -bound, bound, out, dtype)
+}
+
+/// Kaiming (He) initialization with normal distribution `N(0, std^2)`,
+/// where `std = gain / sqrt(fan)`.
+///
+/// Use `fan_in` to preserve the variance in the forward pass, or `fan_out` for the backward pass.
+pub fn kaiming_normal<R: Runtime>(
+    client: &ComputeClient<R>,
+    generator: &mut RandomGenerator,
+    fan: usize,
+    nonlinearity: Nonlinearity,
+    out: TensorHandleRef<R>,
+    dtype: StorageType,
+) -> Result<(), LaunchError> {
+    let std = nonlinearity.gain() / f32::sqrt(fan as f32);
+
+    random_normal(client, generator, 0.0, std, out, dtype)
+}
+
+/// Xavier (Glorot) initialization with uniform distribution in `[-bound, bound]`,
+/// where `bound = gain * sqrt(6 / (fan_in + fan_out))`.
+pub fn xavier_uniform<R: Runtime>(
+    client: &ComputeClient<R>,
+    generator: &mut RandomGenerator,
+    fan_in: usize,
+    fan_out: usize,
+    gain: f32,
+    out: TensorHandleRef<R>,
+    dtype: StorageType,
+) -> Result<(), LaunchError> {
+    let bound = gain * f32::sqrt(6.0 / (fan_in + fan_out) as f32);
+
+    random_uniform(client, generator, -bound, bound, out, dtype)
+}
+
+/// Xavier (Glorot) initialization with normal distribution `N(0, std^2)`,
+/// where `std = gain * sqrt(2 / (fan_in + fan_out))`.
+pub fn xavier_normal<R: Runtime>(
+    client: &ComputeClient<R>,
+    generator: &mut RandomGenerator,
+    fan_in: usize,
+    fan_out: usize,
+    gain: f32,
+    out: TensorHandleRef<R>,
+    dtype: StorageType,
+) -> Result<(), LaunchError> {
+    let std = gain * f32::sqrt(2.0 / (fan_in + fan_out) as f32);
+
+    random_normal(client, generator, 0.0, std, out, dtype)
+}
@@ -0,0 +1,287 @@
+use cubecl::prelude::*;
+use cubecl::std::tensor::View;
+
+use super::{PrngArgs, PrngRuntime, random};
+
+use crate::{RandomFamily, RandomGenerator, next_random_word};
+
+/// Uniform integers in `[low, low + range)`, with every 64-bit quantity split in `(lo, hi)` words.
+///
+/// A range of zero stands for the full `2^32` or `2^64` range of the generated words.
+#[derive(CubeLaunch, CubeType)]
+pub(crate) struct UniformInt {
+    range_lo: u32,
+    range_hi: u32,
+    threshold_lo: u32,
+    threshold_hi: u32,
+    low_lo: u32,
+    low_hi: u32,
+    #[cube(comptime)]
+    wide: bool,
+    #[cube(comptime)]
+    signed: bool,
+}
+
+#[derive(Debug)]
+struct UniformIntFamily;
+
+impl RandomFamily for UniformIntFamily {
+    type Runtime = UniformInt;
+}
+
+#[cube]
+impl PrngRuntime for UniformInt {
+    fn inner_loop<E: Numeric>(
+        args: UniformInt,
+        write_index_base: usize,
+        n_invocations: u32,
+        #[comptime] n_values_per_thread: usize,
+        #[comptime] line_size: LineSize,
+        state_0: &mut Line<u32>,
+        state_1: &mut Line<u32>,
+        state_2: &mut Line<u32>,
+        state_3: &mut Line<u32>,
+        output: &mut View<Line<E>, usize, ReadWrite>,
+    ) {
+        let mut output_line = Line::empty(line_size);
+
+        let num_iterations = n_values_per_thread / line_size;
+        #[unroll(num_iterations <= 8)]
+        for line_index in 0..num_iterations {
+            // vectorization
+            #[unroll]
+            for i in 0..line_size {
+                if args.wide {
+                    let (offset_lo, offset_hi) = lemire_64(
+                        args.range_lo,
+                        args.range_hi,
+                        args.threshold_lo,
+                        args.threshold_hi,
+                        state_0,
+                        state_1,
+                        state_2,
+                        state_3,
+                        i,
+                    );
+
+                    // 64-bit addition of the offset to the lower bound, wrapping like two's
+                    // complement so negative bounds work
+                    let value_lo = args.low_lo + offset_lo;
+                    let carry = u32::cast_from(value_lo < offset_lo);
+                    let value_hi = args.low_hi + offset_hi + carry;
+
+                    let word = E::cast_from(65536u32);
+                    output_line[i] = E::cast_from(value_hi) * word * word + E::cast_from(value_lo);
+                } else {
+                    let offset = lemire_32(
+                        args.range_lo,
+                        args.threshold_lo,
+                        state_0,
+                        state_1,
+                        state_2,
+                        state_3,
+                        i,
+                    );
+                    let value = args.low_lo + offset;
+
+                    if args.signed {
+                        output_line[i] = E::cast_from(i32::cast_from(value));
+                    } else {
+                        output_line[i] = E::cast_from(value);
+                    }
+                }
+            }
+
+            let write_index = line_index * n_invocations as usize + write_index_base;
+
+            output.write_checked(write_index, output_line);
+        }
+    }
+}
+
+/// Unbiased integer in `[0, range)` from 32-bit words, with Lemire's multiply-and-reject method.
+///
+/// `threshold` is `2^32 mod range`: products whose low word falls below it are rejected.
+#[cube]
+#[allow(clippy::too_many_arguments)]
+fn lemire_32(
+    range: u32,
+    threshold: u32,
+    state_0: &mut Line<u32>,
+    state_1: &mut Line<u32>,
+    state_2: &mut Line<u32>,
+    state_3: &mut Line<u32>,
+    #[comptime] lane: usize,
+) -> u32 {
+    let mut result = 0u32;
+
+    loop {
+        let word = next_random_word(state_0, state_1, state_2, state_3, lane);
+
+        if range == 0 {
+            result = word;
+            break;
+        }
+
+        if word * range >= threshold {
+            result = word.mul_hi(range);
+            break;
+        }
+    }
+
+    result
+}
+
+/// Unbiased integer in `[0, range)` from 64-bit words, with Lemire's multiply-and-reject method.
+///
+/// `threshold` is `2^64 mod range`. The 128-bit product is computed on 32-bit words, so no 64-bit
+/// integer support is needed to generate the values.
+#[cube]
+#[allow(clippy::too_many_arguments)]
+fn lemire_64(
+    range_lo: u32,
+    range_hi: u32,
+    threshold_lo: u32,
+    threshold_hi: u32,
+    state_0: &mut Line<u32>,
+    state_1: &mut Line<u32>,
+    state_2: &mut Line<u32>,
+    state_3: &mut Line<u32>,
+    #[comptime] lane: usize,
+) -> (u32, u32) {
+    let mut result_lo = 0u32;
+    let mut result_hi = 0u32;
+
+    loop {
+        let word_lo = next_random_word(state_0, state_1, state_2, state_3, lane);
+        let word_hi = next_random_word(state_0, state_1, state_2, state_3, lane);
+
+        if range_lo == 0 && range_hi == 0 {
+            result_lo = word_lo;
+            result_hi = word_hi;
+            break;
+        }
+
+        let (product_0, product_1, product_2, product_3) =
+            mul_wide(word_lo, word_hi, range_lo, range_hi);
+
+        if product_1 > threshold_hi || (product_1 == threshold_hi && product_0 >= threshold_lo) {
+            result_lo = product_2;
+            result_hi = product_3;
+            break;
+        }
+    }
+
+    (result_lo, result_hi)
+}
+
+/// Full 128-bit product of two 64-bit integers, as four words from least to most significant.
+#[cube]
+fn mul_wide(a_lo: u32, a_hi: u32, b_lo: u32, b_hi: u32) -> (u32, u32, u32, u32) {
+    let ll_lo = a_lo * b_lo;
+    let ll_hi = a_lo.mul_hi(b_lo);
+    let lh_lo = a_lo * b_hi;
+    let lh_hi = a_lo.mul_hi(b_hi);
+    let hl_lo = a_hi * b_lo;
+    let hl_hi = a_hi.mul_hi(b_lo);
+    let hh_lo = a_hi * b_hi;
+    let hh_hi = a_hi.mul_hi(b_hi);
+
+    let sum_1 = ll_hi + lh_lo;
+    let word_1 = sum_1 + hl_lo;
+    let carry_1 = u32::cast_from(sum_1 < ll_hi) + u32::cast_from(word_1 < sum_1);
+
+    let sum_2 = lh_hi + hl_hi;
+    let sum_3 = sum_2 + hh_lo;
+    let word_2 = sum_3 + carry_1;
+    let carry_2 = u32::cast_from(sum_2 < lh_hi)
+        + u32::cast_from(sum_3 < sum_2)
+        + u32::cast_from(word_2 < sum_3);
+
+    let word_3 = hh_hi + carry_2;
+
+    (ll_lo, word_1, word_2, word_3)
+}
+
+impl PrngArgs for UniformInt {
+    type Args = Self;
+
+    fn args<'a, R: Runtime>(self) -> UniformIntLaunch<'a, R> {
+        UniformIntLaunch::new(
+            ScalarArg::new(self.range_lo),
+            ScalarArg::new(self.range_hi),
+            ScalarArg::new(self.threshold_lo),
+            ScalarArg::new(self.threshold_hi),
+            ScalarArg::new(self.low_lo),
+            ScalarArg::new(self.low_hi),
+            self.wide,
+            self.signed,
+        )
+    }
+}
+
+/// Pseudo-random generator with discrete uniform distribution in `[low, high)`.
+///
+/// Works for every signed and unsigned integer type up to 64 bits, and the bounds are `i128` so
+/// that the whole range of `u64` and `i64` can be expressed. Values are unbiased, using Lemire's
+/// method on the raw generator words instead of scaling a float.
+pub fn random_int<R: Runtime>(
+    client: &ComputeClient<R>,
+    generator: &mut RandomGenerator,
+    low: i128,
+    high: i128,
+    out: TensorHandleRef<R>,
+    dtype: StorageType,
+) -> Result<(), LaunchError> {
+    assert_eq!(
+        out.elem_size,
+        dtype.size(),
+        "Tensor element type must be the same as type E"
+    );
+    assert!(
+        dtype.is_int() && dtype.size_bits() <= 64,
+        "Integers can only be generated for integer types up to 64 bits, got {dtype:?}"
+    );
+
+    let bits = dtype.size_bits() as u32;
+    let signed = dtype.is_signed_int();
+    let (min, max) = match signed {
+        true => (-(1i128 << (bits - 1)), (1i128 << (bits - 1)) - 1),
+        false => (0, (1i128 << bits) - 1),
+    };
+    assert!(
+        low < high,
+        "Integer interval must not be empty, got [{low}, {high})"
+    );
+    assert!(
+        low >= min && high - 1 <= max,
+        "Integer interval [{low}, {high}) does not fit in {dtype:?}"
+    );
+
+    // Ranges are computed modulo the width of the generated words, a full range wrapping to zero
+    let wide = bits > 32;
+    let word_bits = if wide { 64 } else { 32 };
+    let range = ((high - low) as u128 % (1u128 << word_bits)) as u64;
+    let threshold = match range {
+        0 => 0,
+        range => ((1u128 << word_bits) % range as u128) as u64,
+    };
+    let low = low as u64;
+
+    random::<UniformIntFamily, R>(
+        client,
+        generator,
+        UniformInt {
+            range_lo: range as u32,
+            range_hi: (range >> 32) as u32,
+            threshold_lo: threshold as u32,
+            threshold_hi: (threshold >> 32) as u32,
+            low_lo: low as u32,
+            low_hi: (low >> 32) as u32,
+            wide,
+            signed,
+        },
+        out,
+        dtype,
+    )
+}
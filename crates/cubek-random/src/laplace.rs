@@ -0,0 +1,90 @@
+use cubecl::prelude::*;
+use cubecl::std::tensor::View;
+
+use super::{PrngArgs, PrngRuntime, random};
+
+use crate::{RandomFamily, RandomGenerator, next_random_word, to_unit_interval_open};
+
+#[derive(CubeLaunch, CubeType)]
+pub(crate) struct Laplace {
+    loc: f32,
+    scale: f32,
+}
+
+#[derive(Debug)]
+struct LaplaceFamily;
+
+impl RandomFamily for LaplaceFamily {
+    type Runtime = Laplace;
+}
+
+#[cube]
+impl PrngRuntime for Laplace {
+    fn inner_loop<E: Numeric>(
+        args: Laplace,
+        write_index_base: usize,
+        n_invocations: u32,
+        #[comptime] n_values_per_thread: usize,
+        #[comptime] line_size: LineSize,
+        state_0: &mut Line<u32>,
+        state_1: &mut Line<u32>,
+        state_2: &mut Line<u32>,
+        state_3: &mut Line<u32>,
+        output: &mut View<Line<E>, usize, ReadWrite>,
+    ) {
+        let loc = args.loc;
+        let scale = args.scale;
+
+        let mut output_line = Line::empty(line_size);
+
+        let num_iterations = n_values_per_thread / line_size;
+        #[unroll(num_iterations <= 8)]
+        for line_index in 0..num_iterations {
+            // vectorization
+            #[unroll]
+            for i in 0..line_size {
+                let int_random = next_random_word(state_0, state_1, state_2, state_3, i);
+                let centered = to_unit_interval_open(int_random) - 0.5;
+
+                // Inverse CDF, mirrored around the location
+                let mut laplace = loc - scale * f32::ln(1.0 - 2.0 * centered);
+                if centered < 0.0 {
+                    laplace = loc + scale * f32::ln(1.0 + 2.0 * centered);
+                }
+
+                output_line[i] = E::cast_from(laplace);
+            }
+
+            let write_index = line_index * n_invocations as usize + write_index_base;
+
+            output.write_checked(write_index, output_line);
+        }
+    }
+}
+
+impl PrngArgs for Laplace {
+    type Args = Self;
+
+    fn args<'a, R: Runtime>(self) -> LaplaceLaunch<'a, R> {
+        LaplaceLaunch::new(ScalarArg::new(self.loc), ScalarArg::new(self.scale))
+    }
+}
+
+/// Pseudo-random generator with Laplace (double exponential) distribution.
+pub fn random_laplace<R: Runtime>(
+    client: &ComputeClient<R>,
+    generator: &mut RandomGenerator,
+    loc: f32,
+    scale: f32,
+    out: TensorHandleRef<R>,
+    dtype: StorageType,
+) -> Result<(), LaunchError> {
+    assert_eq!(
+        out.elem_size,
+        dtype.size(),
+        "Tensor element type must be the same as type E"
+    );
+    assert!(scale > 0.0, "Scale must be positive, got {scale}");
+
+    random::<LaplaceFamily, R>(client, generator, Laplace { loc, scale }, out, dtype)
+}
@@ -1,11 +1,45 @@
 mod base;
 mod bernoulli;
+mod beta;
+mod categorical;
+mod cauchy;
+mod dropout;
+mod exponential;
+mod gamma;
+mod generator;
+mod init;
+mod int;
+mod laplace;
 mod normal;
+mod permutation;
+mod philox;
+mod poisson;
+mod quasi_random;
+mod reference;
+mod stochastic_round;
 mod tests_utils;
+mod truncated_normal;
 mod uniform;
 
 pub use base::*;
 pub use bernoulli::*;
+pub use beta::*;
+pub use categorical::*;
+pub use cauchy::*;
+pub use dropout::*;
+pub use exponential::*;
+pub use gamma::*;
+pub use generator::*;
+pub use init::*;
+pub use int::*;
+pub use laplace::*;
 pub use normal::*;
+pub use permutation::*;
+pub use philox::*;
+pub use poisson::*;
+pub use quasi_random::*;
+pub use reference::*;
+pub use stochastic_round::*;
 pub use tests_utils::*;
+pub use truncated_normal::*;
 pub use uniform::*;
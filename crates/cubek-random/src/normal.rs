@@ -4,7 +4,10 @@ use std::f32::consts::PI;
 
 use super::{PrngArgs, PrngRuntime, random};
 
-use crate::{RandomFamily, lcg_step, taus_step_0, taus_step_1, taus_step_2, to_unit_interval_open};
+use crate::{
+    PhiloxFamily, PhiloxRuntime, RandomFamily, RandomGenerator, next_random_word, random_philox,
+    to_unit_interval_open,
+};
 
 #[derive(CubeLaunch, CubeType)]
 pub(crate) struct Normal {
@@ -19,6 +22,10 @@ impl RandomFamily for NormalFamily {
     type Runtime = Normal;
 }
 
+impl PhiloxFamily for NormalFamily {
+    type Runtime = Normal;
+}
+
 #[cube]
 impl PrngRuntime for Normal {
     fn inner_loop<E: Numeric>(
@@ -27,10 +34,10 @@ impl PrngRuntime for Normal {
         n_invocations: u32,
         #[comptime] n_values_per_thread: usize,
         #[comptime] line_size: LineSize,
-        state_0: &mut u32,
-        state_1: &mut u32,
-        state_2: &mut u32,
-        state_3: &mut u32,
+        state_0: &mut Line<u32>,
+        state_1: &mut Line<u32>,
+        state_2: &mut Line<u32>,
+        state_3: &mut Line<u32>,
         output: &mut View<Line<E>, usize, ReadWrite>,
     ) {
         let mean = f32::cast_from(args.mean);
@@ -46,30 +53,14 @@ impl PrngRuntime for Normal {
             #[unroll]
             for i in 0..line_size {
                 // First random uniform integer
-                *state_0 = taus_step_0(*state_0);
-                *state_1 = taus_step_1(*state_1);
-                *state_2 = taus_step_2(*state_2);
-                *state_3 = lcg_step(*state_3);
-
-                let int_random = *state_0 ^ *state_1 ^ *state_2 ^ *state_3;
+                let int_random = next_random_word(state_0, state_1, state_2, state_3, i);
                 let unit_0 = to_unit_interval_open(int_random);
 
                 // Second random uniform integer
-                *state_0 = taus_step_0(*state_0);
-                *state_1 = taus_step_1(*state_1);
-                *state_2 = taus_step_2(*state_2);
-                *state_3 = lcg_step(*state_3);
-
-                let int_random = *state_0 ^ *state_1 ^ *state_2 ^ *state_3;
+                let int_random = next_random_word(state_0, state_1, state_2, state_3, i);
                 let unit_1 = to_unit_interval_open(int_random);
 
-                // Box-Muller transform
-                let coeff = unit_0.ln() * -2.0;
-                let coeff = coeff.sqrt() * std;
-                let trigo_arg = 2.0 * PI * unit_1;
-
-                let normal_0 = f32::cos(trigo_arg) * coeff + mean;
-                let normal_1 = f32::sin(trigo_arg) * coeff + mean;
+                let (normal_0, normal_1) = box_muller(unit_0, unit_1, mean, std);
 
                 output_line_0[i] = E::cast_from(normal_0);
                 output_line_1[i] = E::cast_from(normal_1);
@@ -79,12 +70,49 @@ impl PrngRuntime for Normal {
             let write_index_0 = write_index_base + iteration_offset;
             let write_index_1 = write_index_0 + n_invocations as usize;
 
-            output[write_index_0] = output_line_0;
-            output[write_index_1] = output_line_1;
+            output.write_checked(write_index_0, output_line_0);
+            output.write_checked(write_index_1, output_line_1);
         }
     }
 }
 
+#[cube]
+impl PhiloxRuntime for Normal {
+    fn transform_block<E: Numeric>(args: Normal, words: Line<u32>) -> Line<E> {
+        let mean = f32::cast_from(args.mean);
+        let std = f32::cast_from(args.std);
+        let line_size = words.line_size();
+
+        let mut output_line = Line::empty(line_size);
+
+        #[unroll]
+        for i in 0..line_size / 2 {
+            let unit_0 = to_unit_interval_open(words[2 * i]);
+            let unit_1 = to_unit_interval_open(words[2 * i + 1]);
+
+            let (normal_0, normal_1) = box_muller(unit_0, unit_1, mean, std);
+
+            output_line[2 * i] = E::cast_from(normal_0);
+            output_line[2 * i + 1] = E::cast_from(normal_1);
+        }
+
+        output_line
+    }
+}
+
+/// Box-Muller transform, mapping two uniform values in `(0, 1)` to two independent normal values.
+#[cube]
+fn box_muller(unit_0: f32, unit_1: f32, mean: f32, std: f32) -> (f32, f32) {
+    let coeff = unit_0.ln() * -2.0;
+    let coeff = coeff.sqrt() * std;
+    let trigo_arg = 2.0 * PI * unit_1;
+
+    let normal_0 = f32::cos(trigo_arg) * coeff + mean;
+    let normal_1 = f32::sin(trigo_arg) * coeff + mean;
+
+    (normal_0, normal_1)
+}
+
 impl PrngArgs for Normal {
     type Args = Self;
 
@@ -96,6 +124,30 @@ impl PrngArgs for Normal {
 /// Pseudo-random generator with uniform distribution
 pub fn random_normal<R: Runtime>(
     client: &ComputeClient<R>,
+    generator: &mut RandomGenerator,
+    mean: f32,
+    std: f32,
+    out: TensorHandleRef<R>,
+    dtype: StorageType,
+) -> Result<(), LaunchError> {
+    assert_eq!(
+        out.elem_size,
+        dtype.size(),
+        "Tensor element type must be the same as type E"
+    );
+
+    random::<NormalFamily, R>(client, generator, Normal { mean, std }, out, dtype)
+}
+
+/// Counter-based pseudo-random generator with normal distribution.
+///
+/// The output only depends on `seed`, `offset` and the element index, so the same pair always
+/// produces the same values. Use a different `offset` for every launch sharing a `seed`.
+#[allow(clippy::too_many_arguments)]
+pub fn random_normal_philox<R: Runtime>(
+    client: &ComputeClient<R>,
+    seed: u64,
+    offset: u64,
     mean: f32,
     std: f32,
     out: TensorHandleRef<R>,
@@ -107,5 +159,5 @@ pub fn random_normal<R: Runtime>(
         "Tensor element type must be the same as type E"
     );
 
-    random::<NormalFamily, R>(client, Normal { mean, std }, out, dtype)
+    random_philox::<NormalFamily, R>(client, seed, offset, Normal { mean, std }, out, dtype)
 }
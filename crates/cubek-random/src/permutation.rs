@@ -0,0 +1,293 @@
+use cubecl::calculate_cube_count_elemwise;
+use cubecl::ir::ElemType;
+use cubecl::prelude::*;
+use cubecl::server::Handle;
+use cubecl::std::tensor::layout::linear::{LinearView, linear_view};
+
+use crate::{RandomGenerator, philox4x32_10_block};
+
+/// Random permutation of `[0, n)`, where `n` is the number of elements of `out`, whose integer type
+/// must hold `n - 1`.
+///
+/// Every index gets a 64-bit random sort key derived from the generator with Philox, and the
+/// indices are ordered by key with a bitonic sort, so the permutation is reproducible from the
+/// generator state and never leaves the device. The sort takes `O(n log² n)` work spread over
+/// `O(log² n)` launches.
+pub fn random_permutation<R: Runtime>(
+    client: &ComputeClient<R>,
+    generator: &mut RandomGenerator,
+    out: TensorHandleRef<R>,
+    dtype: StorageType,
+) -> Result<(), LaunchError> {
+    assert_eq!(
+        out.elem_size,
+        dtype.size(),
+        "Tensor element type must be the same as type I"
+    );
+    assert!(
+        matches!(dtype.elem_type(), ElemType::Int(_) | ElemType::UInt(_)),
+        "Permutations can only be written to integer types, got {dtype:?}"
+    );
+
+    let num_elems = out.size();
+    if num_elems == 0 {
+        return Ok(());
+    }
+
+    let bits = dtype.size_bits();
+    let max_index = match dtype.is_signed_int() {
+        true => (1u128 << (bits - 1)) - 1,
+        false => (1u128 << bits) - 1,
+    };
+    assert!(
+        (num_elems - 1) as u128 <= max_index,
+        "Indices of a permutation of {num_elems} elements do not fit in {dtype:?}"
+    );
+
+    let indices = sorted_indices(client, generator, num_elems)?;
+
+    let cube_dim = CubeDim::new(client, num_elems);
+    let cube_count = calculate_cube_count_elemwise(client, num_elems, cube_dim);
+
+    write_permutation_kernel::launch::<R>(
+        client,
+        cube_count,
+        cube_dim,
+        unsafe { ArrayArg::from_raw_parts::<u32>(&indices, num_elems, 1) },
+        linear_view(client, &out, 1),
+        dtype,
+    )
+}
+
+/// Shuffles `input` along `axis` into `output`.
+///
+/// A single [random permutation](random_permutation) of the axis is applied to every slice, so
+/// `output[.., j, ..] = input[.., perm[j], ..]`.
+pub fn shuffle_along_axis<R: Runtime>(
+    client: &ComputeClient<R>,
+    generator: &mut RandomGenerator,
+    input: TensorHandleRef<R>,
+    axis: usize,
+    output: TensorHandleRef<R>,
+    dtype: StorageType,
+) -> Result<(), LaunchError> {
+    assert_eq!(
+        input.elem_size,
+        dtype.size(),
+        "Input element type must be the same as type E"
+    );
+    assert_eq!(
+        output.elem_size,
+        dtype.size(),
+        "Output element type must be the same as type E"
+    );
+    assert_eq!(
+        input.shape, output.shape,
+        "Input and output must have the same shape"
+    );
+    assert!(
+        axis < input.shape.len(),
+        "Axis {axis} out of bounds for a tensor of rank {}",
+        input.shape.len()
+    );
+
+    let num_elems = output.size();
+    if num_elems == 0 {
+        return Ok(());
+    }
+
+    let axis_len = input.shape[axis];
+    let indices = sorted_indices(client, generator, axis_len)?;
+
+    let cube_dim = CubeDim::new(client, num_elems);
+    let cube_count = calculate_cube_count_elemwise(client, num_elems, cube_dim);
+
+    shuffle_kernel::launch::<R>(
+        client,
+        cube_count,
+        cube_dim,
+        input.as_tensor_arg(1),
+        unsafe { ArrayArg::from_raw_parts::<u32>(&indices, axis_len, 1) },
+        output.as_tensor_arg(1),
+        axis,
+        dtype,
+    )
+}
+
+/// Sorts `[0, n)` by random key into a new buffer, padded to the next power of two.
+///
+/// The keys are a pure function of the index, so they are recomputed at every comparison instead
+/// of being stored next to the indices. Padding indices get the largest key and end up last.
+fn sorted_indices<R: Runtime>(
+    client: &ComputeClient<R>,
+    generator: &mut RandomGenerator,
+    num_elems: usize,
+) -> Result<Handle, LaunchError> {
+    assert!(
+        num_elems <= 1 << 31,
+        "Permutations are limited to 2^31 elements, got {num_elems}"
+    );
+
+    let seeds = generator.next_seeds();
+
+    let padded = num_elems.next_power_of_two();
+    let indices = client.empty(padded * size_of::<u32>());
+
+    let cube_dim = CubeDim::new(client, padded);
+    let cube_count = calculate_cube_count_elemwise(client, padded, cube_dim);
+
+    iota_kernel::launch::<R>(client, cube_count.clone(), cube_dim, unsafe {
+        ArrayArg::from_raw_parts::<u32>(&indices, padded, 1)
+    })?;
+
+    let mut block = 2;
+    while block <= padded {
+        let mut stride = block / 2;
+        while stride > 0 {
+            bitonic_step_kernel::launch::<R>(
+                client,
+                cube_count.clone(),
+                cube_dim,
+                unsafe { ArrayArg::from_raw_parts::<u32>(&indices, padded, 1) },
+                ScalarArg::new(num_elems as u32),
+                ScalarArg::new(block as u32),
+                ScalarArg::new(stride as u32),
+                ScalarArg::new(seeds[0]),
+                ScalarArg::new(seeds[1]),
+                ScalarArg::new(seeds[2]),
+                ScalarArg::new(seeds[3]),
+            )?;
+            stride /= 2;
+        }
+        block *= 2;
+    }
+
+    Ok(indices)
+}
+
+#[cube(launch)]
+fn iota_kernel(indices: &mut Array<u32>) {
+    if ABSOLUTE_POS < indices.len() {
+        indices[ABSOLUTE_POS] = ABSOLUTE_POS as u32;
+    }
+}
+
+#[cube(launch)]
+#[allow(clippy::too_many_arguments)]
+fn bitonic_step_kernel(
+    indices: &mut Array<u32>,
+    num_elems: u32,
+    block: u32,
+    stride: u32,
+    key_0: u32,
+    key_1: u32,
+    stream_0: u32,
+    stream_1: u32,
+) {
+    if ABSOLUTE_POS >= indices.len() {
+        terminate!();
+    }
+
+    let position = ABSOLUTE_POS as u32;
+    let partner = position ^ stride;
+
+    if partner > position {
+        let first = indices[position as usize];
+        let second = indices[partner as usize];
+
+        let ascending = (position & block) == 0;
+        let swap = if ascending {
+            sorts_before(second, first, num_elems, key_0, key_1, stream_0, stream_1)
+        } else {
+            sorts_before(first, second, num_elems, key_0, key_1, stream_0, stream_1)
+        };
+
+        if swap {
+            indices[position as usize] = second;
+            indices[partner as usize] = first;
+        }
+    }
+}
+
+/// Whether index `a` comes before index `b`, comparing their random keys then the indices.
+#[cube]
+fn sorts_before(
+    a: u32,
+    b: u32,
+    num_elems: u32,
+    key_0: u32,
+    key_1: u32,
+    stream_0: u32,
+    stream_1: u32,
+) -> bool {
+    let (a_hi, a_lo) = sort_key(a, num_elems, key_0, key_1, stream_0, stream_1);
+    let (b_hi, b_lo) = sort_key(b, num_elems, key_0, key_1, stream_0, stream_1);
+
+    a_hi < b_hi || (a_hi == b_hi && (a_lo < b_lo || (a_lo == b_lo && a < b)))
+}
+
+#[cube]
+fn sort_key(
+    index: u32,
+    num_elems: u32,
+    key_0: u32,
+    key_1: u32,
+    stream_0: u32,
+    stream_1: u32,
+) -> (u32, u32) {
+    let mut hi = u32::MAX;
+    let mut lo = u32::MAX;
+
+    if index < num_elems {
+        let words = philox4x32_10_block(index, 0u32, stream_0, stream_1, key_0, key_1);
+        hi = words[0];
+        lo = words[1];
+    }
+
+    (hi, lo)
+}
+
+#[cube(launch)]
+fn write_permutation_kernel<I: Int>(
+    indices: &Array<u32>,
+    output: &mut LinearView<Line<I>, ReadWrite>,
+    #[define(I)] _dtype: StorageType,
+) {
+    if output.is_in_bounds(ABSOLUTE_POS) {
+        output[ABSOLUTE_POS] = Line::new(I::cast_from(indices[ABSOLUTE_POS]));
+    }
+}
+
+#[cube(launch)]
+fn shuffle_kernel<E: Numeric>(
+    input: &Tensor<E>,
+    permutation: &Array<u32>,
+    output: &mut Tensor<E>,
+    #[comptime] axis: usize,
+    #[define(E)] _dtype: StorageType,
+) {
+    if ABSOLUTE_POS >= output.len() {
+        terminate!();
+    }
+
+    let mut remainder = ABSOLUTE_POS;
+    let mut input_offset = 0usize;
+    let mut output_offset = 0usize;
+
+    for i in 0..output.rank() {
+        let dim = output.rank() - 1 - i;
+        let coordinate = remainder % output.shape(dim);
+        remainder /= output.shape(dim);
+
+        let source = if dim == axis {
+            permutation[coordinate] as usize
+        } else {
+            coordinate
+        };
+
+        input_offset += source * input.stride(dim);
+        output_offset += coordinate * output.stride(dim);
+    }
+
+    output[output_offset] = input[input_offset];
+}
@@ -0,0 +1,154 @@
+use cubecl::calculate_cube_count_elemwise;
+use cubecl::prelude::*;
+use cubecl::std::tensor::layout::linear::{LinearView, linear_view};
+
+use crate::PrngArgs;
+
+/// Number of `u32` words produced by a single Philox4x32 block.
+pub(crate) const PHILOX_WORDS: usize = 4;
+
+const PHILOX_M0: u32 = 0xD2511F53;
+const PHILOX_M1: u32 = 0xCD9E8D57;
+const PHILOX_W0: u32 = 0x9E3779B9;
+const PHILOX_W1: u32 = 0xBB67AE85;
+
+/// Counter-based pseudo-random generator (Philox4x32-10).
+///
+/// Every group of four consecutive output elements is generated from its own counter
+/// `[block_lo, block_hi, offset_lo, offset_hi]` under the key `[seed_lo, seed_hi]`, so each value
+/// is a pure function of `(seed, offset, index)` and does not depend on the launch configuration.
+pub(crate) fn random_philox<F: PhiloxFamily, R: Runtime>(
+    client: &ComputeClient<R>,
+    seed: u64,
+    offset: u64,
+    prng: F::Runtime,
+    output: TensorHandleRef<'_, R>,
+    dtype: StorageType,
+) -> Result<(), LaunchError> {
+    let args = prng.args();
+
+    let num_blocks = output.size().div_ceil(PHILOX_WORDS);
+    let cube_dim = CubeDim::new(client, num_blocks);
+    let cube_count = calculate_cube_count_elemwise(client, num_blocks, cube_dim);
+
+    let output = linear_view(client, &output, 1);
+
+    philox_kernel::launch::<F, R>(
+        client,
+        cube_count,
+        cube_dim,
+        output,
+        ScalarArg::new(seed as u32),
+        ScalarArg::new((seed >> 32) as u32),
+        ScalarArg::new(offset as u32),
+        ScalarArg::new((offset >> 32) as u32),
+        args,
+        dtype,
+    )
+}
+
+pub(crate) trait PhiloxFamily: Send + Sync + 'static + std::fmt::Debug {
+    type Runtime: PhiloxRuntime;
+}
+
+#[cube]
+pub(crate) trait PhiloxRuntime: Send + Sync + 'static + PrngArgs {
+    /// Maps the random words of a Philox block to the same number of values of the distribution.
+    fn transform_block<E: Numeric>(args: Self::Args, words: Line<u32>) -> Line<E>;
+}
+
+type Args<F> = <<F as PhiloxFamily>::Runtime as PrngArgs>::Args;
+
+#[cube(launch)]
+fn philox_kernel<F: PhiloxFamily, E: Numeric>(
+    output: &mut LinearView<Line<E>, ReadWrite>,
+    key_0: u32,
+    key_1: u32,
+    offset_0: u32,
+    offset_1: u32,
+    args: Args<F>,
+    #[define(E)] _dtype: StorageType,
+) {
+    // Shifted twice so that the shift stays valid with 32-bit positions
+    let block_0 = u32::cast_from(ABSOLUTE_POS);
+    let block_1 = u32::cast_from((ABSOLUTE_POS >> 16) >> 16);
+    let words = philox4x32_10_block(block_0, block_1, offset_0, offset_1, key_0, key_1);
+    let values = F::Runtime::transform_block::<E>(args, words);
+
+    let write_index_base = ABSOLUTE_POS * PHILOX_WORDS;
+
+    #[unroll]
+    for i in 0..PHILOX_WORDS {
+        let write_index = write_index_base + i;
+        if output.is_in_bounds(write_index) {
+            output[write_index] = Line::new(values[i]);
+        }
+    }
+}
+
+/// Computes one Philox4x32-10 block on the device.
+///
+/// Returns a line of four independent random words.
+#[cube]
+pub(crate) fn philox4x32_10_block(
+    counter_0: u32,
+    counter_1: u32,
+    counter_2: u32,
+    counter_3: u32,
+    key_0: u32,
+    key_1: u32,
+) -> Line<u32> {
+    let mut c_0 = counter_0;
+    let mut c_1 = counter_1;
+    let mut c_2 = counter_2;
+    let mut c_3 = counter_3;
+    let mut k_0 = key_0;
+    let mut k_1 = key_1;
+
+    #[unroll]
+    for _round in 0..10u32 {
+        let hi_0 = c_0.mul_hi(PHILOX_M0);
+        let lo_0 = c_0 * PHILOX_M0;
+        let hi_1 = c_2.mul_hi(PHILOX_M1);
+        let lo_1 = c_2 * PHILOX_M1;
+
+        c_0 = hi_1 ^ c_1 ^ k_0;
+        c_1 = lo_1;
+        c_2 = hi_0 ^ c_3 ^ k_1;
+        c_3 = lo_0;
+
+        k_0 += PHILOX_W0;
+        k_1 += PHILOX_W1;
+    }
+
+    let mut words = Line::empty(PHILOX_WORDS);
+    words[0] = c_0;
+    words[1] = c_1;
+    words[2] = c_2;
+    words[3] = c_3;
+    words
+}
+
+/// Computes one Philox4x32-10 block on the host.
+///
+/// Matches the device implementation bit for bit, and the reference vectors of the
+/// [Random123](https://github.com/DEShawResearch/random123) library.
+pub fn philox4x32_10(counter: [u32; 4], key: [u32; 2]) -> [u32; 4] {
+    let [mut c_0, mut c_1, mut c_2, mut c_3] = counter;
+    let [mut k_0, mut k_1] = key;
+
+    for _ in 0..10 {
+        let product_0 = PHILOX_M0 as u64 * c_0 as u64;
+        let product_1 = PHILOX_M1 as u64 * c_2 as u64;
+
+        c_0 = (product_1 >> 32) as u32 ^ c_1 ^ k_0;
+        c_1 = product_1 as u32;
+        c_2 = (product_0 >> 32) as u32 ^ c_3 ^ k_1;
+        c_3 = product_0 as u32;
+
+        k_0 = k_0.wrapping_add(PHILOX_W0);
+        k_1 = k_1.wrapping_add(PHILOX_W1);
+    }
+
+    [c_0, c_1, c_2, c_3]
+}
@@ -0,0 +1,175 @@
+use cubecl::prelude::*;
+use cubecl::std::tensor::View;
+
+use super::{PrngArgs, PrngRuntime, random};
+
+use crate::{RandomFamily, RandomGenerator, next_random_word, to_unit_interval_open};
+
+#[derive(CubeLaunch, CubeType)]
+pub(crate) struct Poisson {
+    lambda: f32,
+}
+
+#[derive(Debug)]
+struct PoissonFamily;
+
+impl RandomFamily for PoissonFamily {
+    type Runtime = Poisson;
+}
+
+#[cube]
+impl PrngRuntime for Poisson {
+    fn inner_loop<E: Numeric>(
+        args: Poisson,
+        write_index_base: usize,
+        n_invocations: u32,
+        #[comptime] n_values_per_thread: usize,
+        #[comptime] line_size: LineSize,
+        state_0: &mut Line<u32>,
+        state_1: &mut Line<u32>,
+        state_2: &mut Line<u32>,
+        state_3: &mut Line<u32>,
+        output: &mut View<Line<E>, usize, ReadWrite>,
+    ) {
+        let lambda = args.lambda;
+
+        let mut output_line = Line::empty(line_size);
+
+        let num_iterations = n_values_per_thread / line_size;
+        #[unroll(num_iterations <= 8)]
+        for line_index in 0..num_iterations {
+            // vectorization
+            #[unroll]
+            for i in 0..line_size {
+                let poisson = sample_poisson(lambda, state_0, state_1, state_2, state_3, i);
+
+                output_line[i] = E::cast_from(poisson);
+            }
+
+            let write_index = line_index * n_invocations as usize + write_index_base;
+
+            output.write_checked(write_index, output_line);
+        }
+    }
+}
+
+/// Samples a Poisson variable, returned as a float holding an integer value.
+#[cube]
+fn sample_poisson(
+    lambda: f32,
+    state_0: &mut Line<u32>,
+    state_1: &mut Line<u32>,
+    state_2: &mut Line<u32>,
+    state_3: &mut Line<u32>,
+    #[comptime] lane: usize,
+) -> f32 {
+    let mut poisson = 0.0f32;
+
+    if lambda < 10.0 {
+        let int_random = next_random_word(state_0, state_1, state_2, state_3, lane);
+        let unit = to_unit_interval_open(int_random);
+
+        // Sequential search of the inverse CDF
+        let mut prob = f32::exp(-lambda);
+        let mut cumulative = prob;
+
+        loop {
+            // Bounded to stay safe from rounding errors in the tail
+            if unit <= cumulative || poisson >= 100.0 {
+                break;
+            }
+
+            poisson += 1.0;
+            prob *= lambda / poisson;
+            cumulative += prob;
+        }
+    } else {
+        let log_lambda = f32::ln(lambda);
+        let b = 0.931 + 2.53 * f32::sqrt(lambda);
+        let a = -0.059 + 0.02483 * b;
+        let inv_alpha = 1.1239 + 1.1328 / (b - 3.4);
+        let v_r = 0.9277 - 3.6224 / (b - 2.0);
+
+        loop {
+            let int_random = next_random_word(state_0, state_1, state_2, state_3, lane);
+            let u = to_unit_interval_open(int_random) - 0.5;
+            let int_random = next_random_word(state_0, state_1, state_2, state_3, lane);
+            let v = to_unit_interval_open(int_random);
+
+            let u_s = 0.5 - f32::abs(u);
+            let k = f32::floor((2.0 * a / u_s + b) * u + lambda + 0.43);
+
+            // Squeeze, accepting most samples without evaluating the density
+            if u_s >= 0.07 && v <= v_r {
+                poisson = k;
+                break;
+            }
+
+            if k >= 0.0 && (u_s >= 0.013 || v <= u_s) {
+                let lhs = f32::ln(v * inv_alpha / (a / (u_s * u_s) + b));
+                let rhs = -lambda + k * log_lambda - log_factorial(k);
+
+                if lhs <= rhs {
+                    poisson = k;
+                    break;
+                }
+            }
+        }
+    }
+
+    poisson
+}
+
+/// Natural logarithm of `k!`, with Stirling's series.
+#[cube]
+fn log_factorial(k: f32) -> f32 {
+    // ln(k!) = ln(Gamma(k + 1)), shifted until the series is accurate
+    let mut x = k + 1.0;
+    let mut shift = 0.0f32;
+
+    loop {
+        if x >= 7.0 {
+            break;
+        }
+
+        shift += f32::ln(x);
+        x += 1.0;
+    }
+
+    let inv_x = 1.0 / x;
+    let inv_x_sq = inv_x * inv_x;
+    let series = inv_x * (1.0 / 12.0 - inv_x_sq * (1.0 / 360.0 - inv_x_sq / 1260.0));
+
+    // 0.9189385 = ln(2 * pi) / 2
+    (x - 0.5) * f32::ln(x) - x + 0.9189385 + series - shift
+}
+
+impl PrngArgs for Poisson {
+    type Args = Self;
+
+    fn args<'a, R: Runtime>(self) -> PoissonLaunch<'a, R> {
+        PoissonLaunch::new(ScalarArg::new(self.lambda))
+    }
+}
+
+/// Pseudo-random generator with Poisson distribution of the given rate `lambda`.
+///
+/// Small rates use inversion of the CDF, and rates of 10 or more use the transformed rejection
+/// method with squeeze (PTRS) of W. Hörmann, "The transformed rejection method for generating
+/// Poisson random variables", 1993.
+pub fn random_poisson<R: Runtime>(
+    client: &ComputeClient<R>,
+    generator: &mut RandomGenerator,
+    lambda: f32,
+    out: TensorHandleRef<R>,
+    dtype: StorageType,
+) -> Result<(), LaunchError> {
+    assert_eq!(
+        out.elem_size,
+        dtype.size(),
+        "Tensor element type must be the same as type E"
+    );
+    assert!(lambda > 0.0, "Lambda must be positive, got {lambda}");
+
+    random::<PoissonFamily, R>(client, generator, Poisson { lambda }, out, dtype)
+}
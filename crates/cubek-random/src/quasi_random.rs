@@ -0,0 +1,331 @@
+use std::sync::OnceLock;
+
+use cubecl::calculate_cube_count_elemwise;
+use cubecl::prelude::*;
+use cubecl::std::tensor::layout::linear::{LinearView, linear_view};
+
+use crate::{RandomGenerator, philox4x32_10, philox4x32_10_block, to_unit_interval_closed_open};
+
+/// Maximum number of dimensions of the quasi-random sequences.
+pub const MAX_QUASI_RANDOM_DIMS: usize = 1024;
+
+/// Maximum number of dimensions of the Sobol sequence, the first one and one per entry of the
+/// direction numbers table, which is its number of lines with the header.
+pub const MAX_SOBOL_DIMS: usize = count_lines(SOBOL_TABLE);
+
+/// Leading lines of the `new-joe-kuo-6.21201` table of Joe and Kuo, in its original format: a
+/// header followed by the dimension, degree `s` and coefficients `a` of the primitive polynomial,
+/// and the initial direction numbers `m_1 .. m_s` of every dimension after the first.
+const SOBOL_TABLE: &str = include_str!("sobol_directions.txt");
+
+/// Number of direction numbers of a Sobol dimension, one per bit of the point index.
+const SOBOL_BITS: usize = 32;
+
+/// Direction numbers of a dimension, followed by its digital shift.
+const SOBOL_STRIDE: usize = SOBOL_BITS + 1;
+
+/// Writes points of the Sobol sequence to `out`, in `[0, 1)`.
+///
+/// The last dimension of `out` is the dimension of the points, and all the others are flattened
+/// into consecutive points starting at index `first_index`, so a long sequence can be generated in
+/// chunks. Up to [MAX_SOBOL_DIMS] dimensions are supported, with the direction numbers of Joe and
+/// Kuo, which are optimized for the uniformity of two-dimensional projections.
+///
+/// With a `scrambling` generator, the directions go through a random linear matrix scrambling and
+/// a random digital shift, which keeps the stratification properties of the sequence while making
+/// it usable for randomized quasi-Monte Carlo error estimates.
+pub fn sobol_sequence<R: Runtime>(
+    client: &ComputeClient<R>,
+    scrambling: Option<&mut RandomGenerator>,
+    first_index: u32,
+    out: TensorHandleRef<R>,
+    dtype: StorageType,
+) -> Result<(), LaunchError> {
+    let dims = quasi_random_dims(&out, dtype, MAX_SOBOL_DIMS);
+
+    let mut directions = sobol_directions()[..dims * SOBOL_STRIDE].to_vec();
+    if let Some(generator) = scrambling {
+        scramble_sobol_directions(&mut directions, generator.next_seeds());
+    }
+    let directions = client.create_from_slice(u32::as_bytes(&directions));
+
+    let num_elems = out.size();
+    let cube_dim = CubeDim::new(client, num_elems);
+    let cube_count = calculate_cube_count_elemwise(client, num_elems, cube_dim);
+
+    sobol_kernel::launch::<R>(
+        client,
+        cube_count,
+        cube_dim,
+        unsafe { ArrayArg::from_raw_parts::<u32>(&directions, dims * SOBOL_STRIDE, 1) },
+        linear_view(client, &out, 1),
+        ScalarArg::new(dims as u32),
+        ScalarArg::new(first_index),
+        dtype,
+    )
+}
+
+/// Writes points of the Halton sequence to `out`, in `[0, 1)`.
+///
+/// Uses the same layout as [sobol_sequence], dimension `d` being the radical inverse in the base
+/// of the `d`-th prime. With a `scrambling` generator, every digit goes through a random affine
+/// permutation `digit -> (a * digit + b) mod base` drawn per dimension and digit position, which
+/// breaks the correlation between high dimensions of the plain sequence.
+pub fn halton_sequence<R: Runtime>(
+    client: &ComputeClient<R>,
+    scrambling: Option<&mut RandomGenerator>,
+    first_index: u32,
+    out: TensorHandleRef<R>,
+    dtype: StorageType,
+) -> Result<(), LaunchError> {
+    let dims = quasi_random_dims(&out, dtype, MAX_QUASI_RANDOM_DIMS);
+
+    let scrambled = scrambling.is_some();
+    let seeds = scrambling
+        .map(|generator| generator.next_seeds())
+        .unwrap_or_default();
+
+    let primes = client.create_from_slice(u32::as_bytes(&halton_bases()[..dims]));
+
+    let num_elems = out.size();
+    let cube_dim = CubeDim::new(client, num_elems);
+    let cube_count = calculate_cube_count_elemwise(client, num_elems, cube_dim);
+
+    halton_kernel::launch::<R>(
+        client,
+        cube_count,
+        cube_dim,
+        unsafe { ArrayArg::from_raw_parts::<u32>(&primes, dims, 1) },
+        linear_view(client, &out, 1),
+        ScalarArg::new(dims as u32),
+        ScalarArg::new(first_index),
+        ScalarArg::new(seeds[0]),
+        ScalarArg::new(seeds[1]),
+        ScalarArg::new(seeds[2]),
+        ScalarArg::new(seeds[3]),
+        scrambled,
+        dtype,
+    )
+}
+
+fn quasi_random_dims<R: Runtime>(
+    out: &TensorHandleRef<R>,
+    dtype: StorageType,
+    max_dims: usize,
+) -> usize {
+    assert_eq!(
+        out.elem_size,
+        dtype.size(),
+        "Tensor element type must be the same as type E"
+    );
+
+    let dims = *out
+        .shape
+        .last()
+        .expect("Output must have at least one dimension");
+    assert!(
+        (1..=max_dims).contains(&dims),
+        "The sequence supports 1 to {max_dims} dimensions, got {dims}"
+    );
+
+    dims
+}
+
+#[cube(launch)]
+fn sobol_kernel<E: Numeric>(
+    directions: &Array<u32>,
+    output: &mut LinearView<Line<E>, ReadWrite>,
+    dims: u32,
+    first_index: u32,
+    #[define(E)] _dtype: StorageType,
+) {
+    if !output.is_in_bounds(ABSOLUTE_POS) {
+        terminate!();
+    }
+
+    let point = first_index + (ABSOLUTE_POS / dims as usize) as u32;
+    let base = (ABSOLUTE_POS % dims as usize) * SOBOL_STRIDE;
+
+    let mut value = directions[base + SOBOL_BITS];
+
+    #[unroll]
+    for bit in 0..SOBOL_BITS {
+        if (point >> bit as u32) & 1 == 1 {
+            value ^= directions[base + bit];
+        }
+    }
+
+    output[ABSOLUTE_POS] = Line::new(E::cast_from(to_unit_interval_closed_open(value)));
+}
+
+#[cube(launch)]
+#[allow(clippy::too_many_arguments)]
+fn halton_kernel<E: Numeric>(
+    primes: &Array<u32>,
+    output: &mut LinearView<Line<E>, ReadWrite>,
+    dims: u32,
+    first_index: u32,
+    key_0: u32,
+    key_1: u32,
+    stream_0: u32,
+    stream_1: u32,
+    #[comptime] scrambled: bool,
+    #[define(E)] _dtype: StorageType,
+) {
+    if !output.is_in_bounds(ABSOLUTE_POS) {
+        terminate!();
+    }
+
+    let mut index = first_index + (ABSOLUTE_POS / dims as usize) as u32;
+    let dim = (ABSOLUTE_POS % dims as usize) as u32;
+    let base = primes[dim as usize];
+
+    let inv_base = 1.0 / f32::cast_from(base);
+    let mut digit_weight = inv_base;
+    let mut value = 0.0f32;
+    let mut position = 0u32;
+
+    loop {
+        // Scrambled zero digits are not zero, so they are needed down to the f32 precision
+        if scrambled {
+            if digit_weight < 5.9604645e-8 {
+                break;
+            }
+        }
+        if !scrambled {
+            if index == 0 {
+                break;
+            }
+        }
+
+        let mut digit = index % base;
+
+        if scrambled {
+            let words = philox4x32_10_block(dim, position, stream_0, stream_1, key_0, key_1);
+            let multiplier = 1 + words[0] % (base - 1);
+            let shift = words[1] % base;
+            digit = (multiplier * digit + shift) % base;
+        }
+
+        value += f32::cast_from(digit) * digit_weight;
+        digit_weight *= inv_base;
+        index /= base;
+        position += 1;
+    }
+
+    // Accumulated rounding errors could reach 1
+    let value = f32::min(value, 0.99999994);
+
+    output[ABSOLUTE_POS] = Line::new(E::cast_from(value));
+}
+
+/// Unscrambled direction numbers of every dimension, with a zero digital shift.
+fn sobol_directions() -> &'static [u32] {
+    static DIRECTIONS: OnceLock<Vec<u32>> = OnceLock::new();
+
+    DIRECTIONS.get_or_init(|| {
+        let mut directions = Vec::with_capacity(MAX_SOBOL_DIMS * SOBOL_STRIDE);
+
+        // The first dimension is the van der Corput sequence
+        directions.extend((0..SOBOL_BITS).map(|k| 1u32 << (31 - k)));
+        directions.push(0);
+
+        for line in SOBOL_TABLE.lines().skip(1) {
+            let values: Vec<u32> = line
+                .split_whitespace()
+                .map(|value| {
+                    value
+                        .parse()
+                        .expect("Invalid Sobol direction numbers table")
+                })
+                .collect();
+            let (degree, coefficients) = (values[1] as usize, values[2]);
+            let polynomial = (1 << degree) | (coefficients << 1) | 1;
+
+            let mut m = values[3..].to_vec();
+            assert_eq!(m.len(), degree, "Invalid Sobol direction numbers table");
+
+            // m_k = 2 a_1 m_{k-1} ^ 4 a_2 m_{k-2} ^ ... ^ 2^s m_{k-s} ^ m_{k-s}
+            for k in degree..SOBOL_BITS {
+                let mut value = m[k - degree] ^ (m[k - degree] << degree);
+                for i in 1..degree {
+                    if (polynomial >> (degree - i)) & 1 == 1 {
+                        value ^= m[k - i] << i;
+                    }
+                }
+                m.push(value);
+            }
+
+            directions.extend(m.iter().enumerate().map(|(k, m)| m << (31 - k)));
+            directions.push(0);
+        }
+
+        directions
+    })
+}
+
+/// Number of lines of `text`, which ends with a line break.
+const fn count_lines(text: &str) -> usize {
+    let bytes = text.as_bytes();
+    let mut count = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'\n' {
+            count += 1;
+        }
+        i += 1;
+    }
+
+    count
+}
+
+/// Applies a random linear matrix scrambling and digital shift to the directions of every
+/// dimension.
+fn scramble_sobol_directions(directions: &mut [u32], seeds: [u32; 4]) {
+    let key = [seeds[0], seeds[1]];
+
+    for (dim, directions) in directions.chunks_mut(SOBOL_STRIDE).enumerate() {
+        // Row i of a random lower triangular matrix with unit diagonal, where bit 31 is the first
+        // digit: output digit i only depends on input digits 0 to i
+        let rows: Vec<u32> = (0..SOBOL_BITS)
+            .map(|i| {
+                let [word, ..] = philox4x32_10([dim as u32, i as u32, seeds[2], seeds[3]], key);
+                let higher_digits = !(u32::MAX >> i);
+                (word & higher_digits) | (1 << (31 - i))
+            })
+            .collect();
+
+        for direction in directions[..SOBOL_BITS].iter_mut() {
+            *direction = rows.iter().enumerate().fold(0, |scrambled, (i, row)| {
+                scrambled | (((*direction & row).count_ones() & 1) << (31 - i))
+            });
+        }
+
+        let [shift, ..] = philox4x32_10([dim as u32, SOBOL_BITS as u32, seeds[2], seeds[3]], key);
+        directions[SOBOL_BITS] = shift;
+    }
+}
+
+/// The first [MAX_QUASI_RANDOM_DIMS] primes.
+fn halton_bases() -> &'static [u32] {
+    static PRIMES: OnceLock<Vec<u32>> = OnceLock::new();
+
+    PRIMES.get_or_init(|| {
+        let mut primes = Vec::with_capacity(MAX_QUASI_RANDOM_DIMS);
+        let mut candidate = 2u32;
+
+        while primes.len() < MAX_QUASI_RANDOM_DIMS {
+            if primes
+                .iter()
+                .take_while(|p| *p * *p <= candidate)
+                .all(|p| candidate % p != 0)
+            {
+                primes.push(candidate);
+            }
+            candidate += 1;
+        }
+
+        primes
+    })
+}
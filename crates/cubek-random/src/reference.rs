@@ -0,0 +1,125 @@
+use std::f32::consts::PI;
+
+use crate::{N_VALUES_PER_THREAD, RandomGenerator, prng_stream_count};
+
+/// Computes on the host the random words that [random_uniform](crate::random_uniform),
+/// [random_normal](crate::random_normal) and [random_bernoulli](crate::random_bernoulli) draw for
+/// an output of `num_elems` elements, in logical row-major order.
+///
+/// `seeds` is the quadruple returned by [RandomGenerator::next_seeds] for the launch. The words of
+/// the device generator only depend on the seeds and on the number of elements, not on the cube
+/// dim, the cube count or the line size of the launch, so they are reproduced bit for bit.
+pub fn reference_random_words(seeds: [u32; 4], num_elems: usize) -> Vec<u32> {
+    let mut words = stream_words(seeds, num_elems);
+    words.truncate(num_elems);
+    words
+}
+
+/// Host reference of [random_uniform](crate::random_uniform), advancing `generator` like the
+/// launch.
+///
+/// Values in `[0, 1)` are reproduced bit for bit. Other bounds are scaled with a multiplication
+/// and an addition in `f32`, which backends contracting them into a fused multiply-add round
+/// differently by at most one unit in the last place.
+pub fn reference_uniform(
+    generator: &mut RandomGenerator,
+    lower_bound: f32,
+    upper_bound: f32,
+    num_elems: usize,
+) -> Vec<f32> {
+    let scale = upper_bound - lower_bound;
+
+    reference_random_words(generator.next_seeds(), num_elems)
+        .into_iter()
+        .map(|word| unit_interval_closed_open(word) * scale + lower_bound)
+        .collect()
+}
+
+/// Host reference of [random_bernoulli](crate::random_bernoulli), advancing `generator` like the
+/// launch, with ones and zeros as `f32`. Reproduced bit for bit.
+pub fn reference_bernoulli(
+    generator: &mut RandomGenerator,
+    probability: f32,
+    num_elems: usize,
+) -> Vec<f32> {
+    reference_random_words(generator.next_seeds(), num_elems)
+        .into_iter()
+        .map(|word| (unit_interval_closed_open(word) < probability) as u32 as f32)
+        .collect()
+}
+
+/// Host reference of [random_normal](crate::random_normal), advancing `generator` like the
+/// launch.
+///
+/// Values `2j` and `2j + 1` of every stream are the two outputs of a Box-Muller transform of its
+/// words `2j` and `2j + 1`. The uniform values are reproduced bit for bit, but device logarithms,
+/// square roots and trigonometric functions are not correctly rounded, so the normal values only
+/// match to a few units in the last place.
+pub fn reference_normal(
+    generator: &mut RandomGenerator,
+    mean: f32,
+    std: f32,
+    num_elems: usize,
+) -> Vec<f32> {
+    let num_streams = prng_stream_count(num_elems);
+    let words = stream_words(generator.next_seeds(), num_elems);
+
+    let mut values = vec![0.; words.len()];
+    for stream in 0..num_streams {
+        for pair in 0..N_VALUES_PER_THREAD / 2 {
+            let index_0 = 2 * pair * num_streams + stream;
+            let index_1 = index_0 + num_streams;
+
+            let unit_0 = unit_interval_open(words[index_0]);
+            let unit_1 = unit_interval_open(words[index_1]);
+
+            let coeff = f32::sqrt(unit_0.ln() * -2.) * std;
+            let trigo_arg = 2. * PI * unit_1;
+
+            values[index_0] = f32::cos(trigo_arg) * coeff + mean;
+            values[index_1] = f32::sin(trigo_arg) * coeff + mean;
+        }
+    }
+
+    values.truncate(num_elems);
+    values
+}
+
+/// Every word drawn by the streams, including the ones past the last element: value `k` of stream
+/// `s` is at index `k * num_streams + s`.
+fn stream_words(seeds: [u32; 4], num_elems: usize) -> Vec<u32> {
+    let num_streams = prng_stream_count(num_elems);
+    let mut words = vec![0; num_streams * N_VALUES_PER_THREAD];
+
+    for stream in 0..num_streams {
+        // Same seeding as the device, where the stream is the lane position
+        let stream_seed = 1000000007u32.wrapping_mul(stream as u32);
+        let mut state = seeds.map(|seed| stream_seed.wrapping_add(seed));
+
+        for value in 0..N_VALUES_PER_THREAD {
+            state[0] = taus_step(state[0], 13, 19, 12, 4294967294);
+            state[1] = taus_step(state[1], 2, 25, 4, 4294967288);
+            state[2] = taus_step(state[2], 3, 11, 17, 4294967280);
+            state[3] = state[3].wrapping_mul(1664525).wrapping_add(1013904223);
+
+            words[value * num_streams + stream] = state[0] ^ state[1] ^ state[2] ^ state[3];
+        }
+    }
+
+    words
+}
+
+fn taus_step(z: u32, s1: u32, s2: u32, s3: u32, m: u32) -> u32 {
+    let b = ((z << s1) ^ z) >> s2;
+    ((z & m) << s3) ^ b
+}
+
+/// Host version of [to_unit_interval_closed_open](crate::to_unit_interval_closed_open).
+fn unit_interval_closed_open(word: u32) -> f32 {
+    (word >> 8) as f32 / 16777216.
+}
+
+/// Host version of [to_unit_interval_open](crate::to_unit_interval_open).
+fn unit_interval_open(word: u32) -> f32 {
+    ((word >> 9) as f32 + 1.) / 8388609.
+}
@@ -0,0 +1,233 @@
+use cubecl::calculate_cube_count_elemwise;
+use cubecl::ir::{ElemType, FloatKind};
+use cubecl::prelude::*;
+use cubecl::std::tensor::layout::linear::{LinearView, linear_view};
+use cubecl::tensor_line_size_parallel;
+
+use crate::{
+    N_VALUES_PER_THREAD, RandomGenerator, STREAM_ALIGNMENT, next_random_word, prng_stream_count,
+    seed_lane_states, to_unit_interval_closed_open,
+};
+
+/// Casts `input` from `f32` to a lower precision float type with stochastic rounding.
+///
+/// Each value is rounded up with a probability equal to its distance to the lower neighbour in the
+/// target type, divided by the spacing of that type around it, so the rounding is unbiased in
+/// expectation. Supported outputs are `bf16`, `f16`, `E4M3` and `E5M2`. Values outside the range
+/// of the target type saturate to its largest finite value.
+///
+/// The random words follow the same streams as the pseudo-random distributions, so the result only
+/// depends on the generator state and the shape, not on the hardware or launch configuration.
+pub fn stochastic_round<R: Runtime>(
+    client: &ComputeClient<R>,
+    generator: &mut RandomGenerator,
+    input: TensorHandleRef<R>,
+    output: TensorHandleRef<R>,
+    dtype: StorageType,
+) -> Result<(), LaunchError> {
+    assert_eq!(
+        input.elem_size,
+        size_of::<f32>(),
+        "Input element type must be f32"
+    );
+    assert_eq!(
+        output.elem_size,
+        dtype.size(),
+        "Tensor element type must be the same as type E"
+    );
+    assert_eq!(
+        input.shape, output.shape,
+        "Input and output must have the same shape"
+    );
+
+    let format = RoundingFormat::new(dtype);
+    let seeds = generator.next_seeds();
+
+    let num_elems = output.size();
+    if num_elems == 0 {
+        return Ok(());
+    }
+
+    // A scalar has no dimension to vectorize along
+    let line_size = match output.shape.is_empty() {
+        true => 1,
+        false => {
+            let input_line_size = tensor_line_size_parallel(
+                client.io_optimized_line_sizes_unchecked(input.elem_size),
+                input.shape,
+                input.strides,
+                input.strides.len() - 1,
+            );
+            let output_line_size = tensor_line_size_parallel(
+                client.io_optimized_line_sizes_unchecked(output.elem_size),
+                output.shape,
+                output.strides,
+                output.strides.len() - 1,
+            );
+            input_line_size.min(output_line_size).min(STREAM_ALIGNMENT)
+        }
+    };
+
+    // Every unit drives one stream per lane
+    let num_units = prng_stream_count(num_elems) / line_size;
+
+    let cube_dim = CubeDim::new(client, num_units);
+    let cube_count = calculate_cube_count_elemwise(client, num_units, cube_dim);
+
+    stochastic_round_kernel::launch::<R>(
+        client,
+        cube_count,
+        cube_dim,
+        linear_view(client, &input, line_size),
+        linear_view(client, &output, line_size),
+        ScalarArg::new(seeds[0]),
+        ScalarArg::new(seeds[1]),
+        ScalarArg::new(seeds[2]),
+        ScalarArg::new(seeds[3]),
+        ScalarArg::new(num_units),
+        ScalarArg::new(format.max_value),
+        ScalarArg::new(format.subnormal_spacing),
+        format.mantissa_bits,
+        format.min_biased_exponent,
+        N_VALUES_PER_THREAD * line_size,
+        line_size,
+        dtype,
+    )
+}
+
+/// Properties of the target type needed to find the neighbours of a value.
+struct RoundingFormat {
+    mantissa_bits: u32,
+    /// Smallest exponent of a normal value, with the `f32` bias.
+    min_biased_exponent: u32,
+    max_value: f32,
+    /// Distance between two consecutive subnormal values.
+    subnormal_spacing: f32,
+}
+
+impl RoundingFormat {
+    fn new(dtype: StorageType) -> Self {
+        let (mantissa_bits, min_exponent, max_value) = match dtype.elem_type() {
+            ElemType::Float(FloatKind::BF16) => (7, -126, f32::from_bits(0x7F7F0000)),
+            ElemType::Float(FloatKind::F16) => (10, -14, 65504.0),
+            ElemType::Float(FloatKind::E4M3) => (3, -6, 448.0),
+            ElemType::Float(FloatKind::E5M2) => (2, -14, 57344.0),
+            _ => panic!("Stochastic rounding is not supported for {dtype:?}"),
+        };
+
+        Self {
+            mantissa_bits,
+            min_biased_exponent: (min_exponent + 127) as u32,
+            max_value,
+            subnormal_spacing: 2f32.powi(min_exponent - mantissa_bits as i32),
+        }
+    }
+}
+
+#[cube(launch)]
+#[allow(clippy::too_many_arguments)]
+fn stochastic_round_kernel<E: Float>(
+    input: &LinearView<Line<f32>>,
+    output: &mut LinearView<Line<E>, ReadWrite>,
+    seed_0: u32,
+    seed_1: u32,
+    seed_2: u32,
+    seed_3: u32,
+    num_units: usize,
+    max_value: f32,
+    subnormal_spacing: f32,
+    #[comptime] mantissa_bits: u32,
+    #[comptime] min_biased_exponent: u32,
+    #[comptime] n_values_per_thread: usize,
+    #[comptime] line_size: usize,
+    #[define(E)] _dtype: StorageType,
+) {
+    // Extra units would wrap around to the lines of the first streams
+    if ABSOLUTE_POS >= num_units {
+        terminate!();
+    }
+
+    let mut state_0 = Line::empty(line_size);
+    let mut state_1 = Line::empty(line_size);
+    let mut state_2 = Line::empty(line_size);
+    let mut state_3 = Line::empty(line_size);
+
+    seed_lane_states(
+        &mut state_0,
+        &mut state_1,
+        &mut state_2,
+        &mut state_3,
+        seed_0,
+        seed_1,
+        seed_2,
+        seed_3,
+        line_size,
+    );
+
+    // Value `k` of the streams of this unit rounds line `k * num_units + ABSOLUTE_POS`
+    let num_iterations = n_values_per_thread / line_size;
+    #[unroll(num_iterations <= 8)]
+    for line_index in 0..num_iterations {
+        let index = line_index * num_units + ABSOLUTE_POS;
+
+        if output.is_in_bounds(index) {
+            let input_line = input[index];
+            let mut output_line = Line::empty(line_size);
+
+            #[unroll]
+            for i in 0..line_size {
+                let int_random =
+                    next_random_word(&mut state_0, &mut state_1, &mut state_2, &mut state_3, i);
+
+                output_line[i] = E::cast_from(round_stochastically(
+                    input_line[i],
+                    int_random,
+                    max_value,
+                    subnormal_spacing,
+                    mantissa_bits,
+                    min_biased_exponent,
+                ));
+            }
+
+            output[index] = output_line;
+        }
+    }
+}
+
+/// Rounds `value` to a neighbour representable in the target type, as an `f32`.
+#[cube]
+fn round_stochastically(
+    value: f32,
+    int_random: u32,
+    max_value: f32,
+    subnormal_spacing: f32,
+    #[comptime] mantissa_bits: u32,
+    #[comptime] min_biased_exponent: u32,
+) -> f32 {
+    let magnitude = clamp(f32::abs(value), 0.0, max_value);
+    let bits = u32::reinterpret(magnitude);
+
+    let mut rounded = 0.0f32;
+    if bits >> 23 >= min_biased_exponent {
+        // Normal in the target type: adding random bits below the kept mantissa before truncating
+        // carries into it with the right probability.
+        let dropped_bits = comptime!(23 - mantissa_bits);
+        let noise = int_random >> comptime!(32 - dropped_bits);
+        let kept_mask = comptime!(!((1u32 << dropped_bits) - 1));
+
+        rounded = f32::reinterpret((bits + noise) & kept_mask);
+    } else {
+        // Subnormal in the target type: the spacing is constant
+        let scaled = magnitude / subnormal_spacing;
+        let lower = f32::floor(scaled);
+        let round_up = to_unit_interval_closed_open(int_random) < scaled - lower;
+
+        rounded = (lower + f32::cast_from(round_up)) * subnormal_spacing;
+    }
+
+    if value < 0.0 {
+        rounded = -rounded;
+    }
+
+    rounded
+}
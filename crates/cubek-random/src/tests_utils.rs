@@ -106,8 +106,6 @@ pub fn assert_wald_wolfowitz_runs_test<E: Numeric>(data: &[E], bins_low: f32, bi
 
     // below 2 means we can have good confidence in the randomness
     // we put 2.6 to make sure it passes even when very unlucky.
-    // With higher vectorization, adjacent values are more
-    // correlated, which makes this test is more flaky.
     assert!(z.abs() < 2.6, "z: {z}, var: {variance}");
 }
 
@@ -123,3 +121,445 @@ pub fn assert_at_least_one_value_per_bin<E: Numeric>(
     assert!(stats[1].count >= 1);
     assert!(stats[2].count >= 1);
 }
+
+/// Asserts that the data follows the given cumulative distribution function, by comparing the
+/// number of values in each bin between `low` and `high` with its expected count.
+/// There is a very small chance this raises a false negative.
+pub fn assert_respects_cdf<E: Numeric>(
+    data: &[E],
+    number_of_bins: usize,
+    low: f32,
+    high: f32,
+    cdf: impl Fn(f32) -> f32,
+) {
+    let stats = calculate_bin_stats(data, number_of_bins, low, high);
+    let range = (high - low) / number_of_bins as f32;
+    let n = data.len() as f32;
+
+    for (i, stat) in stats.iter().enumerate() {
+        let bin_low = low + i as f32 * range;
+        let prob = cdf(bin_low + range) - cdf(bin_low);
+        let expected = prob * n;
+        // The count in a bin follows a binomial distribution
+        let std = f32::sqrt(n * prob * (1. - prob));
+
+        assert!(
+            f32::abs(stat.count as f32 - expected) <= 5. * std + 1.,
+            "Bin [{bin_low}, {}): count={}, expected={expected}",
+            bin_low + range,
+            stat.count
+        );
+    }
+}
+
+/// Asserts that the mean and variance of a dataset are approximately equal to expected values.
+/// The mean must be within 5 standard errors, and the variance within 5%.
+/// There is a very small chance this raises a false negative.
+pub fn assert_mean_and_variance_approx_equal<E: Numeric>(
+    data: &[E],
+    expected_mean: f32,
+    expected_variance: f32,
+) {
+    let n = data.len() as f64;
+    let mean = data.iter().map(|e| e.to_f64().unwrap()).sum::<f64>() / n;
+    let variance = data
+        .iter()
+        .map(|e| {
+            let d = e.to_f64().unwrap() - mean;
+            d * d
+        })
+        .sum::<f64>()
+        / (n - 1.);
+
+    let standard_error = f64::sqrt(expected_variance as f64 / n);
+    assert!(
+        (mean - expected_mean as f64).abs() < 5. * standard_error,
+        "mean={mean}, expected mean={expected_mean}, standard error={standard_error}",
+    );
+    assert!(
+        (variance / expected_variance as f64 - 1.).abs() < 0.05,
+        "variance={variance}, expected variance={expected_variance}",
+    );
+}
+
+/// Significance level under which the tests of the statistical battery fail.
+///
+/// The generators are seeded, so a test either always passes or always fails: the level is kept
+/// low so that a failure points at a defect of the generator rather than at an unlucky seed.
+pub const SIGNIFICANCE_LEVEL: f64 = 1e-4;
+
+/// Number of equiprobable bins of the chi-square test of the battery.
+const BATTERY_BINS: usize = 100;
+
+/// Largest lag of the serial correlation test of the battery, the largest line size. Values at
+/// these distances come from adjacent lanes and adjacent units.
+const BATTERY_MAX_LAG: usize = 16;
+
+/// Runs every test of the battery on values of a continuous distribution with the given
+/// cumulative distribution function: chi-square, Kolmogorov-Smirnov, serial correlation and
+/// birthday spacings.
+///
+/// Values go through the probability integral transform `u = cdf(x)` first, which is uniform in
+/// `[0, 1)` for a correct generator, so the same battery works for any distribution.
+pub fn assert_passes_continuous_battery<E: Numeric>(data: &[E], cdf: impl Fn(f32) -> f32) {
+    let transformed: Vec<f32> = data.iter().map(|e| cdf(e.to_f32().unwrap())).collect();
+
+    assert_chi_square_goodness_of_fit(&transformed, BATTERY_BINS, 0., 1., |u| u);
+    assert_kolmogorov_smirnov(data, &cdf);
+    assert_no_serial_correlation(&transformed, BATTERY_MAX_LAG);
+    assert_birthday_spacings(&transformed, 0., 1.);
+}
+
+/// Runs the tests of the battery that apply to values of a discrete distribution over the
+/// integers of `[low, high]`, with the given cumulative distribution function: chi-square with
+/// one bin per integer, and serial correlation.
+pub fn assert_passes_discrete_battery<E: Numeric>(
+    data: &[E],
+    low: i64,
+    high: i64,
+    cdf: impl Fn(f32) -> f32,
+) {
+    let number_of_bins = (high - low + 1) as usize;
+    assert_chi_square_goodness_of_fit(
+        data,
+        number_of_bins,
+        low as f32 - 0.5,
+        high as f32 + 0.5,
+        cdf,
+    );
+    assert_no_serial_correlation(data, BATTERY_MAX_LAG);
+}
+
+/// Asserts that the data follows the given cumulative distribution function with Pearson's
+/// chi-square test, on bins of equal size between `low` and `high`.
+///
+/// The first and last bins extend to infinity so that every value is counted, and bins with an
+/// expected count below 5 are merged with their neighbours to keep the test valid.
+pub fn assert_chi_square_goodness_of_fit<E: Numeric>(
+    data: &[E],
+    number_of_bins: usize,
+    low: f32,
+    high: f32,
+    cdf: impl Fn(f32) -> f32,
+) {
+    let range = (high - low) / number_of_bins as f32;
+    let n = data.len() as f64;
+
+    let mut observed = vec![0usize; number_of_bins];
+    for elem in data {
+        let bin = f32::floor((elem.to_f32().unwrap() - low) / range);
+        let bin = (bin.max(0.) as usize).min(number_of_bins - 1);
+        observed[bin] += 1;
+    }
+
+    let mut merged_observed = Vec::new();
+    let mut merged_expected = Vec::new();
+    let (mut pending_observed, mut pending_expected) = (0, 0.);
+    let mut previous_cdf = 0.;
+
+    for (i, count) in observed.iter().enumerate() {
+        let next_cdf = match i == number_of_bins - 1 {
+            true => 1.,
+            false => cdf(low + (i + 1) as f32 * range) as f64,
+        };
+
+        pending_observed += count;
+        pending_expected += (next_cdf - previous_cdf) * n;
+        previous_cdf = next_cdf;
+
+        if pending_expected >= 5. {
+            merged_observed.push(pending_observed);
+            merged_expected.push(pending_expected);
+            (pending_observed, pending_expected) = (0, 0.);
+        }
+    }
+    if let (Some(observed), Some(expected)) =
+        (merged_observed.last_mut(), merged_expected.last_mut())
+    {
+        *observed += pending_observed;
+        *expected += pending_expected;
+    }
+
+    assert!(
+        merged_observed.len() >= 2,
+        "Chi-square test needs at least two bins with an expected count of 5"
+    );
+
+    let statistic = chi_square_statistic(&merged_observed, &merged_expected);
+    let degrees_of_freedom = merged_observed.len() - 1;
+    let p_value = chi_square_p_value(statistic, degrees_of_freedom);
+
+    assert!(
+        p_value > SIGNIFICANCE_LEVEL,
+        "Chi-square test failed: statistic={statistic}, degrees of freedom={degrees_of_freedom}, \
+         p-value={p_value}"
+    );
+}
+
+/// Pearson's chi-square statistic of observed counts against their expected values.
+pub fn chi_square_statistic(observed: &[usize], expected: &[f64]) -> f64 {
+    observed
+        .iter()
+        .zip(expected)
+        .map(|(observed, expected)| {
+            let d = *observed as f64 - expected;
+            d * d / expected
+        })
+        .sum()
+}
+
+/// Probability for a chi-square distribution with `degrees_of_freedom` to exceed `statistic`.
+pub fn chi_square_p_value(statistic: f64, degrees_of_freedom: usize) -> f64 {
+    regularized_gamma_q(degrees_of_freedom as f64 / 2., statistic / 2.)
+}
+
+/// Asserts that the data follows the given continuous cumulative distribution function with the
+/// Kolmogorov-Smirnov test.
+pub fn assert_kolmogorov_smirnov<E: Numeric>(data: &[E], cdf: impl Fn(f32) -> f32) {
+    let statistic = kolmogorov_smirnov_statistic(data, cdf);
+    let p_value = kolmogorov_smirnov_p_value(statistic, data.len());
+
+    assert!(
+        p_value > SIGNIFICANCE_LEVEL,
+        "Kolmogorov-Smirnov test failed: statistic={statistic}, n={}, p-value={p_value}",
+        data.len()
+    );
+}
+
+/// Largest distance between the empirical cumulative distribution function of the data and the
+/// given one.
+pub fn kolmogorov_smirnov_statistic<E: Numeric>(data: &[E], cdf: impl Fn(f32) -> f32) -> f64 {
+    let mut sorted: Vec<f32> = data.iter().map(|e| e.to_f32().unwrap()).collect();
+    sorted.sort_by(f32::total_cmp);
+
+    let n = sorted.len() as f64;
+    let mut statistic = 0f64;
+
+    for (i, value) in sorted.iter().enumerate() {
+        let expected = cdf(*value) as f64;
+        statistic = statistic
+            .max(expected - i as f64 / n)
+            .max((i + 1) as f64 / n - expected);
+    }
+
+    statistic
+}
+
+/// Asymptotic probability for the Kolmogorov-Smirnov statistic of `n` values to exceed
+/// `statistic`, with Stephens' correction for finite samples.
+pub fn kolmogorov_smirnov_p_value(statistic: f64, n: usize) -> f64 {
+    let sqrt_n = f64::sqrt(n as f64);
+    let lambda = (sqrt_n + 0.12 + 0.11 / sqrt_n) * statistic;
+
+    // The series converges too slowly to be useful there, and the p-value is 1 anyway
+    if lambda < 0.2 {
+        return 1.;
+    }
+
+    let mut p_value = 0.;
+    let mut sign = 1.;
+    for j in 1..=100 {
+        let j = j as f64;
+        p_value += sign * 2. * f64::exp(-2. * j * j * lambda * lambda);
+        sign = -sign;
+    }
+
+    p_value.clamp(0., 1.)
+}
+
+/// Asserts that the Pearson correlation between values at a distance of every lag from 1 to
+/// `max_lag` is not significant.
+///
+/// Values of adjacent lanes and of units with adjacent positions are next to each other in the
+/// output, so this catches streams seeded too similarly.
+pub fn assert_no_serial_correlation<E: Numeric>(data: &[E], max_lag: usize) {
+    for lag in 1..=max_lag {
+        let correlation = serial_correlation(data, lag);
+
+        // Without correlation, the coefficient is approximately normal with a variance of 1 / n
+        let z = correlation * f64::sqrt((data.len() - lag) as f64);
+        let p_value = erfc(z.abs() / std::f64::consts::SQRT_2);
+
+        assert!(
+            p_value > SIGNIFICANCE_LEVEL,
+            "Serial correlation test failed: lag={lag}, correlation={correlation}, \
+             p-value={p_value}"
+        );
+    }
+}
+
+/// Pearson correlation coefficient between the data and itself shifted by `lag`.
+pub fn serial_correlation<E: Numeric>(data: &[E], lag: usize) -> f64 {
+    let values: Vec<f64> = data.iter().map(|e| e.to_f64().unwrap()).collect();
+    let first = &values[..values.len() - lag];
+    let second = &values[lag..];
+
+    let n = first.len() as f64;
+    let mean_first = first.iter().sum::<f64>() / n;
+    let mean_second = second.iter().sum::<f64>() / n;
+
+    let (mut covariance, mut variance_first, mut variance_second) = (0., 0., 0.);
+    for (a, b) in first.iter().zip(second) {
+        let (a, b) = (a - mean_first, b - mean_second);
+        covariance += a * b;
+        variance_first += a * a;
+        variance_second += b * b;
+    }
+
+    covariance / f64::sqrt(variance_first * variance_second)
+}
+
+/// Asserts that data uniform in `[low, high)` passes Marsaglia's birthday spacings test.
+///
+/// Consecutive chunks of 512 values are mapped to birthdays in a year of `2^22` days. The number
+/// of repeated spacings between sorted birthdays of a chunk is approximately Poisson with a mean
+/// of `512^3 / (4 * 2^22) = 8`, so their total over all chunks is compared with a Poisson
+/// distribution. Generators with a lattice structure repeat spacings far more often.
+pub fn assert_birthday_spacings<E: Numeric>(data: &[E], low: f32, high: f32) {
+    const BIRTHDAYS: usize = 512;
+    const DAYS: f64 = (1 << 22) as f64;
+
+    let chunks = data.chunks_exact(BIRTHDAYS);
+    let num_chunks = chunks.len();
+    assert!(
+        num_chunks > 0,
+        "Birthday spacings test needs at least {BIRTHDAYS} values"
+    );
+
+    let mut repeated = 0usize;
+    for chunk in chunks {
+        let mut birthdays: Vec<u64> = chunk
+            .iter()
+            .map(|e| {
+                let u = (e.to_f64().unwrap() - low as f64) / (high - low) as f64;
+                (u * DAYS).clamp(0., DAYS - 1.) as u64
+            })
+            .collect();
+        birthdays.sort_unstable();
+
+        let mut spacings: Vec<u64> = birthdays.windows(2).map(|w| w[1] - w[0]).collect();
+        spacings.sort_unstable();
+
+        repeated += spacings.windows(2).filter(|w| w[0] == w[1]).count();
+    }
+
+    let birthdays = BIRTHDAYS as f64;
+    let expected = num_chunks as f64 * birthdays * birthdays * birthdays / (4. * DAYS);
+
+    // Two-sided p-value, with P(X <= k) = Q(k + 1, mean) and P(X >= k) = P(k, mean)
+    let lower = regularized_gamma_q(repeated as f64 + 1., expected);
+    let upper = match repeated {
+        0 => 1.,
+        repeated => regularized_gamma_p(repeated as f64, expected),
+    };
+    let p_value = f64::min(1., 2. * f64::min(lower, upper));
+
+    assert!(
+        p_value > SIGNIFICANCE_LEVEL,
+        "Birthday spacings test failed: repeated spacings={repeated}, expected={expected}, \
+         p-value={p_value}"
+    );
+}
+
+/// Cumulative distribution function of the normal distribution.
+pub fn normal_cdf(x: f32, mean: f32, std: f32) -> f32 {
+    let z = (x as f64 - mean as f64) / (std as f64 * std::f64::consts::SQRT_2);
+    (0.5 * erfc(-z)) as f32
+}
+
+/// Regularized lower incomplete gamma function `P(a, x)`, which is also the cumulative
+/// distribution function of the gamma distribution with shape `a` and unit scale.
+pub fn regularized_gamma_p(a: f64, x: f64) -> f64 {
+    1. - regularized_gamma_q(a, x)
+}
+
+/// Regularized upper incomplete gamma function `Q(a, x) = 1 - P(a, x)`.
+pub fn regularized_gamma_q(a: f64, x: f64) -> f64 {
+    if x <= 0. {
+        return 1.;
+    }
+
+    let log_prefactor = a * x.ln() - x - ln_gamma(a);
+
+    if x < a + 1. {
+        // Series for P(a, x)
+        let mut term = 1. / a;
+        let mut sum = term;
+        let mut denominator = a;
+        for _ in 0..1000 {
+            denominator += 1.;
+            term *= x / denominator;
+            sum += term;
+            if term.abs() < sum.abs() * 1e-15 {
+                break;
+            }
+        }
+
+        1. - sum * f64::exp(log_prefactor)
+    } else {
+        // Continued fraction for Q(a, x), with the modified Lentz method
+        let tiny = 1e-300;
+        let mut b = x + 1. - a;
+        let mut c = 1. / tiny;
+        let mut d = 1. / b;
+        let mut fraction = d;
+        for i in 1..1000 {
+            let an = -(i as f64) * (i as f64 - a);
+            b += 2.;
+            d = an * d + b;
+            if d.abs() < tiny {
+                d = tiny;
+            }
+            c = b + an / c;
+            if c.abs() < tiny {
+                c = tiny;
+            }
+            d = 1. / d;
+            let delta = d * c;
+            fraction *= delta;
+            if (delta - 1.).abs() < 1e-15 {
+                break;
+            }
+        }
+
+        f64::exp(log_prefactor) * fraction
+    }
+}
+
+/// Natural logarithm of the gamma function, with the Lanczos approximation.
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 6] = [
+        76.18009172947146,
+        -86.50532032941677,
+        24.01409824083091,
+        -1.231739572450155,
+        0.1208650973866179e-2,
+        -0.5395239384953e-5,
+    ];
+
+    let tmp = x + 5.5;
+    let tmp = tmp - (x + 0.5) * tmp.ln();
+    let mut series = 1.000000000190015;
+    for (i, coefficient) in COEFFICIENTS.iter().enumerate() {
+        series += coefficient / (x + 1. + i as f64);
+    }
+
+    -tmp + f64::ln(2.5066282746310005 * series / x)
+}
+
+/// Complementary error function, with a relative error below `1.2e-7` everywhere.
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1. / (1. + 0.5 * z);
+    let polynomial = -z * z - 1.26551223
+        + t * (1.00002368
+            + t * (0.37409196
+                + t * (0.09678418
+                    + t * (-0.18628806
+                        + t * (0.27886807
+                            + t * (-1.13520398
+                                + t * (1.48851587 + t * (-0.82215223 + t * 0.17087277))))))));
+    let result = t * f64::exp(polynomial);
+
+    if x >= 0. { result } else { 2. - result }
+}
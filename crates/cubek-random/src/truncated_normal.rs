@@ -0,0 +1,160 @@
+use cubecl::prelude::*;
+use cubecl::std::tensor::View;
+use std::f32::consts::SQRT_2;
+
+use super::{PrngArgs, PrngRuntime, random};
+
+use crate::{RandomFamily, RandomGenerator, next_random_word, to_unit_interval_open};
+
+#[derive(CubeLaunch, CubeType)]
+pub(crate) struct TruncatedNormal {
+    mean: f32,
+    std: f32,
+    low: f32,
+    high: f32,
+}
+
+#[derive(Debug)]
+struct TruncatedNormalFamily;
+
+impl RandomFamily for TruncatedNormalFamily {
+    type Runtime = TruncatedNormal;
+}
+
+#[cube]
+impl PrngRuntime for TruncatedNormal {
+    fn inner_loop<E: Numeric>(
+        args: TruncatedNormal,
+        write_index_base: usize,
+        n_invocations: u32,
+        #[comptime] n_values_per_thread: usize,
+        #[comptime] line_size: LineSize,
+        state_0: &mut Line<u32>,
+        state_1: &mut Line<u32>,
+        state_2: &mut Line<u32>,
+        state_3: &mut Line<u32>,
+        output: &mut View<Line<E>, usize, ReadWrite>,
+    ) {
+        let mean = args.mean;
+        let low = args.low;
+        let high = args.high;
+        let scale = args.std * SQRT_2;
+
+        // Bounds of the truncation, mapped to the erf domain
+        let erf_low = ((low - mean) / scale).erf();
+        let erf_high = ((high - mean) / scale).erf();
+        let erf_range = erf_high - erf_low;
+
+        let mut output_line = Line::empty(line_size);
+
+        let num_iterations = n_values_per_thread / line_size;
+        #[unroll(num_iterations <= 8)]
+        for line_index in 0..num_iterations {
+            // vectorization
+            #[unroll]
+            for i in 0..line_size {
+                let int_random = next_random_word(state_0, state_1, state_2, state_3, i);
+                let unit = to_unit_interval_open(int_random);
+
+                // Inverse transform sampling of the normal CDF restricted to [low, high]
+                let erf_value = unit * erf_range + erf_low;
+                let normal = erf_inv(erf_value) * scale + mean;
+
+                // Guard against rounding errors at the edges of the interval
+                output_line[i] = E::cast_from(clamp(normal, low, high));
+            }
+
+            let write_index = line_index * n_invocations as usize + write_index_base;
+
+            output.write_checked(write_index, output_line);
+        }
+    }
+}
+
+/// Inverse of the error function, for `x` in `(-1, 1)`.
+///
+/// Single precision approximation from M. Giles, "Approximating the erfinv function", GPU
+/// Computing Gems Jade Edition, 2011.
+#[cube]
+pub fn erf_inv(x: f32) -> f32 {
+    let w = -f32::ln((1.0 - x) * (1.0 + x));
+
+    let mut p = 0.0f32;
+    if w < 5.0 {
+        let w = w - 2.5;
+        p = 2.8102264e-8;
+        p = 3.4327394e-7 + p * w;
+        p = -3.5233877e-6 + p * w;
+        p = -4.3915065e-6 + p * w;
+        p = 0.00021858087 + p * w;
+        p = -0.0012537250 + p * w;
+        p = -0.0041776816 + p * w;
+        p = 0.24664073 + p * w;
+        p = 1.5014094 + p * w;
+    } else {
+        let w = f32::sqrt(w) - 3.0;
+        p = -0.00020021426;
+        p = 0.00010095056 + p * w;
+        p = 0.0013493432 + p * w;
+        p = -0.0036734284 + p * w;
+        p = 0.0057395077 + p * w;
+        p = -0.0076224613 + p * w;
+        p = 0.0094388705 + p * w;
+        p = 1.0016741 + p * w;
+        p = 2.8329768 + p * w;
+    }
+
+    p * x
+}
+
+impl PrngArgs for TruncatedNormal {
+    type Args = Self;
+
+    fn args<'a, R: Runtime>(self) -> TruncatedNormalLaunch<'a, R> {
+        TruncatedNormalLaunch::new(
+            ScalarArg::new(self.mean),
+            ScalarArg::new(self.std),
+            ScalarArg::new(self.low),
+            ScalarArg::new(self.high),
+        )
+    }
+}
+
+/// Pseudo-random generator with normal distribution, truncated to `[low, high]`.
+///
+/// Values are sampled with the inverse CDF method, so no value is ever rejected. Precision degrades
+/// when the whole interval lies far in a tail, more than about 5 standard deviations from the mean.
+#[allow(clippy::too_many_arguments)]
+pub fn random_truncated_normal<R: Runtime>(
+    client: &ComputeClient<R>,
+    generator: &mut RandomGenerator,
+    mean: f32,
+    std: f32,
+    low: f32,
+    high: f32,
+    out: TensorHandleRef<R>,
+    dtype: StorageType,
+) -> Result<(), LaunchError> {
+    assert_eq!(
+        out.elem_size,
+        dtype.size(),
+        "Tensor element type must be the same as type E"
+    );
+    assert!(
+        low < high,
+        "Truncation interval must not be empty, got [{low}, {high}]"
+    );
+
+    random::<TruncatedNormalFamily, R>(
+        client,
+        generator,
+        TruncatedNormal {
+            mean,
+            std,
+            low,
+            high,
+        },
+        out,
+        dtype,
+    )
+}
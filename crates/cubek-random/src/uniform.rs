@@ -2,7 +2,8 @@ use cubecl::prelude::*;
 use cubecl::std::tensor::View;
 
 use crate::{
-    RandomFamily, lcg_step, taus_step_0, taus_step_1, taus_step_2, to_unit_interval_closed_open,
+    PhiloxFamily, PhiloxRuntime, RandomFamily, RandomGenerator, next_random_word, random_philox,
+    to_unit_interval_closed_open,
 };
 
 use super::{PrngArgs, PrngRuntime, random};
@@ -20,6 +21,10 @@ impl RandomFamily for UniformFamily {
     type Runtime = Uniform;
 }
 
+impl PhiloxFamily for UniformFamily {
+    type Runtime = Uniform;
+}
+
 #[cube]
 impl PrngRuntime for Uniform {
     fn inner_loop<E: Numeric>(
@@ -28,10 +33,10 @@ impl PrngRuntime for Uniform {
         n_invocations: u32,
         #[comptime] n_values_per_thread: usize,
         #[comptime] line_size: LineSize,
-        state_0: &mut u32,
-        state_1: &mut u32,
-        state_2: &mut u32,
-        state_3: &mut u32,
+        state_0: &mut Line<u32>,
+        state_1: &mut Line<u32>,
+        state_2: &mut Line<u32>,
+        state_3: &mut Line<u32>,
         output: &mut View<Line<E>, usize, ReadWrite>,
     ) {
         let lower_bound = args.lower_bound;
@@ -47,12 +52,7 @@ impl PrngRuntime for Uniform {
             // vectorization
             #[unroll]
             for i in 0..line_size {
-                *state_0 = taus_step_0(*state_0);
-                *state_1 = taus_step_1(*state_1);
-                *state_2 = taus_step_2(*state_2);
-                *state_3 = lcg_step(*state_3);
-
-                let int_random = *state_0 ^ *state_1 ^ *state_2 ^ *state_3;
+                let int_random = next_random_word(state_0, state_1, state_2, state_3, i);
                 let f32_random = to_unit_interval_closed_open(int_random);
 
                 let f32_uniform = f32_random * f32::cast_from(scale) + f32::cast_from(lower_bound);
@@ -64,8 +64,28 @@ impl PrngRuntime for Uniform {
 
             let write_index = line_index * n_invocations as usize + write_index_base;
 
-            output[write_index] = output_line;
+            output.write_checked(write_index, output_line);
+        }
+    }
+}
+
+#[cube]
+impl PhiloxRuntime for Uniform {
+    fn transform_block<E: Numeric>(args: Uniform, words: Line<u32>) -> Line<E> {
+        let scale = args.upper_bound - args.lower_bound;
+        let line_size = words.line_size();
+
+        let mut output_line = Line::empty(line_size);
+
+        #[unroll]
+        for i in 0..line_size {
+            let f32_random = to_unit_interval_closed_open(words[i]);
+            let f32_uniform = f32_random * scale + args.lower_bound;
+
+            output_line[i] = E::cast_from(f32_uniform);
         }
+
+        output_line
     }
 }
 
@@ -83,6 +103,7 @@ impl PrngArgs for Uniform {
 /// Pseudo-random generator with uniform distribution
 pub fn random_uniform<R: Runtime>(
     client: &ComputeClient<R>,
+    generator: &mut RandomGenerator,
     lower_bound: f32,
     upper_bound: f32,
     out: TensorHandleRef<R>,
@@ -96,6 +117,40 @@ pub fn random_uniform<R: Runtime>(
 
     random::<UniformFamily, R>(
         client,
+        generator,
+        Uniform {
+            lower_bound,
+            upper_bound,
+        },
+        out,
+        dtype,
+    )
+}
+
+/// Counter-based pseudo-random generator with uniform distribution.
+///
+/// The output only depends on `seed`, `offset` and the element index, so the same pair always
+/// produces the same values. Use a different `offset` for every launch sharing a `seed`.
+#[allow(clippy::too_many_arguments)]
+pub fn random_uniform_philox<R: Runtime>(
+    client: &ComputeClient<R>,
+    seed: u64,
+    offset: u64,
+    lower_bound: f32,
+    upper_bound: f32,
+    out: TensorHandleRef<R>,
+    dtype: StorageType,
+) -> Result<(), LaunchError> {
+    assert_eq!(
+        out.elem_size,
+        dtype.size(),
+        "Tensor element type must be the same as type E"
+    );
+
+    random_philox::<UniformFamily, R>(
+        client,
+        seed,
+        offset,
         Uniform {
             lower_bound,
             upper_bound,
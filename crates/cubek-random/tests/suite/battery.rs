@@ -0,0 +1,201 @@
+use cubecl::TestRuntime;
+use cubecl::prelude::*;
+use cubecl::std::tensor::TensorHandle;
+use cubek_random::*;
+
+use LINE_SIZE_SHAPES;
+
+#[test]
+fn uniform_passes_battery() {
+    for shape in LINE_SIZE_SHAPES {
+        let output_data = get_random_data(&shape, |client, generator, out, dtype| {
+            random_uniform(client, generator, 0., 1., out, dtype)
+        });
+
+        assert_passes_continuous_battery(&output_data, |x| x);
+    }
+}
+
+#[test]
+fn uniform_philox_passes_battery() {
+    for shape in LINE_SIZE_SHAPES {
+        let output_data = get_random_data(&shape, |client, _, out, dtype| {
+            random_uniform_philox(client, 0, 0, 0., 1., out, dtype)
+        });
+
+        assert_passes_continuous_battery(&output_data, |x| x);
+    }
+}
+
+#[test]
+fn normal_passes_battery() {
+    for shape in LINE_SIZE_SHAPES {
+        let output_data = get_random_data(&shape, |client, generator, out, dtype| {
+            random_normal(client, generator, 2., 3., out, dtype)
+        });
+
+        assert_passes_continuous_battery(&output_data, |x| normal_cdf(x, 2., 3.));
+    }
+}
+
+#[test]
+fn normal_philox_passes_battery() {
+    for shape in LINE_SIZE_SHAPES {
+        let output_data = get_random_data(&shape, |client, _, out, dtype| {
+            random_normal_philox(client, 0, 0, 0., 1., out, dtype)
+        });
+
+        assert_passes_continuous_battery(&output_data, |x| normal_cdf(x, 0., 1.));
+    }
+}
+
+#[test]
+fn truncated_normal_passes_battery() {
+    let (low, high) = (-1., 2.);
+    let mass = normal_cdf(high, 0., 1.) - normal_cdf(low, 0., 1.);
+
+    for shape in LINE_SIZE_SHAPES {
+        let output_data = get_random_data(&shape, |client, generator, out, dtype| {
+            random_truncated_normal(client, generator, 0., 1., low, high, out, dtype)
+        });
+
+        assert_passes_continuous_battery(&output_data, |x| {
+            ((normal_cdf(x, 0., 1.) - normal_cdf(low, 0., 1.)) / mass).clamp(0., 1.)
+        });
+    }
+}
+
+#[test]
+fn exponential_passes_battery() {
+    for shape in LINE_SIZE_SHAPES {
+        let output_data = get_random_data(&shape, |client, generator, out, dtype| {
+            random_exponential(client, generator, 1.5, out, dtype)
+        });
+
+        assert_passes_continuous_battery(&output_data, |x| 1. - f32::exp(-1.5 * x));
+    }
+}
+
+#[test]
+fn laplace_passes_battery() {
+    let (loc, scale) = (1., 2.);
+
+    for shape in LINE_SIZE_SHAPES {
+        let output_data = get_random_data(&shape, |client, generator, out, dtype| {
+            random_laplace(client, generator, loc, scale, out, dtype)
+        });
+
+        assert_passes_continuous_battery(&output_data, |x| match x < loc {
+            true => 0.5 * f32::exp((x - loc) / scale),
+            false => 1. - 0.5 * f32::exp(-(x - loc) / scale),
+        });
+    }
+}
+
+#[test]
+fn cauchy_passes_battery() {
+    let (loc, scale) = (-1., 0.5);
+
+    for shape in LINE_SIZE_SHAPES {
+        let output_data = get_random_data(&shape, |client, generator, out, dtype| {
+            random_cauchy(client, generator, loc, scale, out, dtype)
+        });
+
+        assert_passes_continuous_battery(&output_data, |x| {
+            0.5 + f32::atan((x - loc) / scale) / std::f32::consts::PI
+        });
+    }
+}
+
+#[test]
+fn gamma_passes_battery() {
+    let (shape_param, scale) = (2.5, 2.);
+
+    for shape in LINE_SIZE_SHAPES {
+        let output_data = get_random_data(&shape, |client, generator, out, dtype| {
+            random_gamma(client, generator, shape_param, scale, out, dtype)
+        });
+
+        assert_passes_continuous_battery(&output_data, |x| {
+            regularized_gamma_p(shape_param as f64, x.max(0.) as f64 / scale as f64) as f32
+        });
+    }
+}
+
+#[test]
+fn beta_passes_battery() {
+    for shape in LINE_SIZE_SHAPES {
+        let output_data = get_random_data(&shape, |client, generator, out, dtype| {
+            random_beta(client, generator, 2., 1., out, dtype)
+        });
+
+        // Beta(2, 1) has density 2x on [0, 1]
+        assert_passes_continuous_battery(&output_data, |x| x.clamp(0., 1.).powi(2));
+    }
+}
+
+#[test]
+fn bernoulli_passes_battery() {
+    let prob = 0.3;
+
+    for shape in LINE_SIZE_SHAPES {
+        let output_data = get_random_data(&shape, |client, generator, out, dtype| {
+            random_bernoulli(client, generator, prob, out, dtype)
+        });
+
+        assert_passes_discrete_battery(&output_data, 0, 1, |x| match x {
+            x if x < 0. => 0.,
+            x if x < 1. => 1. - prob,
+            _ => 1.,
+        });
+    }
+}
+
+#[test]
+fn poisson_passes_battery() {
+    let lambda = 4.;
+
+    for shape in LINE_SIZE_SHAPES {
+        let output_data = get_random_data(&shape, |client, generator, out, dtype| {
+            random_poisson(client, generator, lambda, out, dtype)
+        });
+
+        // P(X <= k) = Q(k + 1, lambda)
+        assert_passes_discrete_battery(&output_data, 0, 15, |x| match x < 0. {
+            true => 0.,
+            false => regularized_gamma_q(x.floor() as f64 + 1., lambda as f64) as f32,
+        });
+    }
+}
+
+fn get_random_data(
+    shape: &[usize],
+    launch: impl Fn(
+        &ComputeClient<TestRuntime>,
+        &mut RandomGenerator,
+        TensorHandleRef<TestRuntime>,
+        StorageType,
+    ) -> Result<(), LaunchError>,
+) -> Vec<TestDType> {
+    let mut generator = RandomGenerator::new(0);
+
+    let client = TestRuntime::client(&Default::default());
+    let output = TensorHandle::empty(
+        &client,
+        shape.to_vec(),
+        TestDType::as_type_native_unchecked(),
+    );
+
+    launch(
+        &client,
+        &mut generator,
+        output.as_ref(),
+        TestDType::as_type_native_unchecked(),
+    )
+    .unwrap();
+
+    let output_data = client.read_one_tensor(output.as_copy_descriptor());
+    let output_data = TestDType::from_bytes(&output_data);
+
+    output_data.to_owned()
+}
@@ -3,6 +3,8 @@ use cubecl::prelude::*;
 use cubecl::std::tensor::TensorHandle;
 use cubek_random::*;
 
+use crate::suite::LINE_SIZE_SHAPES;
+
 #[test]
 fn number_of_1_proportional_to_prob_f32() {
     let shape = &[40, 40];
@@ -33,8 +35,17 @@ fn wald_wolfowitz_runs_test() {
     assert_wald_wolfowitz_runs_test(&output_data, 0., 1.1);
 }
 
+#[test]
+fn wald_wolfowitz_runs_test_every_line_size() {
+    for shape in LINE_SIZE_SHAPES {
+        let output_data = get_random_bernoulli_data(&shape, 0.5);
+
+        assert_wald_wolfowitz_runs_test(&output_data, 0., 1.1);
+    }
+}
+
 fn get_random_bernoulli_data(shape: &[usize], prob: f32) -> Vec<TestDType> {
-    seed(0);
+    let mut generator = RandomGenerator::new(0);
 
     let client = TestRuntime::client(&Default::default());
     let output = TensorHandle::empty(
@@ -45,6 +56,7 @@ fn get_random_bernoulli_data(shape: &[usize], prob: f32) -> Vec<TestDType> {
 
     random_bernoulli(
         &client,
+        &mut generator,
         prob,
         output.as_ref(),
         TestDType::as_type_native_unchecked(),
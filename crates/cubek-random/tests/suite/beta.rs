@@ -0,0 +1,52 @@
+use cubecl::TestRuntime;
+use cubecl::prelude::*;
+use cubecl::std::tensor::TensorHandle;
+use cubek_random::*;
+
+#[test]
+fn beta_mean_and_variance() {
+    let (alpha, beta) = (2., 5.);
+    let output_data = get_random_beta_data(&[1000, 1000], alpha, beta);
+
+    for e in output_data.iter() {
+        assert!((0. ..=1.).contains(e), "Not in range, got {e}");
+    }
+
+    let sum = alpha + beta;
+    let mean = alpha / sum;
+    let variance = alpha * beta / (sum * sum * (sum + 1.));
+    assert_mean_and_variance_approx_equal(&output_data, mean, variance);
+}
+
+#[test]
+fn beta_one_one_is_uniform() {
+    let output_data = get_random_beta_data(&[1000, 1000], 1., 1.);
+
+    assert_respects_cdf(&output_data, 20, 0., 1., |x| x);
+}
+
+fn get_random_beta_data(shape: &[usize], alpha: f32, beta: f32) -> Vec<TestDType> {
+    let mut generator = RandomGenerator::new(0);
+
+    let client = TestRuntime::client(&Default::default());
+    let output = TensorHandle::empty(
+        &client,
+        shape.to_vec(),
+        TestDType::as_type_native_unchecked(),
+    );
+
+    random_beta(
+        &client,
+        &mut generator,
+        alpha,
+        beta,
+        output.as_ref(),
+        TestDType::as_type_native_unchecked(),
+    )
+    .unwrap();
+
+    let output_data = client.read_one_tensor(output.as_copy_descriptor());
+    let output_data = TestDType::from_bytes(&output_data);
+
+    output_data.to_owned()
+}
@@ -0,0 +1,209 @@
+use cubecl::TestRuntime;
+use cubecl::prelude::*;
+use cubecl::std::tensor::TensorHandle;
+use cubek_random::*;
+
+const PROBABILITIES: [f32; 5] = [0.1, 0.2, 0.3, 0.4, 0.0];
+
+#[test]
+fn categorical_probabilities_follow_distribution() {
+    let samples = sample_categorical::<u32>(
+        &PROBABILITIES,
+        CategoricalInput::Probabilities,
+        None,
+        1000,
+        100,
+        true,
+    );
+
+    assert_frequencies(&samples, &PROBABILITIES);
+}
+
+#[test]
+fn categorical_logits_follow_distribution() {
+    let logits = PROBABILITIES.map(f32::ln);
+    let samples =
+        sample_categorical::<u32>(&logits, CategoricalInput::Logits, None, 1000, 100, true);
+
+    assert_frequencies(&samples, &PROBABILITIES);
+}
+
+#[test]
+fn categorical_unnormalized_probabilities() {
+    let weights = PROBABILITIES.map(|p| p * 7.);
+    let samples = sample_categorical::<u32>(
+        &weights,
+        CategoricalInput::Probabilities,
+        None,
+        1000,
+        100,
+        true,
+    );
+
+    assert_frequencies(&samples, &PROBABILITIES);
+}
+
+#[test]
+fn categorical_without_replacement_is_distinct() {
+    let samples = sample_categorical::<u32>(
+        &PROBABILITIES[..4],
+        CategoricalInput::Probabilities,
+        None,
+        256,
+        4,
+        false,
+    );
+
+    for row in samples.chunks(4) {
+        let mut sorted = row.to_vec();
+        sorted.sort();
+        assert_eq!(sorted, [0, 1, 2, 3], "Row {row:?} is not a permutation");
+    }
+}
+
+#[test]
+fn categorical_without_replacement_first_follows_distribution() {
+    let samples = sample_categorical::<u32>(
+        &PROBABILITIES,
+        CategoricalInput::Probabilities,
+        None,
+        100_000,
+        2,
+        false,
+    );
+    let first: Vec<u32> = samples.chunks(2).map(|row| row[0]).collect();
+
+    assert_frequencies(&first, &PROBABILITIES);
+}
+
+#[test]
+fn categorical_respects_mask() {
+    let mask = [1u32, 0, 1, 0, 1];
+    let samples = sample_categorical::<u32>(
+        &PROBABILITIES,
+        CategoricalInput::Logits,
+        Some(&mask),
+        1000,
+        10,
+        true,
+    );
+
+    for index in samples {
+        assert!(mask[index as usize] != 0, "Sampled masked index {index}");
+    }
+}
+
+#[test]
+fn categorical_fully_masked_row() {
+    let mask = [0u32; 5];
+    let samples = sample_categorical::<u32>(
+        &PROBABILITIES,
+        CategoricalInput::Probabilities,
+        Some(&mask),
+        16,
+        10,
+        true,
+    );
+
+    assert!(samples.iter().all(|index| *index == u32::MAX));
+}
+
+#[test]
+fn categorical_i64_output() {
+    let logits = PROBABILITIES.map(f32::ln);
+    let samples =
+        sample_categorical::<i64>(&logits, CategoricalInput::Logits, None, 1000, 100, true);
+    let samples: Vec<u32> = samples.iter().map(|index| *index as u32).collect();
+
+    assert_frequencies(&samples, &PROBABILITIES);
+}
+
+#[test]
+fn categorical_large_vocab() {
+    // More categories than units in a cube, with all the mass on a single one
+    let mut probabilities = vec![0.0; 50_000];
+    probabilities[31_337] = 1.0;
+
+    let samples = sample_categorical::<u32>(
+        &probabilities,
+        CategoricalInput::Probabilities,
+        None,
+        4,
+        8,
+        true,
+    );
+
+    assert!(samples.iter().all(|index| *index == 31_337));
+}
+
+fn assert_frequencies(samples: &[u32], probabilities: &[f32]) {
+    let n = samples.len() as f32;
+    let mut counts = vec![0usize; probabilities.len()];
+    for index in samples {
+        counts[*index as usize] += 1;
+    }
+
+    for (index, (count, p)) in counts.iter().zip(probabilities).enumerate() {
+        let expected = n * p;
+        let tolerance = 5. * f32::sqrt(n * p * (1. - p)) + 1.;
+        assert!(
+            (*count as f32 - expected).abs() <= tolerance,
+            "Category {index} sampled {count} times, expected {expected}"
+        );
+    }
+}
+
+fn sample_categorical<I: Numeric + CubeElement>(
+    row: &[f32],
+    kind: CategoricalInput,
+    mask: Option<&[u32]>,
+    batch: usize,
+    num_samples: usize,
+    replacement: bool,
+) -> Vec<I> {
+    let mut generator = RandomGenerator::new(0);
+    let client = TestRuntime::client(&Default::default());
+
+    let vocab = row.len();
+    let input_data: Vec<f32> = row.iter().copied().cycle().take(batch * vocab).collect();
+    let input = TensorHandle::new_contiguous(
+        vec![batch, vocab],
+        client.create_from_slice(f32::as_bytes(&input_data)),
+        f32::as_type_native_unchecked(),
+    );
+
+    let mask = mask.map(|mask| {
+        let mask_data: Vec<u32> = mask.iter().copied().cycle().take(batch * vocab).collect();
+        TensorHandle::new_contiguous(
+            vec![batch, vocab],
+            client.create_from_slice(u32::as_bytes(&mask_data)),
+            u32::as_type_native_unchecked(),
+        )
+    });
+
+    let output = TensorHandle::empty(
+        &client,
+        vec![batch, num_samples],
+        I::as_type_native_unchecked(),
+    );
+
+    random_categorical(
+        &client,
+        &mut generator,
+        input.as_ref(),
+        kind,
+        mask.as_ref().map(|mask| mask.as_ref()),
+        replacement,
+        output.as_ref(),
+        CategoricalDtypes {
+            input: f32::as_type_native_unchecked(),
+            mask: u32::as_type_native_unchecked(),
+            output: I::as_type_native_unchecked(),
+        },
+    )
+    .unwrap();
+
+    let output_data = client.read_one_tensor(output.as_copy_descriptor());
+
+    I::from_bytes(&output_data).to_owned()
+}
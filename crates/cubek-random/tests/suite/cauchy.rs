@@ -0,0 +1,41 @@
+use cubecl::TestRuntime;
+use cubecl::prelude::*;
+use cubecl::std::tensor::TensorHandle;
+use cubek_random::*;
+
+#[test]
+fn cauchy_respects_cdf() {
+    let (loc, scale) = (-1., 0.5);
+    let output_data = get_random_cauchy_data(&[1000, 1000], loc, scale);
+
+    // Values outside of the bins are ignored, the mean and variance are undefined.
+    assert_respects_cdf(&output_data, 40, -6., 4., |x| {
+        0.5 + f32::atan((x - loc) / scale) / core::f32::consts::PI
+    });
+}
+
+fn get_random_cauchy_data(shape: &[usize], loc: f32, scale: f32) -> Vec<TestDType> {
+    let mut generator = RandomGenerator::new(0);
+
+    let client = TestRuntime::client(&Default::default());
+    let output = TensorHandle::empty(
+        &client,
+        shape.to_vec(),
+        TestDType::as_type_native_unchecked(),
+    );
+
+    random_cauchy(
+        &client,
+        &mut generator,
+        loc,
+        scale,
+        output.as_ref(),
+        TestDType::as_type_native_unchecked(),
+    )
+    .unwrap();
+
+    let output_data = client.read_one_tensor(output.as_copy_descriptor());
+    let output_data = TestDType::from_bytes(&output_data);
+
+    output_data.to_owned()
+}
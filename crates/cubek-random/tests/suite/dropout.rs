@@ -0,0 +1,148 @@
+use cubecl::TestRuntime;
+use cubecl::prelude::*;
+use cubecl::std::tensor::TensorHandle;
+use cubek_random::*;
+
+#[test]
+fn dropout_scales_kept_values() {
+    let p = 0.3;
+    let input: Vec<TestDType> = (0..10_000).map(|i| (i % 7 + 1) as TestDType).collect();
+    let (output, _) = get_dropout_data(&input, p, false);
+
+    for (x, y) in input.iter().zip(output.iter()) {
+        assert!(
+            *y == 0. || (y - x / (1. - p)).abs() < 1e-4 * y.abs(),
+            "Expected 0 or {}, got {y}",
+            x / (1. - p)
+        );
+    }
+}
+
+#[test]
+fn dropout_drops_with_probability_p() {
+    let p = 0.2;
+    let input = vec![1.; 1_000_000];
+    let (output, _) = get_dropout_data(&input, p, false);
+
+    let dropped = output.iter().filter(|y| **y == 0.).count() as f32;
+    let expected = 1_000_000. * p;
+    let tolerance = 5. * f32::sqrt(1_000_000. * p * (1. - p));
+
+    assert!(
+        (dropped - expected).abs() < tolerance,
+        "Dropped {dropped} values, expected {expected}"
+    );
+}
+
+#[test]
+fn dropout_mask_matches_output() {
+    // Not a multiple of 32, to check the padding bits of the last word
+    let num_elems = 10_001;
+    let input = vec![1.; num_elems];
+    let (output, mask) = get_dropout_data(&input, 0.5, true);
+    let mask = mask.unwrap();
+
+    assert_eq!(mask.len(), num_elems.div_ceil(32));
+    for (i, y) in output.iter().enumerate() {
+        let kept = mask[i / 32] & (1 << (i % 32)) != 0;
+        assert_eq!(kept, *y != 0., "Mask mismatch at {i}");
+    }
+    assert_eq!(mask[mask.len() - 1] >> (num_elems % 32), 0);
+}
+
+#[test]
+fn dropout_zero_probability_is_identity() {
+    let input: Vec<TestDType> = (0..1000).map(|i| i as TestDType).collect();
+    let (output, _) = get_dropout_data(&input, 0., false);
+
+    assert_eq!(input, output);
+}
+
+#[test]
+fn dropout_of_no_elements_is_empty() {
+    let (output, mask) = get_dropout_data(&[], 0.5, true);
+
+    assert!(output.is_empty());
+    assert_eq!(mask, Some(vec![]));
+}
+
+#[test]
+#[should_panic(expected = "Input and output must have the same strides")]
+fn dropout_rejects_mismatched_strides() {
+    let mut generator = RandomGenerator::new(0);
+    let client = TestRuntime::client(&Default::default());
+
+    let shape = vec![4, 8];
+    let input = TensorHandle::empty(
+        &client,
+        shape.clone(),
+        TestDType::as_type_native_unchecked(),
+    );
+    // Same shape, laid out column-major
+    let output = TensorHandle::new(
+        client.empty(shape.iter().product::<usize>() * size_of::<TestDType>()),
+        shape,
+        vec![1, 4],
+        TestDType::as_type_native_unchecked(),
+    );
+
+    dropout(
+        &client,
+        &mut generator,
+        input.as_ref(),
+        0.5,
+        output.as_ref(),
+        None,
+        TestDType::as_type_native_unchecked(),
+    )
+    .unwrap();
+}
+
+fn get_dropout_data(
+    input: &[TestDType],
+    p: f32,
+    with_mask: bool,
+) -> (Vec<TestDType>, Option<Vec<u32>>) {
+    let mut generator = RandomGenerator::new(0);
+    let client = TestRuntime::client(&Default::default());
+
+    let shape = vec![input.len()];
+    let input = TensorHandle::new_contiguous(
+        shape.clone(),
+        client.create_from_slice(TestDType::as_bytes(input)),
+        TestDType::as_type_native_unchecked(),
+    );
+    let output = TensorHandle::empty(
+        &client,
+        shape.clone(),
+        TestDType::as_type_native_unchecked(),
+    );
+    let mask = with_mask.then(|| {
+        TensorHandle::empty(
+            &client,
+            vec![shape[0].div_ceil(32)],
+            u32::as_type_native_unchecked(),
+        )
+    });
+
+    dropout(
+        &client,
+        &mut generator,
+        input.as_ref(),
+        p,
+        output.as_ref(),
+        mask.as_ref().map(|mask| mask.as_ref()),
+        TestDType::as_type_native_unchecked(),
+    )
+    .unwrap();
+
+    let output_data = client.read_one_tensor(output.as_copy_descriptor());
+    let output_data = TestDType::from_bytes(&output_data).to_owned();
+
+    let mask_data = mask.map(|mask| {
+        let mask_data = client.read_one_tensor(mask.as_copy_descriptor());
+        u32::from_bytes(&mask_data).to_owned()
+    });
+
+    (output_data, mask_data)
+}
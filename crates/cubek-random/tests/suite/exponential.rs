@@ -0,0 +1,48 @@
+use cubecl::TestRuntime;
+use cubecl::prelude::*;
+use cubecl::std::tensor::TensorHandle;
+use cubek_random::*;
+
+#[test]
+fn exponential_respects_cdf() {
+    let rate = 2.;
+    let output_data = get_random_exponential_data(&[1000, 1000], rate);
+
+    assert_respects_cdf(&output_data, 30, 0., 3., |x| 1. - f32::exp(-rate * x));
+}
+
+#[test]
+fn exponential_mean_and_variance() {
+    let rate = 0.5;
+    let output_data = get_random_exponential_data(&[1000, 1000], rate);
+
+    for e in output_data.iter() {
+        assert!(*e >= 0., "Negative value {e}");
+    }
+    assert_mean_and_variance_approx_equal(&output_data, 1. / rate, 1. / (rate * rate));
+}
+
+fn get_random_exponential_data(shape: &[usize], rate: f32) -> Vec<TestDType> {
+    let mut generator = RandomGenerator::new(0);
+
+    let client = TestRuntime::client(&Default::default());
+    let output = TensorHandle::empty(
+        &client,
+        shape.to_vec(),
+        TestDType::as_type_native_unchecked(),
+    );
+
+    random_exponential(
+        &client,
+        &mut generator,
+        rate,
+        output.as_ref(),
+        TestDType::as_type_native_unchecked(),
+    )
+    .unwrap();
+
+    let output_data = client.read_one_tensor(output.as_copy_descriptor());
+    let output_data = TestDType::from_bytes(&output_data);
+
+    output_data.to_owned()
+}
@@ -0,0 +1,57 @@
+use cubecl::TestRuntime;
+use cubecl::prelude::*;
+use cubecl::std::tensor::TensorHandle;
+use cubek_random::*;
+
+#[test]
+fn gamma_mean_and_variance() {
+    let (k, scale) = (3., 2.);
+    let output_data = get_random_gamma_data(&[1000, 1000], k, scale);
+
+    assert_mean_and_variance_approx_equal(&output_data, k * scale, k * scale * scale);
+}
+
+#[test]
+fn gamma_small_shape_mean_and_variance() {
+    // Shapes below 1 take the boosted path
+    let (k, scale) = (0.5, 1.5);
+    let output_data = get_random_gamma_data(&[1000, 1000], k, scale);
+
+    for e in output_data.iter() {
+        assert!(*e >= 0., "Negative value {e}");
+    }
+    assert_mean_and_variance_approx_equal(&output_data, k * scale, k * scale * scale);
+}
+
+#[test]
+fn gamma_shape_one_is_exponential() {
+    let output_data = get_random_gamma_data(&[1000, 1000], 1., 1.);
+
+    assert_respects_cdf(&output_data, 30, 0., 3., |x| 1. - f32::exp(-x));
+}
+
+fn get_random_gamma_data(shape: &[usize], k: f32, scale: f32) -> Vec<TestDType> {
+    let mut generator = RandomGenerator::new(0);
+
+    let client = TestRuntime::client(&Default::default());
+    let output = TensorHandle::empty(
+        &client,
+        shape.to_vec(),
+        TestDType::as_type_native_unchecked(),
+    );
+
+    random_gamma(
+        &client,
+        &mut generator,
+        k,
+        scale,
+        output.as_ref(),
+        TestDType::as_type_native_unchecked(),
+    )
+    .unwrap();
+
+    let output_data = client.read_one_tensor(output.as_copy_descriptor());
+    let output_data = TestDType::from_bytes(&output_data);
+
+    output_data.to_owned()
+}
@@ -0,0 +1,89 @@
+use cubecl::TestRuntime;
+use cubecl::prelude::*;
+use cubecl::std::tensor::TensorHandle;
+use cubek_random::*;
+
+#[test]
+fn launches_advance_the_stream() {
+    let mut generator = RandomGenerator::new(0);
+
+    let first = get_random_uniform_data(&mut generator);
+    let second = get_random_uniform_data(&mut generator);
+
+    assert_eq!(generator.offset(), 2);
+    assert_ne!(first, second);
+}
+
+#[test]
+fn restored_generator_replays_the_stream() {
+    let mut generator = RandomGenerator::new(17);
+    get_random_uniform_data(&mut generator);
+
+    let checkpoint = serde_json::to_string(&generator).unwrap();
+    let expected = get_random_uniform_data(&mut generator);
+
+    let mut restored: RandomGenerator = serde_json::from_str(&checkpoint).unwrap();
+    let actual = get_random_uniform_data(&mut restored);
+
+    assert_eq!(expected, actual);
+    assert_eq!(generator, restored);
+}
+
+#[test]
+fn generators_do_not_interfere() {
+    let mut generator_a = RandomGenerator::new(3);
+    let mut generator_b = RandomGenerator::new(3);
+
+    let expected = get_random_uniform_data(&mut generator_a);
+
+    let mut other = RandomGenerator::new(9);
+    get_random_uniform_data(&mut other);
+
+    assert_eq!(expected, get_random_uniform_data(&mut generator_b));
+}
+
+#[test]
+fn fork_and_split_are_deterministic_and_independent() {
+    let mut generator_a = RandomGenerator::new(5);
+    let mut generator_b = RandomGenerator::new(5);
+
+    let forks_a = generator_a.split(8);
+    let forks_b = generator_b.split(8);
+    assert_eq!(forks_a, forks_b);
+    assert_eq!(generator_a, generator_b);
+
+    for (i, fork) in forks_a.iter().enumerate() {
+        assert_ne!(fork.seed(), generator_a.seed());
+        assert_eq!(fork.offset(), 0);
+        for other in forks_a.iter().skip(i + 1) {
+            assert_ne!(fork.seed(), other.seed());
+        }
+    }
+
+    let mut child_0 = forks_a[0].clone();
+    let mut child_1 = forks_a[1].clone();
+    assert_ne!(
+        get_random_uniform_data(&mut child_0),
+        get_random_uniform_data(&mut child_1)
+    );
+}
+
+fn get_random_uniform_data(generator: &mut RandomGenerator) -> Vec<TestDType> {
+    let client = TestRuntime::client(&Default::default());
+    let output = TensorHandle::empty(&client, vec![64, 64], TestDType::as_type_native_unchecked());
+
+    random_uniform(
+        &client,
+        generator,
+        0.,
+        1.,
+        output.as_ref(),
+        TestDType::as_type_native_unchecked(),
+    )
+    .unwrap();
+
+    let output_data = client.read_one_tensor(output.as_copy_descriptor());
+    let output_data = TestDType::from_bytes(&output_data);
+
+    output_data.to_owned()
+}
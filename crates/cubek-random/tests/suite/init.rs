@@ -0,0 +1,72 @@
+use cubecl::TestRuntime;
+use cubecl::prelude::*;
+use cubecl::std::tensor::TensorHandle;
+use cubek_random::*;
+
+#[test]
+fn gains_match_reference() {
+    assert_eq!(Nonlinearity::Linear.gain(), 1.);
+    assert_eq!(Nonlinearity::Sigmoid.gain(), 1.);
+    assert_eq!(Nonlinearity::Tanh.gain(), 5. / 3.);
+    assert_eq!(Nonlinearity::Relu.gain(), f32::sqrt(2.));
+    assert_eq!(Nonlinearity::LeakyRelu(0.).gain(), f32::sqrt(2.));
+    assert_eq!(Nonlinearity::Selu.gain(), 0.75);
+}
+
+#[test]
+fn fans_of_linear_and_conv_weights() {
+    assert_eq!(calculate_fans(&[64, 32]), (32, 64));
+    assert_eq!(calculate_fans(&[64, 32, 3, 3]), (32 * 9, 64 * 9));
+}
+
+#[test]
+fn kaiming_uniform_within_bound() {
+    let client = TestRuntime::client(&Default::default());
+    let shape = vec![128, 256];
+    let (fan_in, _) = calculate_fans(&shape);
+    let output = TensorHandle::empty(&client, shape, TestDType::as_type_native_unchecked());
+
+    kaiming_uniform(
+        &client,
+        &mut RandomGenerator::new(0),
+        fan_in,
+        Nonlinearity::Relu,
+        output.as_ref(),
+        TestDType::as_type_native_unchecked(),
+    )
+    .unwrap();
+
+    let output_data = client.read_one_tensor(output.as_copy_descriptor());
+    let output_data = TestDType::from_bytes(&output_data);
+
+    let bound = f32::sqrt(2.) * f32::sqrt(3. / fan_in as f32);
+    for e in output_data {
+        assert!((-bound..bound).contains(e), "Not in range, got {}", e);
+    }
+    assert_at_least_one_value_per_bin(output_data, 3, -bound, bound);
+}
+
+#[test]
+fn xavier_normal_respects_68_95_99_rule() {
+    let client = TestRuntime::client(&Default::default());
+    let shape = vec![1000, 1000];
+    let (fan_in, fan_out) = calculate_fans(&shape);
+    let output = TensorHandle::empty(&client, shape, TestDType::as_type_native_unchecked());
+
+    xavier_normal(
+        &client,
+        &mut RandomGenerator::new(0),
+        fan_in,
+        fan_out,
+        1.,
+        output.as_ref(),
+        TestDType::as_type_native_unchecked(),
+    )
+    .unwrap();
+
+    let output_data = client.read_one_tensor(output.as_copy_descriptor());
+    let output_data = TestDType::from_bytes(&output_data);
+
+    let std = f32::sqrt(2. / (fan_in + fan_out) as f32);
+    assert_normal_respects_68_95_99_rule(output_data, 0., std);
+}
@@ -0,0 +1,121 @@
+use cubecl::TestRuntime;
+use cubecl::prelude::*;
+use cubecl::std::tensor::TensorHandle;
+use cubek_random::*;
+
+#[test]
+fn int_small_range_is_uniform() {
+    let output_data = get_random_int_data::<u32>(&[1000, 1000], 0, 10);
+
+    let mut counts = [0usize; 10];
+    for e in output_data {
+        assert!(e < 10, "Value {e} out of range");
+        counts[e as usize] += 1;
+    }
+
+    let expected = 100_000.;
+    let tolerance = 5. * f32::sqrt(expected * 0.9);
+    for (value, count) in counts.iter().enumerate() {
+        assert!(
+            (*count as f32 - expected).abs() <= tolerance,
+            "Value {value} drawn {count} times, expected {expected}"
+        );
+    }
+}
+
+#[test]
+fn int_negative_range() {
+    let output_data = get_random_int_data::<i32>(&[1000, 1000], -1000, 1000);
+
+    for e in output_data.iter() {
+        assert!((-1000..1000).contains(e), "Value {e} out of range");
+    }
+    let data: Vec<f32> = output_data.iter().map(|e| *e as f32).collect();
+    assert_mean_and_variance_approx_equal(&data, -0.5, (2000. * 2000. - 1.) / 12.);
+}
+
+#[test]
+fn int_full_range_i8() {
+    let output_data =
+        get_random_int_data::<i8>(&[256, 1000], i8::MIN as i128, 256 + i8::MIN as i128);
+
+    let mut counts = [0usize; 256];
+    for e in output_data {
+        counts[(e as i32 - i8::MIN as i32) as usize] += 1;
+    }
+    for (value, count) in counts.iter().enumerate() {
+        assert!(
+            (800..1200).contains(count),
+            "Value {} drawn {count} times, expected 1000",
+            value as i32 + i8::MIN as i32
+        );
+    }
+}
+
+#[test]
+fn int_wide_range_i64() {
+    let low = -(1i128 << 40);
+    let high = 1i128 << 41;
+    let output_data = get_random_int_data::<i64>(&[1000, 1000], low, high);
+
+    let mut negatives = 0;
+    for e in output_data.iter() {
+        assert!(
+            (low..high).contains(&(*e as i128)),
+            "Value {e} out of range"
+        );
+        if *e < 0 {
+            negatives += 1;
+        }
+    }
+    // A third of the range is negative
+    assert!(
+        (negatives as f32 - 1_000_000. / 3.).abs() < 3000.,
+        "Got {negatives} negative values"
+    );
+}
+
+#[test]
+fn int_full_range_u64_uses_high_bits() {
+    let output_data = get_random_int_data::<u64>(&[1000, 1000], 0, 1 << 64);
+
+    let above_half = output_data.iter().filter(|e| **e >= 1 << 63).count();
+    let above_u32 = output_data.iter().filter(|e| **e > u32::MAX as u64).count();
+
+    assert!(
+        (above_half as f32 - 500_000.).abs() < 3000.,
+        "Got {above_half} values in the upper half"
+    );
+    assert!(
+        above_u32 > 999_000,
+        "Only {above_u32} values above u32::MAX"
+    );
+}
+
+#[test]
+#[should_panic]
+fn int_range_must_fit_type() {
+    get_random_int_data::<u8>(&[10], 0, 257);
+}
+
+fn get_random_int_data<I: Numeric + CubeElement>(shape: &[usize], low: i128, high: i128) -> Vec<I> {
+    let mut generator = RandomGenerator::new(0);
+
+    let client = TestRuntime::client(&Default::default());
+    let output = TensorHandle::empty(&client, shape.to_vec(), I::as_type_native_unchecked());
+
+    random_int(
+        &client,
+        &mut generator,
+        low,
+        high,
+        output.as_ref(),
+        I::as_type_native_unchecked(),
+    )
+    .unwrap();
+
+    let output_data = client.read_one_tensor(output.as_copy_descriptor());
+    let output_data = I::from_bytes(&output_data);
+
+    output_data.to_owned()
+}
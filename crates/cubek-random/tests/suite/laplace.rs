@@ -0,0 +1,52 @@
+use cubecl::TestRuntime;
+use cubecl::prelude::*;
+use cubecl::std::tensor::TensorHandle;
+use cubek_random::*;
+
+#[test]
+fn laplace_respects_cdf() {
+    let (loc, scale) = (1., 2.);
+    let output_data = get_random_laplace_data(&[1000, 1000], loc, scale);
+
+    assert_respects_cdf(&output_data, 40, -9., 11., |x| {
+        if x < loc {
+            0.5 * f32::exp((x - loc) / scale)
+        } else {
+            1. - 0.5 * f32::exp(-(x - loc) / scale)
+        }
+    });
+    assert_mean_and_variance_approx_equal(&output_data, loc, 2. * scale * scale);
+}
+
+#[test]
+fn runs_test() {
+    let output_data = get_random_laplace_data(&[512, 512], 0., 1.);
+
+    assert_wald_wolfowitz_runs_test(&output_data, -20., 20.);
+}
+
+fn get_random_laplace_data(shape: &[usize], loc: f32, scale: f32) -> Vec<TestDType> {
+    let mut generator = RandomGenerator::new(0);
+
+    let client = TestRuntime::client(&Default::default());
+    let output = TensorHandle::empty(
+        &client,
+        shape.to_vec(),
+        TestDType::as_type_native_unchecked(),
+    );
+
+    random_laplace(
+        &client,
+        &mut generator,
+        loc,
+        scale,
+        output.as_ref(),
+        TestDType::as_type_native_unchecked(),
+    )
+    .unwrap();
+
+    let output_data = client.read_one_tensor(output.as_copy_descriptor());
+    let output_data = TestDType::from_bytes(&output_data);
+
+    output_data.to_owned()
+}
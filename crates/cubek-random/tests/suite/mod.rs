@@ -1,3 +1,7 @@
+// The innermost dimension drives the line size used for the output, so these shapes cover every
+// line size.
+pub const LINE_SIZE_SHAPES: [[usize; 2]; 4] = [[256, 1023], [256, 1022], [256, 1020], [256, 1024]];
+
 mod normal {
     mod f32 {
         type TestDType = f32;
@@ -14,12 +18,114 @@ mod bernoulli {
     include!("bernoulli.rs");
 }
 
+mod generator {
+    type TestDType = f32;
+
+    include!("generator.rs");
+}
+
+mod init {
+    type TestDType = f32;
+
+    include!("init.rs");
+}
+
 mod interval {
     include!("interval.rs");
 }
 
+mod truncated_normal {
+    type TestDType = f32;
+
+    include!("truncated_normal.rs");
+}
+
 mod uniform {
     type TestDType = f32;
 
     include!("uniform.rs");
 }
+
+mod philox {
+    type TestDType = f32;
+
+    include!("philox.rs");
+}
+
+mod exponential {
+    type TestDType = f32;
+
+    include!("exponential.rs");
+}
+
+mod laplace {
+    type TestDType = f32;
+
+    include!("laplace.rs");
+}
+
+mod cauchy {
+    type TestDType = f32;
+
+    include!("cauchy.rs");
+}
+
+mod gamma {
+    type TestDType = f32;
+
+    include!("gamma.rs");
+}
+
+mod beta {
+    type TestDType = f32;
+
+    include!("beta.rs");
+}
+
+mod poisson {
+    type TestDType = f32;
+
+    include!("poisson.rs");
+}
+
+mod categorical {
+    include!("categorical.rs");
+}
+
+mod permutation {
+    include!("permutation.rs");
+}
+
+mod int {
+    include!("int.rs");
+}
+
+mod dropout {
+    type TestDType = f32;
+
+    include!("dropout.rs");
+}
+
+mod stochastic_round {
+    include!("stochastic_round.rs");
+}
+
+mod quasi_random {
+    include!("quasi_random.rs");
+}
+
+mod strided {
+    type TestDType = f32;
+
+    include!("strided.rs");
+}
+
+mod battery {
+    type TestDType = f32;
+
+    include!("battery.rs");
+}
+
+mod reference {
+    include!("reference.rs");
+}
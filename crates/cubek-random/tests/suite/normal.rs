@@ -3,6 +3,8 @@ use cubecl::prelude::*;
 use cubecl::std::tensor::TensorHandle;
 use cubek_random::*;
 
+use crate::suite::LINE_SIZE_SHAPES;
+
 #[test]
 fn empirical_mean_close_to_expectation() {
     let shape = &[100, 100];
@@ -34,8 +36,18 @@ fn normal_respects_68_95_99_rule() {
     assert_normal_respects_68_95_99_rule(&output_data, mu, s);
 }
 
+#[test]
+fn normal_respects_68_95_99_rule_every_line_size() {
+    for shape in LINE_SIZE_SHAPES {
+        let output_data = get_random_normal_data(&shape, 0., 1.);
+
+        assert_normal_respects_68_95_99_rule(&output_data, 0., 1.);
+        assert_wald_wolfowitz_runs_test(&output_data, -10., 10.);
+    }
+}
+
 fn get_random_normal_data(shape: &[usize], mean: f32, std: f32) -> Vec<TestDType> {
-    seed(0);
+    let mut generator = RandomGenerator::new(0);
 
     let client = TestRuntime::client(&Default::default());
     let output = TensorHandle::empty(
@@ -46,6 +58,7 @@ fn get_random_normal_data(shape: &[usize], mean: f32, std: f32) -> Vec<TestDType
 
     random_normal(
         &client,
+        &mut generator,
         mean,
         std,
         output.as_ref(),
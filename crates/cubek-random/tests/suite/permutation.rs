@@ -0,0 +1,189 @@
+use cubecl::TestRuntime;
+use cubecl::prelude::*;
+use cubecl::std::tensor::TensorHandle;
+use cubek_random::*;
+
+#[test]
+fn permutation_contains_every_index() {
+    for n in [1, 2, 7, 1000, 65_537] {
+        let permutation = get_random_permutation(n, &mut RandomGenerator::new(0));
+
+        let mut sorted = permutation.clone();
+        sorted.sort();
+        assert_eq!(sorted, (0..n as u32).collect::<Vec<_>>(), "n = {n}");
+    }
+}
+
+#[test]
+fn permutation_of_no_elements_is_empty() {
+    let permutation = get_random_permutation(0, &mut RandomGenerator::new(0));
+    assert!(permutation.is_empty());
+}
+
+#[test]
+#[should_panic]
+fn permutation_indices_must_fit_type() {
+    let client = TestRuntime::client(&Default::default());
+    // 256 is the largest permutation with u8 indices
+    let output = TensorHandle::empty(&client, vec![257], u8::as_type_native_unchecked());
+
+    random_permutation(
+        &client,
+        &mut RandomGenerator::new(0),
+        output.as_ref(),
+        u8::as_type_native_unchecked(),
+    )
+    .unwrap();
+}
+
+#[test]
+#[should_panic]
+fn permutation_indices_must_be_integers() {
+    let client = TestRuntime::client(&Default::default());
+    let output = TensorHandle::empty(&client, vec![16], f32::as_type_native_unchecked());
+
+    random_permutation(
+        &client,
+        &mut RandomGenerator::new(0),
+        output.as_ref(),
+        f32::as_type_native_unchecked(),
+    )
+    .unwrap();
+}
+
+#[test]
+fn permutation_is_reproducible() {
+    let first = get_random_permutation(1000, &mut RandomGenerator::new(42));
+    let second = get_random_permutation(1000, &mut RandomGenerator::new(42));
+    let other = get_random_permutation(1000, &mut RandomGenerator::new(43));
+
+    assert_eq!(first, second);
+    assert_ne!(first, other);
+}
+
+#[test]
+fn permutation_is_well_mixed() {
+    let n = 100_000;
+    let permutation = get_random_permutation(n, &mut RandomGenerator::new(0));
+
+    // For a uniform permutation, E|perm[i] - i| = (n^2 - 1) / 3n and about one fixed point
+    let mean_displacement = permutation
+        .iter()
+        .enumerate()
+        .map(|(i, p)| (*p as f64 - i as f64).abs())
+        .sum::<f64>()
+        / n as f64;
+    let expected = n as f64 / 3.;
+    assert!(
+        (mean_displacement - expected).abs() < expected * 0.01,
+        "Mean displacement {mean_displacement}, expected {expected}"
+    );
+
+    let fixed_points = permutation
+        .iter()
+        .enumerate()
+        .filter(|(i, p)| **p as usize == *i)
+        .count();
+    assert!(fixed_points < 10, "Too many fixed points: {fixed_points}");
+}
+
+#[test]
+fn permutation_first_position_is_uniform() {
+    let n = 8;
+    let runs = 4000;
+    let mut generator = RandomGenerator::new(0);
+    let mut counts = [0usize; 8];
+
+    for _ in 0..runs {
+        counts[get_random_permutation(n, &mut generator)[0] as usize] += 1;
+    }
+
+    let expected = runs as f32 / n as f32;
+    let tolerance = 5. * f32::sqrt(expected * (1. - 1. / n as f32));
+    for (index, count) in counts.iter().enumerate() {
+        assert!(
+            (*count as f32 - expected).abs() <= tolerance,
+            "Index {index} came first {count} times, expected {expected}"
+        );
+    }
+}
+
+#[test]
+fn shuffle_applies_same_permutation_to_every_slice() {
+    let (rows, cols) = (4, 1000);
+    let data: Vec<f32> = (0..rows * cols).map(|i| i as f32).collect();
+    let output = get_shuffled_data(&data, &[rows, cols], 1);
+
+    let permutation: Vec<usize> = output[..cols].iter().map(|v| *v as usize).collect();
+    let mut sorted = permutation.clone();
+    sorted.sort();
+    assert_eq!(sorted, (0..cols).collect::<Vec<_>>());
+
+    for row in 0..rows {
+        for col in 0..cols {
+            assert_eq!(
+                output[row * cols + col],
+                data[row * cols + permutation[col]],
+                "Mismatch at ({row}, {col})"
+            );
+        }
+    }
+}
+
+#[test]
+fn shuffle_outer_axis_moves_whole_rows() {
+    let (rows, cols) = (500, 3);
+    let data: Vec<f32> = (0..rows * cols).map(|i| i as f32).collect();
+    let output = get_shuffled_data(&data, &[rows, cols], 0);
+
+    let mut seen = vec![false; rows];
+    for row in output.chunks(cols) {
+        let source = row[0] as usize / cols;
+        assert_eq!(row, &data[source * cols..(source + 1) * cols]);
+        assert!(!seen[source], "Row {source} appears twice");
+        seen[source] = true;
+    }
+}
+
+fn get_random_permutation(n: usize, generator: &mut RandomGenerator) -> Vec<u32> {
+    let client = TestRuntime::client(&Default::default());
+    let output = TensorHandle::empty(&client, vec![n], u32::as_type_native_unchecked());
+
+    random_permutation(
+        &client,
+        generator,
+        output.as_ref(),
+        u32::as_type_native_unchecked(),
+    )
+    .unwrap();
+
+    let output_data = client.read_one_tensor(output.as_copy_descriptor());
+
+    u32::from_bytes(&output_data).to_owned()
+}
+
+fn get_shuffled_data(data: &[f32], shape: &[usize], axis: usize) -> Vec<f32> {
+    let mut generator = RandomGenerator::new(0);
+    let client = TestRuntime::client(&Default::default());
+
+    let input = TensorHandle::new_contiguous(
+        shape.to_vec(),
+        client.create_from_slice(f32::as_bytes(data)),
+        f32::as_type_native_unchecked(),
+    );
+    let output = TensorHandle::empty(&client, shape.to_vec(), f32::as_type_native_unchecked());
+
+    shuffle_along_axis(
+        &client,
+        &mut generator,
+        input.as_ref(),
+        axis,
+        output.as_ref(),
+        f32::as_type_native_unchecked(),
+    )
+    .unwrap();
+
+    let output_data = client.read_one_tensor(output.as_copy_descriptor());
+
+    f32::from_bytes(&output_data).to_owned()
+}
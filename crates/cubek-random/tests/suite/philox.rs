@@ -0,0 +1,136 @@
+use cubecl::TestRuntime;
+use cubecl::prelude::*;
+use cubecl::std::tensor::TensorHandle;
+use cubek_random::*;
+
+#[test]
+fn philox_matches_reference_vectors() {
+    // Known-answer vectors from the Random123 distribution (kat_vectors).
+    assert_eq!(
+        philox4x32_10([0, 0, 0, 0], [0, 0]),
+        [0x6627e8d5, 0xe169c58d, 0xbc57ac4c, 0x9b00dbd8]
+    );
+    assert_eq!(
+        philox4x32_10([u32::MAX; 4], [u32::MAX; 2]),
+        [0x408f276d, 0x41c83b0e, 0xa20bc7c6, 0x6d5451fd]
+    );
+    assert_eq!(
+        philox4x32_10(
+            [0x243f6a88, 0x85a308d3, 0x13198a2e, 0x03707344],
+            [0xa4093822, 0x299f31d0]
+        ),
+        [0xd16cfe09, 0x94fdcceb, 0x5001e420, 0x24126ea1]
+    );
+}
+
+#[test]
+fn same_seed_and_offset_are_reproducible() {
+    let first = get_random_uniform_philox_data(&[64, 64], 42, 7);
+    let second = get_random_uniform_philox_data(&[64, 64], 42, 7);
+
+    assert_eq!(first, second);
+}
+
+#[test]
+fn different_offsets_give_different_streams() {
+    let first = get_random_uniform_philox_data(&[64, 64], 42, 7);
+    let second = get_random_uniform_philox_data(&[64, 64], 42, 8);
+
+    let num_equal = first
+        .iter()
+        .zip(second.iter())
+        .filter(|(a, b)| a == b)
+        .count();
+    assert!(num_equal < first.len() / 100, "{num_equal} equal values");
+}
+
+#[test]
+fn values_do_not_depend_on_launch_layout() {
+    // Different sizes lead to different cube counts, values must only depend on the index.
+    let small = get_random_uniform_philox_data(&[1001], 3, 0);
+    let large = get_random_uniform_philox_data(&[512, 512], 3, 0);
+
+    assert_eq!(small[..], large[..small.len()]);
+}
+
+#[test]
+fn uniform_values_within_interval_and_runs_test() {
+    let output_data = get_random_uniform_philox_data(&[512, 512], 0, 0);
+
+    for e in output_data.iter() {
+        assert!((0. ..1.).contains(e), "Not in range, got {}", e);
+    }
+    assert_wald_wolfowitz_runs_test(&output_data, 0., 1.);
+}
+
+#[test]
+fn normal_respects_68_95_99_rule() {
+    let client = TestRuntime::client(&Default::default());
+    let output = TensorHandle::empty(
+        &client,
+        vec![1000, 1000],
+        TestDType::as_type_native_unchecked(),
+    );
+
+    random_normal_philox(
+        &client,
+        5,
+        0,
+        0.,
+        1.,
+        output.as_ref(),
+        TestDType::as_type_native_unchecked(),
+    )
+    .unwrap();
+
+    let output_data = client.read_one_tensor(output.as_copy_descriptor());
+    let output_data = TestDType::from_bytes(&output_data);
+
+    assert_normal_respects_68_95_99_rule(output_data, 0., 1.);
+}
+
+#[test]
+fn number_of_1_proportional_to_prob() {
+    let client = TestRuntime::client(&Default::default());
+    let output = TensorHandle::empty(&client, vec![40, 40], TestDType::as_type_native_unchecked());
+
+    random_bernoulli_philox(
+        &client,
+        5,
+        0,
+        0.7,
+        output.as_ref(),
+        TestDType::as_type_native_unchecked(),
+    )
+    .unwrap();
+
+    let output_data = client.read_one_tensor(output.as_copy_descriptor());
+    let output_data = TestDType::from_bytes(&output_data);
+
+    assert_number_of_1_proportional_to_prob(output_data, 0.7);
+}
+
+fn get_random_uniform_philox_data(shape: &[usize], seed: u64, offset: u64) -> Vec<TestDType> {
+    let client = TestRuntime::client(&Default::default());
+    let output = TensorHandle::empty(
+        &client,
+        shape.to_vec(),
+        TestDType::as_type_native_unchecked(),
+    );
+
+    random_uniform_philox(
+        &client,
+        seed,
+        offset,
+        0.,
+        1.,
+        output.as_ref(),
+        TestDType::as_type_native_unchecked(),
+    )
+    .unwrap();
+
+    let output_data = client.read_one_tensor(output.as_copy_descriptor());
+    let output_data = TestDType::from_bytes(&output_data);
+
+    output_data.to_owned()
+}
@@ -0,0 +1,68 @@
+use cubecl::TestRuntime;
+use cubecl::prelude::*;
+use cubecl::std::tensor::TensorHandle;
+use cubek_random::*;
+
+#[test]
+fn poisson_small_lambda_respects_pmf() {
+    let lambda = 3.;
+    let output_data = get_random_poisson_data(&[1000, 1000], lambda);
+
+    // One bin per integer
+    assert_respects_cdf(&output_data, 15, -0.5, 14.5, |x| poisson_cdf(lambda, x));
+    assert_mean_and_variance_approx_equal(&output_data, lambda, lambda);
+}
+
+#[test]
+fn poisson_large_lambda_respects_pmf() {
+    let lambda = 50.;
+    let output_data = get_random_poisson_data(&[1000, 1000], lambda);
+
+    for e in output_data.iter() {
+        assert_eq!(e.fract(), 0., "Not an integer, got {e}");
+    }
+
+    // One bin per integer
+    assert_respects_cdf(&output_data, 60, 19.5, 79.5, |x| poisson_cdf(lambda, x));
+    assert_mean_and_variance_approx_equal(&output_data, lambda, lambda);
+}
+
+fn poisson_cdf(lambda: f32, x: f32) -> f32 {
+    let lambda = lambda as f64;
+    let mut prob = f64::exp(-lambda);
+    let mut cumulative = 0.;
+
+    for k in 0..=(x.floor() as i64) {
+        if k > 0 {
+            prob *= lambda / k as f64;
+        }
+        cumulative += prob;
+    }
+
+    cumulative as f32
+}
+
+fn get_random_poisson_data(shape: &[usize], lambda: f32) -> Vec<TestDType> {
+    let mut generator = RandomGenerator::new(0);
+
+    let client = TestRuntime::client(&Default::default());
+    let output = TensorHandle::empty(
+        &client,
+        shape.to_vec(),
+        TestDType::as_type_native_unchecked(),
+    );
+
+    random_poisson(
+        &client,
+        &mut generator,
+        lambda,
+        output.as_ref(),
+        TestDType::as_type_native_unchecked(),
+    )
+    .unwrap();
+
+    let output_data = client.read_one_tensor(output.as_copy_descriptor());
+    let output_data = TestDType::from_bytes(&output_data);
+
+    output_data.to_owned()
+}
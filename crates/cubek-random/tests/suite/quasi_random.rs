@@ -0,0 +1,172 @@
+use cubecl::TestRuntime;
+use cubecl::prelude::*;
+use cubecl::std::tensor::TensorHandle;
+use cubek_random::*;
+
+#[test]
+fn sobol_first_points() {
+    let output = get_sobol_data(None, 0, 4, 2);
+
+    assert_eq!(output, [0., 0., 0.5, 0.5, 0.25, 0.75, 0.75, 0.25]);
+}
+
+#[test]
+fn sobol_joe_kuo_first_points() {
+    let output = get_sobol_data(None, 0, 8, 4);
+    #[rustfmt::skip]
+    let expected = [
+        0., 0., 0., 0.,
+        0.5, 0.5, 0.5, 0.5,
+        0.25, 0.75, 0.75, 0.75,
+        0.75, 0.25, 0.25, 0.25,
+        0.125, 0.625, 0.375, 0.125,
+        0.625, 0.125, 0.875, 0.625,
+        0.375, 0.375, 0.625, 0.875,
+        0.875, 0.875, 0.125, 0.375,
+    ];
+
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn halton_first_points() {
+    let output = get_halton_data(None, 0, 4, 2);
+    let expected = [0., 0., 0.5, 1. / 3., 0.25, 2. / 3., 0.75, 1. / 9.];
+
+    for (actual, expected) in output.iter().zip(expected) {
+        assert!((actual - expected).abs() < 1e-6, "{output:?}");
+    }
+}
+
+#[test]
+fn sobol_is_stratified() {
+    let dims = MAX_SOBOL_DIMS;
+    for scrambling in [None, Some(RandomGenerator::new(0))] {
+        let scrambled = scrambling.is_some();
+        let output = get_sobol_data(scrambling, 1024, 1024, dims);
+
+        assert_stratified(&output, dims, 1024, scrambled);
+    }
+}
+
+#[test]
+#[ignore = "needs the full new-joe-kuo-6.21201 table in src/sobol_directions.txt"]
+fn sobol_is_stratified_in_every_dimension() {
+    let dims = MAX_QUASI_RANDOM_DIMS;
+    assert_eq!(MAX_SOBOL_DIMS, dims);
+
+    for scrambling in [None, Some(RandomGenerator::new(0))] {
+        let scrambled = scrambling.is_some();
+        let output = get_sobol_data(scrambling, 1024, 1024, dims);
+
+        assert_stratified(&output, dims, 1024, scrambled);
+    }
+}
+
+#[test]
+fn halton_is_stratified() {
+    let dims = 3;
+    for scrambling in [None, Some(RandomGenerator::new(0))] {
+        let scrambled = scrambling.is_some();
+        let output = get_halton_data(scrambling, 0, 2 * 3 * 5 * 64, dims);
+
+        // The first base^k points of dimension d fill every interval of width base^-k once
+        for (dim, cells) in [(0, 1024), (1, 729), (2, 625)] {
+            let values: Vec<f32> = output.iter().skip(dim).step_by(dims).copied().collect();
+            assert_stratified(&values[..cells], 1, cells, scrambled);
+        }
+    }
+}
+
+#[test]
+fn sobol_chunks_continue_the_sequence() {
+    let whole = get_sobol_data(None, 0, 256, 8);
+    let second_half = get_sobol_data(None, 128, 128, 8);
+
+    assert_eq!(whole[128 * 8..], second_half);
+}
+
+#[test]
+fn scrambled_sequences_are_uniform() {
+    let sobol = get_sobol_data(Some(RandomGenerator::new(1)), 0, 4096, MAX_SOBOL_DIMS);
+    let halton = get_halton_data(Some(RandomGenerator::new(1)), 0, 4096, 64);
+
+    assert_mean_and_variance_approx_equal(&sobol, 0.5, 1. / 12.);
+    assert_mean_and_variance_approx_equal(&halton, 0.5, 1. / 12.);
+}
+
+/// Checks that each dimension has exactly one of the `num_points` points in every interval of
+/// width `1 / num_points`.
+fn assert_stratified(data: &[f32], dims: usize, num_points: usize, scrambled: bool) {
+    for dim in 0..dims {
+        let mut seen = vec![false; num_points];
+        for value in data.iter().skip(dim).step_by(dims) {
+            assert!((0. ..1.).contains(value), "Value {value} out of [0, 1)");
+
+            let cell = (*value as f64 * num_points as f64) as usize;
+            assert!(
+                !seen[cell],
+                "Two points in cell {cell} of dimension {dim} (scrambled: {scrambled})"
+            );
+            seen[cell] = true;
+        }
+    }
+}
+
+fn get_sobol_data(
+    scrambling: Option<RandomGenerator>,
+    first_index: u32,
+    num_points: usize,
+    dims: usize,
+) -> Vec<f32> {
+    get_quasi_random_data(scrambling, num_points, dims, |client, scrambling, out| {
+        sobol_sequence(
+            client,
+            scrambling,
+            first_index,
+            out,
+            f32::as_type_native_unchecked(),
+        )
+    })
+}
+
+fn get_halton_data(
+    scrambling: Option<RandomGenerator>,
+    first_index: u32,
+    num_points: usize,
+    dims: usize,
+) -> Vec<f32> {
+    get_quasi_random_data(scrambling, num_points, dims, |client, scrambling, out| {
+        halton_sequence(
+            client,
+            scrambling,
+            first_index,
+            out,
+            f32::as_type_native_unchecked(),
+        )
+    })
+}
+
+fn get_quasi_random_data(
+    mut scrambling: Option<RandomGenerator>,
+    num_points: usize,
+    dims: usize,
+    launch: impl FnOnce(
+        &ComputeClient<TestRuntime>,
+        Option<&mut RandomGenerator>,
+        TensorHandleRef<TestRuntime>,
+    ) -> Result<(), LaunchError>,
+) -> Vec<f32> {
+    let client = TestRuntime::client(&Default::default());
+    let output = TensorHandle::empty(
+        &client,
+        vec![num_points, dims],
+        f32::as_type_native_unchecked(),
+    );
+
+    launch(&client, scrambling.as_mut(), output.as_ref()).unwrap();
+
+    let output_data = client.read_one_tensor(output.as_copy_descriptor());
+
+    f32::from_bytes(&output_data).to_owned()
+}
@@ -0,0 +1,112 @@
+use cubecl::TestRuntime;
+use cubecl::prelude::*;
+use cubecl::std::tensor::TensorHandle;
+use cubek_random::*;
+
+use crate::suite::LINE_SIZE_SHAPES;
+
+// A small shape on top of the line size ones, to cover a single partial cube.
+const SHAPES: [[usize; 2]; 5] = {
+    let [a, b, c, d] = LINE_SIZE_SHAPES;
+    [[3, 7], a, b, c, d]
+};
+
+#[test]
+fn uniform_matches_reference_bit_for_bit() {
+    for shape in SHAPES {
+        let output_data = get_random_data(&shape, |client, generator, out, dtype| {
+            random_uniform(client, generator, 0., 1., out, dtype)
+        });
+
+        let expected = reference_uniform(&mut RandomGenerator::new(0), 0., 1., num_elems(&shape));
+
+        assert_eq!(output_data, expected);
+    }
+}
+
+#[test]
+fn bernoulli_matches_reference_bit_for_bit() {
+    for shape in SHAPES {
+        let output_data = get_random_data(&shape, |client, generator, out, dtype| {
+            random_bernoulli(client, generator, 0.3, out, dtype)
+        });
+
+        let expected = reference_bernoulli(&mut RandomGenerator::new(0), 0.3, num_elems(&shape));
+
+        assert_eq!(output_data, expected);
+    }
+}
+
+#[test]
+fn normal_matches_reference() {
+    for shape in SHAPES {
+        let output_data = get_random_data(&shape, |client, generator, out, dtype| {
+            random_normal(client, generator, 1., 2., out, dtype)
+        });
+
+        let expected = reference_normal(&mut RandomGenerator::new(0), 1., 2., num_elems(&shape));
+
+        assert_eq!(output_data.len(), expected.len());
+        for (actual, expected) in output_data.iter().zip(expected) {
+            assert!(
+                (actual - expected).abs() <= 1e-5 * expected.abs().max(1.),
+                "Got {actual}, expected {expected}"
+            );
+        }
+    }
+}
+
+#[test]
+fn reference_advances_generator_like_launch() {
+    let shape = [16, 16];
+    let mut generator = RandomGenerator::new(0);
+
+    let client = TestRuntime::client(&Default::default());
+    let output = TensorHandle::empty(&client, shape.to_vec(), f32::as_type_native_unchecked());
+    random_uniform(
+        &client,
+        &mut generator,
+        0.,
+        1.,
+        output.as_ref(),
+        f32::as_type_native_unchecked(),
+    )
+    .unwrap();
+
+    let mut reference_generator = RandomGenerator::new(0);
+    reference_uniform(&mut reference_generator, 0., 1., num_elems(&shape));
+
+    assert_eq!(generator, reference_generator);
+}
+
+fn num_elems(shape: &[usize]) -> usize {
+    shape.iter().product()
+}
+
+fn get_random_data(
+    shape: &[usize],
+    launch: impl Fn(
+        &ComputeClient<TestRuntime>,
+        &mut RandomGenerator,
+        TensorHandleRef<TestRuntime>,
+        StorageType,
+    ) -> Result<(), LaunchError>,
+) -> Vec<f32> {
+    let mut generator = RandomGenerator::new(0);
+
+    let client = TestRuntime::client(&Default::default());
+    let output = TensorHandle::empty(&client, shape.to_vec(), f32::as_type_native_unchecked());
+
+    launch(
+        &client,
+        &mut generator,
+        output.as_ref(),
+        f32::as_type_native_unchecked(),
+    )
+    .unwrap();
+
+    let output_data = client.read_one_tensor(output.as_copy_descriptor());
+    let output_data = f32::from_bytes(&output_data);
+
+    output_data.to_owned()
+}
@@ -0,0 +1,122 @@
+use cubecl::TestRuntime;
+use cubecl::prelude::*;
+use cubecl::std::tensor::TensorHandle;
+use cubek_random::*;
+use half::{bf16, f16};
+
+#[test]
+fn stochastic_round_f16_is_unbiased() {
+    let spacing = f32::powi(2., -10);
+    let value = 1. + 0.25 * spacing;
+    let output = get_rounded_data::<f16>(&vec![value; 1_000_000]);
+    let output: Vec<f32> = output.iter().map(|e| e.to_f32()).collect();
+
+    assert_rounded_between(&output, 1., 1. + spacing, 0.25);
+}
+
+#[test]
+fn stochastic_round_bf16_is_unbiased() {
+    let spacing = f32::powi(2., -7) * 8.;
+    let value = -(8. + 0.7 * spacing);
+    let output = get_rounded_data::<bf16>(&vec![value; 1_000_000]);
+    let output: Vec<f32> = output.iter().map(|e| -e.to_f32()).collect();
+
+    assert_rounded_between(&output, 8., 8. + spacing, 0.7);
+}
+
+#[test]
+fn stochastic_round_f16_subnormal_is_unbiased() {
+    let spacing = f32::powi(2., -24);
+    let value = 3.5 * spacing;
+    let output = get_rounded_data::<f16>(&vec![value; 1_000_000]);
+    let output: Vec<f32> = output.iter().map(|e| e.to_f32()).collect();
+
+    assert_rounded_between(&output, 3. * spacing, 4. * spacing, 0.5);
+}
+
+#[test]
+fn stochastic_round_keeps_representable_values() {
+    let input: Vec<f32> = (-500..500).map(|i| i as f32 * 0.125).collect();
+    let output = get_rounded_data::<f16>(&input);
+
+    for (x, y) in input.iter().zip(output.iter()) {
+        assert_eq!(*x, y.to_f32());
+    }
+}
+
+#[test]
+fn stochastic_round_saturates() {
+    let output = get_rounded_data::<f16>(&[1e6, -1e6, f32::INFINITY]);
+
+    assert_eq!(output, [f16::MAX, f16::MIN, f16::MAX]);
+}
+
+#[test]
+fn stochastic_round_scalar() {
+    let output = get_rounded_data_with_shape::<f16>(&[0.125], vec![]);
+
+    assert_eq!(output, [f16::from_f32(0.125)]);
+}
+
+#[test]
+fn stochastic_round_does_not_depend_on_line_size() {
+    // The odd last dimension prevents vectorization
+    let spacing = f32::powi(2., -10);
+    let input = vec![1. + 0.5 * spacing; 3 * 1024];
+    let vectorized = get_rounded_data_with_shape::<f16>(&input, vec![3 * 1024]);
+    let unvectorized = get_rounded_data_with_shape::<f16>(&input, vec![1024, 3]);
+
+    assert_eq!(vectorized, unvectorized);
+}
+
+/// Checks every value is one of the two neighbours, and that the upper one is picked with
+/// probability `p_up`.
+fn assert_rounded_between(data: &[f32], lower: f32, upper: f32, p_up: f32) {
+    let mut num_up = 0;
+    for e in data {
+        assert!(
+            *e == lower || *e == upper,
+            "Value {e} is neither {lower} nor {upper}"
+        );
+        if *e == upper {
+            num_up += 1;
+        }
+    }
+
+    let n = data.len() as f32;
+    let tolerance = 5. * f32::sqrt(n * p_up * (1. - p_up));
+    assert!(
+        (num_up as f32 - n * p_up).abs() < tolerance,
+        "Rounded up {num_up} times, expected {}",
+        n * p_up
+    );
+}
+
+fn get_rounded_data<E: Float + CubeElement>(input: &[f32]) -> Vec<E> {
+    get_rounded_data_with_shape(input, vec![input.len()])
+}
+
+fn get_rounded_data_with_shape<E: Float + CubeElement>(input: &[f32], shape: Vec<usize>) -> Vec<E> {
+    let mut generator = RandomGenerator::new(0);
+    let client = TestRuntime::client(&Default::default());
+
+    let input = TensorHandle::new_contiguous(
+        shape.clone(),
+        client.create_from_slice(f32::as_bytes(input)),
+        f32::as_type_native_unchecked(),
+    );
+    let output = TensorHandle::empty(&client, shape, E::as_type_native_unchecked());
+
+    stochastic_round(
+        &client,
+        &mut generator,
+        input.as_ref(),
+        output.as_ref(),
+        E::as_type_native_unchecked(),
+    )
+    .unwrap();
+
+    let output_data = client.read_one_tensor(output.as_copy_descriptor());
+
+    E::from_bytes(&output_data).to_owned()
+}
@@ -0,0 +1,114 @@
+use cubecl::TestRuntime;
+use cubecl::prelude::*;
+use cubecl::std::tensor::TensorHandle;
+use cubek_random::*;
+
+#[test]
+fn transposed_output_matches_contiguous() {
+    let (rows, cols) = (64, 48);
+    let contiguous = get_random_uniform_data(&[rows, cols]);
+
+    // Column-major buffer, element (i, j) at offset i + j * rows
+    let buffer = read_random_uniform_buffer(&[rows, cols], &[1, rows], rows * cols);
+
+    for i in 0..rows {
+        for j in 0..cols {
+            assert_eq!(buffer[i + j * rows], contiguous[i * cols + j]);
+        }
+    }
+}
+
+#[test]
+fn slice_output_leaves_rest_of_buffer_untouched() {
+    let (rows, cols, buffer_cols) = (64, 32, 96);
+    let contiguous = get_random_uniform_data(&[rows, cols]);
+
+    // First columns of a wider buffer, like the query part of a fused QKV weight
+    let buffer = read_random_uniform_buffer(&[rows, cols], &[buffer_cols, 1], rows * buffer_cols);
+
+    for i in 0..rows {
+        for j in 0..buffer_cols {
+            let value = buffer[i * buffer_cols + j];
+            if j < cols {
+                assert_eq!(value, contiguous[i * cols + j]);
+            } else {
+                assert_eq!(
+                    value, SENTINEL,
+                    "Element ({i}, {j}) outside the slice was written"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn broadcast_output_written_with_first_index() {
+    let (rows, cols) = (16, 32);
+    let contiguous = get_random_uniform_data(&[rows, cols]);
+
+    let buffer = read_random_uniform_buffer(&[rows, cols], &[0, 1], cols);
+
+    assert_eq!(buffer, contiguous[..cols]);
+}
+
+const SENTINEL: TestDType = -1.0;
+
+fn get_random_uniform_data(shape: &[usize]) -> Vec<TestDType> {
+    let mut generator = RandomGenerator::new(0);
+    let client = TestRuntime::client(&Default::default());
+    let output = TensorHandle::empty(
+        &client,
+        shape.to_vec(),
+        TestDType::as_type_native_unchecked(),
+    );
+
+    random_uniform(
+        &client,
+        &mut generator,
+        0.,
+        1.,
+        output.as_ref(),
+        TestDType::as_type_native_unchecked(),
+    )
+    .unwrap();
+
+    let output_data = client.read_one_tensor(output.as_copy_descriptor());
+    let output_data = TestDType::from_bytes(&output_data);
+
+    output_data.to_owned()
+}
+
+/// Generates into a view with the given strides over a buffer of `buffer_len` elements, all
+/// initialized to [SENTINEL], and reads back the whole buffer.
+fn read_random_uniform_buffer(
+    shape: &[usize],
+    strides: &[usize],
+    buffer_len: usize,
+) -> Vec<TestDType> {
+    let mut generator = RandomGenerator::new(0);
+    let client = TestRuntime::client(&Default::default());
+    let buffer = client.create_from_slice(TestDType::as_bytes(&vec![SENTINEL; buffer_len]));
+
+    let output =
+        unsafe { TensorHandleRef::from_raw_parts(&buffer, strides, shape, size_of::<TestDType>()) };
+
+    random_uniform(
+        &client,
+        &mut generator,
+        0.,
+        1.,
+        output,
+        TestDType::as_type_native_unchecked(),
+    )
+    .unwrap();
+
+    let buffer = TensorHandle::new_contiguous(
+        vec![buffer_len],
+        buffer,
+        TestDType::as_type_native_unchecked(),
+    );
+    let output_data = client.read_one_tensor(buffer.as_copy_descriptor());
+    let output_data = TestDType::from_bytes(&output_data);
+
+    output_data.to_owned()
+}
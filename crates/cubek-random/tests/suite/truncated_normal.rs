@@ -0,0 +1,79 @@
+use cubecl::TestRuntime;
+use cubecl::prelude::*;
+use cubecl::std::tensor::TensorHandle;
+use cubek_random::*;
+
+#[test]
+fn values_all_within_interval() {
+    let output_data = get_random_truncated_normal_data(&[100, 100], 1., 2., -1., 2.);
+
+    for e in output_data {
+        assert!((-1. ..=2.).contains(&e), "Not in range, got {}", e);
+    }
+}
+
+#[test]
+fn symmetric_truncation_keeps_mean() {
+    let output_data = get_random_truncated_normal_data(&[1000, 1000], 3., 1., 1., 5.);
+
+    assert_mean_approx_equal(&output_data, 3.);
+}
+
+#[test]
+fn half_normal_mean() {
+    let std = 2.;
+    let output_data = get_random_truncated_normal_data(&[1000, 1000], 0., std, 0., f32::INFINITY);
+
+    // Mean of the half-normal distribution
+    let expected_mean = std * f32::sqrt(2. / core::f32::consts::PI);
+    assert_mean_approx_equal(&output_data, expected_mean);
+}
+
+#[test]
+fn wide_interval_respects_68_95_99_rule() {
+    // Truncation far in the tails has almost no effect on the bulk of the distribution.
+    let output_data = get_random_truncated_normal_data(&[1000, 1000], 0., 1., -10., 10.);
+
+    assert_normal_respects_68_95_99_rule(&output_data, 0., 1.);
+}
+
+#[test]
+fn runs_test() {
+    let output_data = get_random_truncated_normal_data(&[512, 512], 0., 1., -2., 2.);
+
+    assert_wald_wolfowitz_runs_test(&output_data, -2., 2.);
+}
+
+fn get_random_truncated_normal_data(
+    shape: &[usize],
+    mean: f32,
+    std: f32,
+    low: f32,
+    high: f32,
+) -> Vec<TestDType> {
+    let mut generator = RandomGenerator::new(0);
+
+    let client = TestRuntime::client(&Default::default());
+    let output = TensorHandle::empty(
+        &client,
+        shape.to_vec(),
+        TestDType::as_type_native_unchecked(),
+    );
+
+    random_truncated_normal(
+        &client,
+        &mut generator,
+        mean,
+        std,
+        low,
+        high,
+        output.as_ref(),
+        TestDType::as_type_native_unchecked(),
+    )
+    .unwrap();
+
+    let output_data = client.read_one_tensor(output.as_copy_descriptor());
+    let output_data = TestDType::from_bytes(&output_data);
+
+    output_data.to_owned()
+}
@@ -3,6 +3,8 @@ use cubecl::prelude::*;
 use cubecl::std::tensor::TensorHandle;
 use cubek_random::*;
 
+use crate::suite::LINE_SIZE_SHAPES;
+
 #[test]
 fn values_all_within_interval_uniform() {
     let shape = &[24, 24];
@@ -35,6 +37,24 @@ fn runs_test() {
     assert_wald_wolfowitz_runs_test(&output_data, 0., 1.);
 }
 
+#[test]
+fn runs_test_every_line_size() {
+    for shape in LINE_SIZE_SHAPES {
+        let output_data = get_random_uniform_data(&shape, 0., 1.);
+
+        assert_wald_wolfowitz_runs_test(&output_data, 0., 1.);
+        assert_at_least_one_value_per_bin(&output_data, 10, 0., 1.);
+    }
+}
+
+#[test]
+fn scalar_output_uniform() {
+    let output_data = get_random_uniform_data(&[], 5., 17.);
+
+    assert_eq!(output_data.len(), 1);
+    assert!((5. ..17.).contains(&output_data[0]));
+}
+
 #[test]
 fn at_least_one_value_per_bin_int_uniform() {
     let shape = &[64, 64];
@@ -44,7 +64,7 @@ fn at_least_one_value_per_bin_int_uniform() {
 }
 
 fn get_random_uniform_data(shape: &[usize], lower_bound: f32, upper_bound: f32) -> Vec<TestDType> {
-    seed(0);
+    let mut generator = RandomGenerator::new(0);
     let client = TestRuntime::client(&Default::default());
     let output = TensorHandle::empty(
         &client,
@@ -54,6 +74,7 @@ fn get_random_uniform_data(shape: &[usize], lower_bound: f32, upper_bound: f32)
 
     random_uniform(
         &client,
+        &mut generator,
         lower_bound,
         upper_bound,
         output.as_ref(),
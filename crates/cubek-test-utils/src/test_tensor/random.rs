@@ -14,18 +14,28 @@ fn random_tensor_handle(
 ) -> TensorHandle<TestRuntime> {
     assert_eq!(tensor_shape.len(), strides.len());
 
-    cubek_random::seed(seed);
+    let mut generator = cubek_random::RandomGenerator::new(seed);
     let flat_len: usize = tensor_shape.iter().product();
     let tensor_handle = TensorHandle::empty(client, vec![flat_len], dtype);
 
     match distribution {
-        Distribution::Uniform(lower, upper) => {
-            cubek_random::random_uniform(client, lower, upper, tensor_handle.as_ref(), dtype)
-                .unwrap()
-        }
-        Distribution::Bernoulli(prob) => {
-            cubek_random::random_bernoulli(client, prob, tensor_handle.as_ref(), dtype).unwrap()
-        }
+        Distribution::Uniform(lower, upper) => cubek_random::random_uniform(
+            client,
+            &mut generator,
+            lower,
+            upper,
+            tensor_handle.as_ref(),
+            dtype,
+        )
+        .unwrap(),
+        Distribution::Bernoulli(prob) => cubek_random::random_bernoulli(
+            client,
+            &mut generator,
+            prob,
+            tensor_handle.as_ref(),
+            dtype,
+        )
+        .unwrap(),
     }
 
     TensorHandle::new(